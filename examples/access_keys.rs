@@ -1,5 +1,4 @@
-use near_jsonrpc_client::methods;
-use near_jsonrpc_primitives::types::query::QueryResponseKind;
+use near_jsonrpc_client::views::AccessKeyFilter;
 use near_primitives::types::BlockReference;
 
 mod utils;
@@ -23,22 +22,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let account_id = utils::input("Enter the Account ID whose keys we're listing: ")?.parse()?;
 
-    let access_key_query_response = client
-        .call(methods::query::RpcQueryRequest {
-            block_reference: BlockReference::latest(),
-            request: near_primitives::views::QueryRequest::ViewAccessKeyList { account_id },
-        })
+    let access_keys = client
+        .view_access_key_list(account_id, AccessKeyFilter::All, BlockReference::latest())
         .await?;
 
-    if let QueryResponseKind::AccessKeyList(response) = access_key_query_response.kind {
-        for access_key in response.keys {
-            println!("🗝 [{}]", access_key.public_key);
-            println!("     \u{21b3}      nonce: {}", access_key.access_key.nonce);
-            println!(
-                "     \u{21b3} permission: {}",
-                indent(20, format!("{:#?}", access_key.access_key.permission))
-            );
-        }
+    for access_key in access_keys {
+        println!("🗝 [{}]", access_key.public_key);
+        println!("     \u{21b3}      nonce: {}", access_key.access_key.nonce);
+        println!(
+            "     \u{21b3} permission: {}",
+            indent(20, format!("{:#?}", access_key.access_key.permission))
+        );
     }
 
     Ok(())
@@ -1,6 +1,5 @@
 use near_crypto::Signer;
 use near_jsonrpc_client::{methods, JsonRpcClient};
-use near_jsonrpc_primitives::types::query::QueryResponseKind;
 use near_primitives::transaction::{Action, FunctionCallAction, Transaction, TransactionV0};
 use near_primitives::types::BlockReference;
 
@@ -19,30 +18,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let signer = near_crypto::InMemorySigner::from_secret_key(signer_account_id, signer_secret_key);
 
-    let access_key_query_response = client
-        .call(methods::query::RpcQueryRequest {
-            block_reference: BlockReference::latest(),
-            request: near_primitives::views::QueryRequest::ViewAccessKey {
-                account_id: signer.account_id.clone(),
-                public_key: signer.public_key.clone(),
-            },
-        })
+    let (access_key, block_hash, _) = client
+        .view_access_key(
+            signer.account_id.clone(),
+            signer.public_key.clone(),
+            BlockReference::latest(),
+        )
         .await?;
 
-    let current_nonce = match access_key_query_response.kind {
-        QueryResponseKind::AccessKey(access_key) => access_key.nonce,
-        _ => Err("failed to extract current nonce")?,
-    };
-
     let other_account = utils::input("Enter the account to be rated: ")?;
     let rating = utils::input("Enter a rating: ")?.parse::<f32>()?;
 
     let transaction = TransactionV0 {
         signer_id: signer.account_id.clone(),
         public_key: signer.public_key.clone(),
-        nonce: current_nonce + 1,
+        nonce: access_key.nonce + 1,
         receiver_id: "nosedive.testnet".parse()?,
-        block_hash: access_key_query_response.block_hash,
+        block_hash,
         actions: vec![Action::FunctionCall(Box::new(FunctionCallAction {
             method_name: "rate".to_string(),
             args: json!({
@@ -58,6 +50,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let request = methods::broadcast_tx_commit::RpcBroadcastTxCommitRequest {
         signed_transaction: Transaction::V0(transaction).sign(&Signer::InMemory(signer)),
+        wait_until: None,
     };
 
     let response = client.call(request).await?;
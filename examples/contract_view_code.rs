@@ -1,7 +1,4 @@
-use near_jsonrpc_client::methods;
-use near_jsonrpc_primitives::types::query::QueryResponseKind;
 use near_primitives::types::{AccountId, BlockReference, Finality};
-use near_primitives::views::QueryRequest;
 
 mod utils;
 
@@ -14,22 +11,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let contract_id: AccountId =
         utils::input("Enter the contract whose code we're downloading: ")?.parse()?;
 
-    let request = methods::query::RpcQueryRequest {
-        block_reference: BlockReference::Finality(Finality::Final),
-        request: QueryRequest::ViewCode {
-            account_id: contract_id.clone(),
-        },
-    };
-
-    let response = client.call(request).await?;
-
-    if let QueryResponseKind::ViewCode(result) = response.kind {
-        let path = format!("/tmp/{}.wasm", contract_id);
-        println!("⚙️  [{}]", contract_id);
-        println!("🏋        size: {} bytes", result.code.len());
-        std::fs::write(&path, result.code)?;
-        println!("💾   saved to: {}", path);
-    }
+    let (code, hash) = client
+        .view_code(
+            contract_id.clone(),
+            BlockReference::Finality(Finality::Final),
+        )
+        .await?;
+
+    let path = format!("/tmp/{}.wasm", contract_id);
+    println!("⚙️  [{}]", contract_id);
+    println!("🏋        size: {} bytes", code.len());
+    println!("🔖        hash: {}", hash);
+    std::fs::write(&path, code)?;
+    println!("💾   saved to: {}", path);
 
     Ok(())
 }
@@ -1,10 +1,8 @@
-use near_jsonrpc_client::{methods, JsonRpcClient};
-use near_jsonrpc_primitives::types::query::QueryResponseKind;
-use near_primitives::types::{BlockReference, Finality, FunctionArgs};
-use near_primitives::views::QueryRequest;
+use near_jsonrpc_client::JsonRpcClient;
+use near_primitives::types::{BlockReference, Finality};
 
 use serde::Deserialize;
-use serde_json::{from_slice, json};
+use serde_json::json;
 
 mod utils;
 
@@ -23,26 +21,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let account_id = utils::input("Enter the account to view: ")?;
 
-    let request = methods::query::RpcQueryRequest {
-        block_reference: BlockReference::Finality(Finality::Final),
-        request: QueryRequest::CallFunction {
-            account_id: "nosedive.testnet".parse()?,
-            method_name: "status".to_string(),
-            args: FunctionArgs::from(
-                json!({
-                    "account_id": account_id,
-                })
-                .to_string()
-                .into_bytes(),
-            ),
-        },
-    };
-
-    let response = client.call(request).await?;
-
-    if let QueryResponseKind::CallResult(result) = response.kind {
-        println!("{:#?}", from_slice::<AccountStatus>(&result.result)?);
-    }
+    let status = client
+        .view_function::<AccountStatus>(
+            "nosedive.testnet".parse()?,
+            "status",
+            json!({
+                "account_id": account_id,
+            }),
+            BlockReference::Finality(Finality::Final),
+        )
+        .await?;
+
+    println!("{:#?}", status.result);
 
     Ok(())
 }
@@ -1,7 +1,4 @@
-use near_jsonrpc_client::methods;
-use near_jsonrpc_primitives::types::query::QueryResponseKind;
 use near_primitives::types::{AccountId, BlockReference, Finality};
-use near_primitives::views::QueryRequest;
 
 mod utils;
 
@@ -14,19 +11,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let contract_id: AccountId =
         utils::input("Enter the contract whose state you want to inspect: ")?.parse()?;
 
-    let request = methods::query::RpcQueryRequest {
-        block_reference: BlockReference::Finality(Finality::Final),
-        request: QueryRequest::ViewState {
-            account_id: contract_id.clone(),
-            prefix: near_primitives::types::StoreKey::from(Vec::new()),
-            include_proof: false,
-        },
-    };
+    let state = client
+        .view_state(contract_id, b"", BlockReference::Finality(Finality::Final))
+        .await?;
 
-    let response = client.call(request).await?;
-
-    if let QueryResponseKind::ViewState(result) = response.kind {
-        println!("{:#?}", result);
+    for (key, value) in state {
+        println!(
+            "{}: {}",
+            String::from_utf8_lossy(&key),
+            String::from_utf8_lossy(&value)
+        );
     }
 
     Ok(())
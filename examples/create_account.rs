@@ -11,7 +11,7 @@
 
 use near_crypto::Signer;
 use near_jsonrpc_client::methods::broadcast_tx_commit::RpcTransactionError;
-use near_jsonrpc_client::{methods, JsonRpcClient};
+use near_jsonrpc_client::{methods, providers::Network, JsonRpcClient};
 use near_jsonrpc_primitives::types::query::QueryResponseKind;
 use near_jsonrpc_primitives::types::transactions::TransactionInfo;
 use near_primitives::hash::CryptoHash;
@@ -31,24 +31,11 @@ async fn account_exists(
     client: &JsonRpcClient,
     account_id: &AccountId,
 ) -> Result<bool, Box<dyn std::error::Error>> {
-    let access_key_query_response = client
-        .call(methods::query::RpcQueryRequest {
-            block_reference: BlockReference::latest(),
-            request: near_primitives::views::QueryRequest::ViewAccount {
-                account_id: account_id.clone(),
-            },
-        })
-        .await;
-
-    match access_key_query_response {
-        Ok(_) => Ok(true),
-        Err(near_jsonrpc_client::errors::JsonRpcError::ServerError(
-            near_jsonrpc_client::errors::JsonRpcServerError::HandlerError(
-                near_jsonrpc_primitives::types::query::RpcQueryError::UnknownAccount { .. },
-            ),
-        )) => Ok(false),
-        Err(res) => Err(res)?,
-    }
+    Ok(client
+        .account(account_id.clone())
+        .at(BlockReference::latest())
+        .exists()
+        .await?)
 }
 
 async fn get_current_nonce(
@@ -159,12 +146,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             vec![],
         )
     } else {
-        let contract_id = if client.server_addr().ends_with("testnet.near.org") {
-            "testnet".parse()?
-        } else if client.server_addr().ends_with("mainnet.near.org") {
-            "near".parse()?
-        } else {
-            Err("can only create non-sub accounts for mainnet / testnet\nconsider creating a sub-account instead")?
+        let contract_id = match client.network().await? {
+            Network::Testnet => "testnet".parse()?,
+            Network::Mainnet => "near".parse()?,
+            _ => Err("can only create non-sub accounts for mainnet / testnet\nconsider creating a sub-account instead")?,
         };
         (
             TransactionV0 {
@@ -198,6 +183,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let request = methods::broadcast_tx_async::RpcBroadcastTxAsyncRequest {
         signed_transaction: Transaction::V0(transaction).sign(&Signer::InMemory(signer.clone())),
+        wait_until: None,
     };
 
     let sent_at = time::Instant::now();
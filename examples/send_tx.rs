@@ -1,6 +1,6 @@
-use near_jsonrpc_client::{methods, JsonRpcClient};
-use near_jsonrpc_primitives::types::query::QueryResponseKind;
-use near_jsonrpc_primitives::types::transactions::{RpcTransactionError, TransactionInfo};
+use std::time::Duration;
+
+use near_jsonrpc_client::{Deadline, JsonRpcClient};
 use near_primitives::transaction::{Action, FunctionCallAction, Transaction, TransactionV0};
 use near_primitives::types::BlockReference;
 use near_primitives::views::TxExecutionStatus;
@@ -21,30 +21,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let signer = near_crypto::InMemorySigner::from_secret_key(signer_account_id, signer_secret_key);
 
-    let access_key_query_response = client
-        .call(methods::query::RpcQueryRequest {
-            block_reference: BlockReference::latest(),
-            request: near_primitives::views::QueryRequest::ViewAccessKey {
-                account_id: signer.account_id.clone(),
-                public_key: signer.public_key.clone(),
-            },
-        })
+    let (access_key, block_hash, _) = client
+        .view_access_key(
+            signer.account_id.clone(),
+            signer.public_key.clone(),
+            BlockReference::latest(),
+        )
         .await?;
 
-    let current_nonce = match access_key_query_response.kind {
-        QueryResponseKind::AccessKey(access_key) => access_key.nonce,
-        _ => Err("failed to extract current nonce")?,
-    };
-
     let other_account = utils::input("Enter the account to be rated: ")?;
     let rating = utils::input("Enter a rating: ")?.parse::<f32>()?;
 
     let transaction = Transaction::V0(TransactionV0 {
         signer_id: signer.account_id.clone(),
         public_key: signer.public_key.clone(),
-        nonce: current_nonce + 1,
+        nonce: access_key.nonce + 1,
         receiver_id: "nosedive.testnet".parse()?,
-        block_hash: access_key_query_response.block_hash,
+        block_hash,
         actions: vec![Action::FunctionCall(Box::new(FunctionCallAction {
             method_name: "rate".to_string(),
             args: serde_json::json!({
@@ -57,50 +50,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             deposit: 0,
         }))],
     });
-    let tx_hash = transaction.get_hash_and_size().0;
-
-    let request = methods::send_tx::RpcSendTransactionRequest {
-        signed_transaction: transaction.sign(&near_crypto::Signer::InMemory(signer.clone())),
-        wait_until: wait_until.clone(),
-    };
 
     let sent_at = time::Instant::now();
-    let response = match client.call(request).await {
-        Ok(response) => response,
-        Err(err) => {
-            match err.handler_error() {
-                Some(RpcTransactionError::TimeoutError) => {}
-                _ => Err(err)?,
-            }
-            loop {
-                let response = client
-                    .call(methods::tx::RpcTransactionStatusRequest {
-                        transaction_info: TransactionInfo::TransactionId {
-                            tx_hash,
-                            sender_account_id: signer.account_id.clone(),
-                        },
-                        wait_until: wait_until.clone(),
-                    })
-                    .await;
-                let received_at = time::Instant::now();
-                let delta = (received_at - sent_at).as_secs();
-
-                if delta > 60 {
-                    Err("time limit exceeded for the transaction to be recognized")?;
-                }
-
-                match response {
-                    Err(err) => match err.handler_error() {
-                        Some(RpcTransactionError::TimeoutError) => {}
-                        _ => Err(err)?,
-                    },
-                    Ok(response) => {
-                        break response;
-                    }
-                }
-            }
-        }
-    };
+    let response = client
+        .send_and_wait(
+            transaction.sign(&near_crypto::Signer::InMemory(signer.clone())),
+            wait_until,
+            Deadline::after(Duration::from_secs(60)),
+        )
+        .await?;
 
     let received_at = time::Instant::now();
     let delta = (received_at - sent_at).as_secs();
@@ -70,7 +70,7 @@ pub fn select_network() -> io::Result<JsonRpcClient> {
     } else {
         loop {
             let url = input("Enter the RPC Server Address: ")?;
-            if let Err(err) = url.parse::<reqwest::Url>() {
+            if let Err(err) = url.parse::<url::Url>() {
                 println!("\x1b[31m(i)\x1b[0m invalid url ({}), retry..", err);
                 continue;
             }
@@ -0,0 +1,753 @@
+//! Contract calls checked against the ABI of the contract.
+//!
+//! Contracts built with `cargo near` embed an ABI, in the [near-abi](https://github.com/near/abi)
+//! format, describing their functions: whether they are view functions or calls, whether they
+//! accept deposits, and the JSON schemas of their arguments and results.
+//!
+//! A [`ContractAbi`] is deserialized from the JSON of the ABI, and attached to a [`ContractRef`]
+//! with [`ContractRef::with_abi`]. The resulting [`AbiContract`] checks the arguments of view
+//! functions and function call actions against their schema before they reach the network, and
+//! the results of view functions before decoding them, catching mistakes in the arguments on the
+//! client side instead of in a failed transaction.
+//!
+//! Only functions (de)serializing their arguments and results as JSON can be checked. The schemas
+//! are checked for the subset of JSON Schema the ABI generator produces: types, references,
+//! enumerations, `anyOf`/`oneOf`/`allOf`, objects and arrays.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::abi::ContractAbi;
+//! use near_jsonrpc_client::JsonRpcClient;
+//! use serde_json::json;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let abi = std::fs::read_to_string("status_message_abi.json")?;
+//! let abi: ContractAbi = serde_json::from_str(&abi)?;
+//! let contract = client.contract("nosedive.testnet".parse()?).with_abi(abi);
+//!
+//! let status = contract
+//!     .view::<Option<String>>("get_status", json!({ "account_id": "miraclx.testnet" }))
+//!     .await?;
+//! println!("{:?}", status.result);
+//!
+//! // fails, the argument is named `message`
+//! assert!(contract
+//!     .call("set_status", json!({ "status": "working on it" }), 10_000_000_000_000, 0)
+//!     .is_err());
+//! # Ok(())
+//! # }
+//! ```
+use std::sync::Arc;
+
+use near_primitives::transaction::Action;
+use near_primitives::types::{AccountId, Balance, BlockReference, Gas};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::contract::ContractRef;
+use crate::errors::ViewError;
+use crate::views::ViewFunctionResult;
+
+/// Most references followed while checking a single value against its schema.
+const MAX_REFERENCE_DEPTH: usize = 64;
+
+/// Potential errors returned while checking calls against the ABI of a contract.
+#[derive(Debug, thiserror::Error)]
+pub enum AbiError {
+    /// The ABI has no function of that name.
+    #[error("the ABI has no function `{0}`")]
+    UnknownFunction(String),
+    /// The function was viewed, but it isn't a view function.
+    #[error("`{0}` isn't a view function")]
+    NotAView(String),
+    /// A deposit was attached to a function that doesn't accept deposits.
+    #[error("`{0}` doesn't accept deposits")]
+    NotPayable(String),
+    /// The function (de)serializes its arguments or result with borsh, which can't be checked.
+    #[error("`{0}` isn't (de)serialized as JSON")]
+    UnsupportedSerialization(String),
+    /// The arguments don't match the parameters of the function.
+    #[error("invalid arguments for `{function}`: {error}")]
+    InvalidArgs {
+        function: String,
+        error: SchemaError,
+    },
+    /// The result doesn't match the result type of the function.
+    #[error("invalid result of `{function}`: {error}")]
+    InvalidResult {
+        function: String,
+        error: SchemaError,
+    },
+    /// The result matches the ABI, but couldn't be decoded into the type requested.
+    #[error("error while decoding the result: {0}")]
+    DecodeError(serde_json::Error),
+    /// The view function couldn't be called.
+    #[error(transparent)]
+    ViewError(#[from] ViewError),
+}
+
+/// A value not matching its schema.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("at `{path}`: {message}")]
+pub struct SchemaError {
+    /// The path of the value, from the argument or result.
+    pub path: String,
+    /// Why the value doesn't match.
+    pub message: String,
+}
+
+/// The ABI of a contract, in the near-abi format.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ContractAbi {
+    /// The version of the format of the ABI.
+    pub schema_version: String,
+    /// The functions of the contract, and the schemas they reference.
+    pub body: AbiBody,
+}
+
+/// The functions of a contract, and the schemas they reference.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AbiBody {
+    pub functions: Vec<AbiFunction>,
+    /// The schema holding the definitions referenced by the schemas of the functions.
+    #[serde(default)]
+    pub root_schema: Value,
+}
+
+/// A function of a contract.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AbiFunction {
+    pub name: String,
+    pub doc: Option<String>,
+    pub kind: AbiFunctionKind,
+    #[serde(default)]
+    pub modifiers: Vec<AbiFunctionModifier>,
+    #[serde(default)]
+    pub params: AbiParameters,
+    /// The type of the value returned, if any.
+    pub result: Option<AbiType>,
+}
+
+/// Whether a function only views the state of the contract.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AbiFunctionKind {
+    View,
+    Call,
+}
+
+/// The modifiers of a function.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AbiFunctionModifier {
+    /// The function initializes the contract.
+    Init,
+    /// The function accepts deposits.
+    Payable,
+    /// The function can only be called by the contract itself.
+    Private,
+}
+
+/// The parameters of a function, by how they are serialized.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "serialization_type", rename_all = "lowercase")]
+pub enum AbiParameters {
+    Json { args: Vec<AbiParameter> },
+    Borsh { args: Vec<AbiParameter> },
+}
+
+impl Default for AbiParameters {
+    fn default() -> Self {
+        Self::Json { args: Vec::new() }
+    }
+}
+
+/// A parameter of a function.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AbiParameter {
+    pub name: String,
+    pub type_schema: Value,
+}
+
+/// The type of a value, by how it is serialized.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "serialization_type", rename_all = "lowercase")]
+pub enum AbiType {
+    Json { type_schema: Value },
+    Borsh { type_schema: Value },
+}
+
+impl AbiFunction {
+    /// Returns true if the function accepts deposits.
+    pub fn is_payable(&self) -> bool {
+        self.modifiers.contains(&AbiFunctionModifier::Payable)
+    }
+}
+
+impl ContractAbi {
+    /// The function named `name`, if any.
+    pub fn function(&self, name: &str) -> Option<&AbiFunction> {
+        self.body
+            .functions
+            .iter()
+            .find(|function| function.name == name)
+    }
+
+    /// Check `args` against the parameters of the function named `name`.
+    ///
+    /// Missing arguments are only accepted for optional parameters, and unknown ones are
+    /// rejected.
+    pub fn check_args(&self, name: &str, args: &Value) -> Result<&AbiFunction, AbiError> {
+        let function = self
+            .function(name)
+            .ok_or_else(|| AbiError::UnknownFunction(name.to_string()))?;
+        let params = match &function.params {
+            AbiParameters::Json { args } => args,
+            AbiParameters::Borsh { .. } => {
+                return Err(AbiError::UnsupportedSerialization(name.to_string()))
+            }
+        };
+        let invalid = |error| AbiError::InvalidArgs {
+            function: name.to_string(),
+            error,
+        };
+        let empty = serde_json::Map::new();
+        let args = match args {
+            Value::Object(args) => args,
+            Value::Null => &empty,
+            _ => return Err(invalid(schema_error("", "expected an object of arguments"))),
+        };
+        for param in params {
+            match args.get(&param.name) {
+                Some(arg) => self
+                    .check(&param.type_schema, arg, &param.name)
+                    .map_err(invalid)?,
+                None => {
+                    if self.check(&param.type_schema, &Value::Null, "").is_err() {
+                        return Err(invalid(schema_error(&param.name, "missing argument")));
+                    }
+                }
+            }
+        }
+        if let Some(name) = args
+            .keys()
+            .find(|name| !params.iter().any(|param| &param.name == *name))
+        {
+            return Err(invalid(schema_error(name, "unexpected argument")));
+        }
+        Ok(function)
+    }
+
+    /// Check `result` against the result type of the function named `name`, and decode it.
+    pub fn decode_result<T: DeserializeOwned>(
+        &self,
+        name: &str,
+        result: Value,
+    ) -> Result<T, AbiError> {
+        let function = self
+            .function(name)
+            .ok_or_else(|| AbiError::UnknownFunction(name.to_string()))?;
+        match &function.result {
+            Some(AbiType::Json { type_schema }) => self
+                .check(type_schema, &result, "result")
+                .map_err(|error| AbiError::InvalidResult {
+                    function: name.to_string(),
+                    error,
+                })?,
+            Some(AbiType::Borsh { .. }) => {
+                return Err(AbiError::UnsupportedSerialization(name.to_string()))
+            }
+            None => {}
+        }
+        serde_json::from_value(result).map_err(AbiError::DecodeError)
+    }
+
+    /// Check `value` against `schema`, resolving references from the root schema.
+    fn check(&self, schema: &Value, value: &Value, path: &str) -> Result<(), SchemaError> {
+        self.check_value(schema, value, path, &mut Vec::new())
+    }
+
+    /// Check `value` against `schema`, with `refs` the references being resolved for `value`.
+    ///
+    /// ABIs come from arbitrary contracts, so references resolving back to themselves without
+    /// moving on to a nested value are reported instead of followed forever.
+    fn check_value<'a>(
+        &'a self,
+        schema: &'a Value,
+        value: &Value,
+        path: &str,
+        refs: &mut Vec<&'a str>,
+    ) -> Result<(), SchemaError> {
+        let schema = match schema {
+            Value::Bool(false) => return Err(schema_error(path, "no value is allowed")),
+            Value::Object(schema) => schema,
+            _ => return Ok(()),
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let target = reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.body.root_schema.pointer(pointer))
+                .ok_or_else(|| {
+                    schema_error(path, format!("unresolved reference `{}`", reference))
+                })?;
+            if refs.contains(&reference) {
+                return Err(schema_error(
+                    path,
+                    format!("cyclic reference `{}`", reference),
+                ));
+            }
+            if refs.len() >= MAX_REFERENCE_DEPTH {
+                return Err(schema_error(path, "references nested too deeply"));
+            }
+            refs.push(reference);
+            let checked = self.check_value(target, value, path, refs);
+            refs.pop();
+            checked?;
+        }
+
+        if let Some(types) = schema.get("type") {
+            let matches = match types {
+                Value::String(ty) => is_type(ty, value),
+                Value::Array(types) => types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .any(|ty| is_type(ty, value)),
+                _ => true,
+            };
+            if !matches {
+                return Err(schema_error(
+                    path,
+                    format!("expected {}, found {}", types, kind_of(value)),
+                ));
+            }
+        }
+        if let Some(Value::Array(values)) = schema.get("enum") {
+            if !values.contains(value) {
+                return Err(schema_error(
+                    path,
+                    format!(
+                        "expected one of {}, found {}",
+                        Value::from(values.clone()),
+                        value
+                    ),
+                ));
+            }
+        }
+        if let Some(constant) = schema.get("const") {
+            if constant != value {
+                return Err(schema_error(
+                    path,
+                    format!("expected {}, found {}", constant, value),
+                ));
+            }
+        }
+
+        if let Some(Value::Array(schemas)) = schema.get("allOf") {
+            for schema in schemas {
+                self.check_value(schema, value, path, refs)?;
+            }
+        }
+        if let Some(Value::Array(schemas)) = schema.get("anyOf") {
+            if !schemas
+                .iter()
+                .any(|schema| self.check_value(schema, value, path, refs).is_ok())
+            {
+                return Err(schema_error(path, "matches none of the variants"));
+            }
+        }
+        if let Some(Value::Array(schemas)) = schema.get("oneOf") {
+            let matches = schemas
+                .iter()
+                .filter(|schema| self.check_value(schema, value, path, refs).is_ok())
+                .count();
+            if matches != 1 {
+                return Err(schema_error(
+                    path,
+                    format!("matches {} of the variants, expected exactly one", matches),
+                ));
+            }
+        }
+
+        if let Some(number) = value.as_f64() {
+            if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+                if number < minimum {
+                    return Err(schema_error(path, format!("less than {}", minimum)));
+                }
+            }
+            if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+                if number > maximum {
+                    return Err(schema_error(path, format!("greater than {}", maximum)));
+                }
+            }
+        }
+
+        match value {
+            Value::Object(object) => {
+                if let Some(Value::Array(required)) = schema.get("required") {
+                    if let Some(name) = required
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .find(|name| !object.contains_key(*name))
+                    {
+                        return Err(schema_error(path, format!("missing field `{}`", name)));
+                    }
+                }
+                let properties = schema.get("properties").and_then(Value::as_object);
+                for (name, value) in object {
+                    let path = format!("{}.{}", path, name);
+                    match (
+                        properties.and_then(|properties| properties.get(name)),
+                        schema.get("additionalProperties"),
+                    ) {
+                        (Some(schema), _) => self.check(schema, value, &path)?,
+                        (None, Some(Value::Bool(false))) => {
+                            return Err(schema_error(&path, "unexpected field"))
+                        }
+                        (None, Some(schema)) => self.check(schema, value, &path)?,
+                        (None, None) => {}
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                    if (items.len() as u64) < min {
+                        return Err(schema_error(path, format!("fewer than {} items", min)));
+                    }
+                }
+                if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                    if items.len() as u64 > max {
+                        return Err(schema_error(path, format!("more than {} items", max)));
+                    }
+                }
+                match schema.get("items") {
+                    Some(Value::Array(schemas)) => {
+                        for (i, (schema, item)) in schemas.iter().zip(items).enumerate() {
+                            self.check(schema, item, &format!("{}[{}]", path, i))?;
+                        }
+                    }
+                    Some(schema) => {
+                        for (i, item) in items.iter().enumerate() {
+                            self.check(schema, item, &format!("{}[{}]", path, i))?;
+                        }
+                    }
+                    None => {}
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+fn schema_error(path: &str, message: impl Into<String>) -> SchemaError {
+    SchemaError {
+        path: path.to_string(),
+        message: message.into(),
+    }
+}
+
+/// Returns true if `value` is of the JSON Schema type `ty`.
+fn is_type(ty: &str, value: &Value) -> bool {
+    match ty {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().map_or(false, |n| n.fract() == 0.0)
+        }
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn kind_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// A handle on a contract, checking calls against its ABI.
+#[derive(Clone, Debug)]
+pub struct AbiContract {
+    contract: ContractRef,
+    abi: Arc<ContractAbi>,
+}
+
+impl AbiContract {
+    /// The account the contract is deployed to.
+    pub fn id(&self) -> &AccountId {
+        self.contract.id()
+    }
+
+    /// The contract, for calls not checked against the ABI.
+    pub fn contract(&self) -> &ContractRef {
+        &self.contract
+    }
+
+    /// The ABI of the contract.
+    pub fn abi(&self) -> &ContractAbi {
+        &self.abi
+    }
+
+    /// View the contract at the block `block_reference`.
+    pub fn at(mut self, block_reference: BlockReference) -> Self {
+        self.contract = self.contract.at(block_reference);
+        self
+    }
+
+    /// Call a view function of the contract, checking its arguments and result against the ABI.
+    pub async fn view<T: DeserializeOwned>(
+        &self,
+        method_name: &str,
+        args: Value,
+    ) -> Result<ViewFunctionResult<T>, AbiError> {
+        let function = self.abi.check_args(method_name, &args)?;
+        if function.kind != AbiFunctionKind::View {
+            return Err(AbiError::NotAView(method_name.to_string()));
+        }
+        let response = self.contract.view::<Value>(method_name, args).await?;
+        Ok(ViewFunctionResult {
+            result: self.abi.decode_result(method_name, response.result)?,
+            logs: response.logs,
+            block_height: response.block_height,
+            block_hash: response.block_hash,
+        })
+    }
+
+    /// An action calling a function of the contract, checking its arguments against the ABI, and
+    /// that the function accepts deposits if `deposit` isn't zero.
+    pub fn call(
+        &self,
+        method_name: &str,
+        args: Value,
+        gas: Gas,
+        deposit: Balance,
+    ) -> Result<Action, AbiError> {
+        let function = self.abi.check_args(method_name, &args)?;
+        if deposit > 0 && !function.is_payable() {
+            return Err(AbiError::NotPayable(method_name.to_string()));
+        }
+        Ok(self.contract.call(method_name, args, gas, deposit))
+    }
+}
+
+impl ContractRef {
+    /// Check the calls to the contract against its ABI.
+    ///
+    /// See the [`abi`](crate::abi) module for details.
+    pub fn with_abi(self, abi: impl Into<Arc<ContractAbi>>) -> AbiContract {
+        AbiContract {
+            contract: self,
+            abi: abi.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::JsonRpcClient;
+
+    fn abi() -> ContractAbi {
+        serde_json::from_value(json!({
+            "schema_version": "0.4.0",
+            "metadata": { "name": "status-message", "version": "0.1.0" },
+            "body": {
+                "functions": [
+                    {
+                        "name": "get_status",
+                        "kind": "view",
+                        "params": {
+                            "serialization_type": "json",
+                            "args": [
+                                {
+                                    "name": "account_id",
+                                    "type_schema": { "$ref": "#/definitions/AccountId" }
+                                }
+                            ]
+                        },
+                        "result": {
+                            "serialization_type": "json",
+                            "type_schema": { "type": ["string", "null"] }
+                        }
+                    },
+                    {
+                        "name": "set_status",
+                        "kind": "call",
+                        "modifiers": ["payable"],
+                        "params": {
+                            "serialization_type": "json",
+                            "args": [
+                                { "name": "message", "type_schema": { "type": "string" } },
+                                {
+                                    "name": "ttl",
+                                    "type_schema": {
+                                        "type": ["integer", "null"],
+                                        "format": "uint64",
+                                        "minimum": 0.0
+                                    }
+                                }
+                            ]
+                        }
+                    },
+                    { "name": "clear", "kind": "call" }
+                ],
+                "root_schema": {
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "title": "String",
+                    "type": "string",
+                    "definitions": {
+                        "AccountId": { "type": "string" }
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn arguments_are_checked_against_the_abi() {
+        let abi = abi();
+
+        assert!(abi
+            .check_args("get_status", &json!({ "account_id": "alice.near" }))
+            .is_ok());
+        assert!(abi
+            .check_args("set_status", &json!({ "message": "hi" }))
+            .is_ok());
+        assert!(abi.check_args("clear", &Value::Null).is_ok());
+
+        let error = |args| match abi.check_args("set_status", &args) {
+            Err(AbiError::InvalidArgs { error, .. }) => error,
+            other => panic!("expected invalid arguments, got {:?}", other),
+        };
+        assert_eq!(
+            error(json!({ "status": "hi" })),
+            schema_error("message", "missing argument")
+        );
+        assert_eq!(
+            error(json!({ "message": "hi", "status": "hi" })),
+            schema_error("status", "unexpected argument")
+        );
+        assert_eq!(
+            error(json!({ "message": "hi", "ttl": -1 })),
+            schema_error("ttl", "less than 0")
+        );
+        assert_eq!(
+            error(json!({ "message": 1 })),
+            schema_error("message", "expected \"string\", found a number")
+        );
+        assert!(matches!(
+            abi.check_args("get_status", &json!({ "account_id": 1 })),
+            Err(AbiError::InvalidArgs { .. })
+        ));
+        assert!(matches!(
+            abi.check_args("get_statuses", &json!({})),
+            Err(AbiError::UnknownFunction(_))
+        ));
+
+        let contract = JsonRpcClient::connect("http://localhost:3030")
+            .contract("status.near".parse().unwrap())
+            .with_abi(abi.clone());
+        assert!(contract
+            .call("set_status", json!({ "message": "hi" }), 10, 1)
+            .is_ok());
+        assert!(matches!(
+            contract.call("clear", json!({}), 10, 1),
+            Err(AbiError::NotPayable(_))
+        ));
+    }
+
+    #[test]
+    fn cyclic_references_are_reported() {
+        let abi: ContractAbi = serde_json::from_value(json!({
+            "schema_version": "0.4.0",
+            "body": {
+                "functions": [
+                    {
+                        "name": "set",
+                        "kind": "call",
+                        "params": {
+                            "serialization_type": "json",
+                            "args": [
+                                { "name": "a", "type_schema": { "$ref": "#/definitions/A" } },
+                                { "name": "b", "type_schema": { "$ref": "#/definitions/B" } },
+                                { "name": "list", "type_schema": { "$ref": "#/definitions/List" } }
+                            ]
+                        }
+                    }
+                ],
+                "root_schema": {
+                    "definitions": {
+                        "A": { "$ref": "#/definitions/A" },
+                        "B": { "anyOf": [{ "$ref": "#/definitions/B" }, { "type": "string" }] },
+                        "List": {
+                            "type": "object",
+                            "properties": {
+                                "next": {
+                                    "anyOf": [{ "$ref": "#/definitions/List" }, { "type": "null" }]
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let list = json!({ "next": { "next": { "next": null } } });
+        let error = |args| match abi.check_args("set", &args) {
+            Err(AbiError::InvalidArgs { error, .. }) => error,
+            other => panic!("expected invalid arguments, got {:?}", other),
+        };
+        assert_eq!(
+            error(json!({ "a": 1, "b": "hi", "list": list })),
+            schema_error("a", "cyclic reference `#/definitions/A`")
+        );
+        assert_eq!(
+            abi.check(&json!({ "$ref": "#/definitions/B" }), &json!(1), "b"),
+            Err(schema_error("b", "matches none of the variants"))
+        );
+        assert!(abi
+            .check(&json!({ "$ref": "#/definitions/B" }), &json!("hi"), "b")
+            .is_ok());
+        assert!(abi
+            .check(&json!({ "$ref": "#/definitions/List" }), &list, "list")
+            .is_ok());
+    }
+
+    #[test]
+    fn results_are_checked_against_the_abi() {
+        let abi = abi();
+
+        let status: Option<String> = abi.decode_result("get_status", json!("hi")).unwrap();
+        assert_eq!(status.as_deref(), Some("hi"));
+        let status: Option<String> = abi.decode_result("get_status", Value::Null).unwrap();
+        assert_eq!(status, None);
+
+        assert!(matches!(
+            abi.decode_result::<Option<String>>("get_status", json!(1)),
+            Err(AbiError::InvalidResult { .. })
+        ));
+        assert!(matches!(
+            abi.decode_result::<u64>("get_status", json!("hi")),
+            Err(AbiError::DecodeError(_))
+        ));
+    }
+}
@@ -0,0 +1,244 @@
+//! Handles on accounts.
+//!
+//! An [`AccountRef`] is derived from a [`JsonRpcClient`] with [`JsonRpcClient::account`], and
+//! bundles the queries wallets and backends make about an account.
+//!
+//! Accounts are deleted with [`JsonRpcClient::delete_account`], checking first that the
+//! beneficiary of their balance exists.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::JsonRpcClient;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let account = client.account("miraclx.testnet".parse()?);
+//!
+//! if account.exists().await? {
+//!     println!("balance: {} yoctoNEAR", account.balance().await?);
+//!     for key in account.access_keys().await? {
+//!         println!("key: {}", key.public_key);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use near_crypto::PublicKey;
+use near_primitives::hash::CryptoHash;
+use near_primitives::types::{AccountId, Balance, BlockReference, Finality, Nonce};
+use near_primitives::views::{AccessKeyInfoView, AccountView};
+
+use crate::actions;
+use crate::errors::{SendTransactionError, ViewError};
+use crate::methods::query::RpcQueryError;
+use crate::methods::send_tx::RpcTransactionResponse;
+use crate::signer::TransactionSigner;
+use crate::views::AccessKeyFilter;
+use crate::JsonRpcClient;
+
+/// Potential errors returned while deleting an account.
+#[derive(Debug, thiserror::Error)]
+pub enum DeleteAccountError {
+    /// The beneficiary couldn't be viewed, to check that it exists.
+    #[error("error while viewing the beneficiary: [{0}]")]
+    BeneficiaryError(ViewError),
+    /// The beneficiary doesn't exist, so the balance of the account would be burnt.
+    #[error("the beneficiary {0} doesn't exist")]
+    UnknownBeneficiary(AccountId),
+    /// The beneficiary is the account deleted, so its balance would be burnt.
+    #[error("{0} can't be the beneficiary of its own deletion")]
+    SelfBeneficiary(AccountId),
+    /// The transaction deleting the account failed.
+    #[error(transparent)]
+    SendError(#[from] SendTransactionError),
+}
+
+/// A handle on an account.
+#[derive(Clone, Debug)]
+pub struct AccountRef {
+    client: JsonRpcClient,
+    account_id: AccountId,
+    block_reference: BlockReference,
+}
+
+/// What's needed to sign the next transaction with an access key.
+#[derive(Eq, Copy, Clone, Debug, PartialEq)]
+pub struct TransactionContext {
+    /// The nonce of the next transaction signed with the access key.
+    pub nonce: Nonce,
+    /// A recent block hash, for the transaction to reference.
+    pub block_hash: CryptoHash,
+}
+
+impl AccountRef {
+    pub(crate) fn new(client: JsonRpcClient, account_id: AccountId) -> Self {
+        Self {
+            client,
+            account_id,
+            block_reference: BlockReference::Finality(Finality::Final),
+        }
+    }
+
+    /// The id of the account.
+    pub fn id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    /// The block the account is viewed at, the latest final block by default.
+    pub fn block_reference(&self) -> &BlockReference {
+        &self.block_reference
+    }
+
+    /// View the account at the block `block_reference`.
+    pub fn at(mut self, block_reference: BlockReference) -> Self {
+        self.block_reference = block_reference;
+        self
+    }
+
+    /// Get the state of the account, see [`JsonRpcClient::view_account`].
+    pub async fn state(&self) -> Result<AccountView, ViewError> {
+        self.client
+            .view_account(self.account_id.clone(), self.block_reference.clone())
+            .await
+    }
+
+    /// Returns true if the account exists.
+    pub async fn exists(&self) -> Result<bool, ViewError> {
+        match self.state().await {
+            Ok(_) => Ok(true),
+            Err(err) if is_unknown_account(&err) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Get the liquid balance of the account, excluding the tokens it has staked.
+    pub async fn balance(&self) -> Result<Balance, ViewError> {
+        self.state().await.map(|account| account.amount)
+    }
+
+    /// List the access keys of the account.
+    pub async fn access_keys(&self) -> Result<Vec<AccessKeyInfoView>, ViewError> {
+        self.client
+            .view_access_key_list(
+                self.account_id.clone(),
+                AccessKeyFilter::All,
+                self.block_reference.clone(),
+            )
+            .await
+    }
+
+    /// Get the nonce and block hash to sign the next transaction of the account with, using the
+    /// access key `public_key`.
+    ///
+    /// This always views the latest final block, regardless of [`at`](Self::at), so the nonce is
+    /// current and the block hash recent.
+    pub async fn transaction_context(
+        &self,
+        public_key: PublicKey,
+    ) -> Result<TransactionContext, ViewError> {
+        let (access_key, block_hash, _) = self
+            .client
+            .view_access_key(
+                self.account_id.clone(),
+                public_key,
+                BlockReference::Finality(Finality::Final),
+            )
+            .await?;
+        Ok(TransactionContext {
+            nonce: access_key.nonce + 1,
+            block_hash,
+        })
+    }
+}
+
+impl JsonRpcClient {
+    /// Delete the account of `signer`, sending its balance to `beneficiary_id`.
+    ///
+    /// The beneficiary is checked to exist first: the balance of an account deleted in favor of
+    /// a missing beneficiary is burnt.
+    pub async fn delete_account(
+        &self,
+        signer: impl TransactionSigner + 'static,
+        beneficiary_id: AccountId,
+    ) -> Result<RpcTransactionResponse, DeleteAccountError> {
+        if &beneficiary_id == signer.account_id() {
+            return Err(DeleteAccountError::SelfBeneficiary(beneficiary_id));
+        }
+        let exists = self
+            .account(beneficiary_id.clone())
+            .exists()
+            .await
+            .map_err(DeleteAccountError::BeneficiaryError)?;
+        if !exists {
+            return Err(DeleteAccountError::UnknownBeneficiary(beneficiary_id));
+        }
+        let account_id = signer.account_id().clone();
+        Ok(self
+            .transaction(signer, account_id)
+            .action(actions::delete_account(beneficiary_id))
+            .send()
+            .await?)
+    }
+}
+
+fn is_unknown_account(err: &ViewError) -> bool {
+    matches!(
+        err.handler_error(),
+        Some(RpcQueryError::UnknownAccount { .. })
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::errors::{JsonRpcError, JsonRpcServerError};
+
+    #[tokio::test]
+    async fn beneficiaries_are_checked() {
+        let client = JsonRpcClient::connect("http://localhost:1");
+        let signer = near_crypto::InMemorySigner::from_secret_key(
+            "alice.near".parse().unwrap(),
+            near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519),
+        );
+
+        let err = client
+            .delete_account(signer.clone(), "alice.near".parse().unwrap())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DeleteAccountError::SelfBeneficiary(_)));
+
+        // nothing is sent unless the beneficiary is known to exist
+        let err = client
+            .delete_account(signer, "bob.near".parse().unwrap())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DeleteAccountError::BeneficiaryError(_)));
+    }
+
+    #[test]
+    fn unknown_accounts_do_not_exist() {
+        let err = ViewError::from(JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+            RpcQueryError::UnknownAccount {
+                requested_account_id: "alice.near".parse().unwrap(),
+                block_height: 1,
+                block_hash: CryptoHash::default(),
+            },
+        )));
+        assert!(is_unknown_account(&err));
+
+        let err = ViewError::from(JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+            RpcQueryError::NoSyncedBlocks,
+        )));
+        assert!(!is_unknown_account(&err));
+
+        let err = ViewError::UnexpectedResponseKind {
+            expected: "ViewAccount",
+            actual: "CallResult",
+        };
+        assert!(!is_unknown_account(&err));
+    }
+}
@@ -0,0 +1,368 @@
+//! Common actions and combinations of actions, and the limits of the actions of a transaction.
+//!
+//! The functions of this module return the actions of common transactions in the order the
+//! runtime expects, ready to be added to a [`TransactionBuilder`], along with the actions managing
+//! access keys, like [`rotate_key`]. [`ActionLimits`] checks actions against the per-transaction
+//! limits of the protocol before they're sent.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::actions::{self, ActionLimits};
+//! use near_jsonrpc_client::JsonRpcClient;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let signer = near_crypto::InMemorySigner::from_secret_key(
+//!     "fido.testnet".parse()?,
+//!     "ed25519:12dhevYshfiRqFSu8DSfxA27pTkmGRv6C5qQWTJYTcBEoB7MSTyidghi5NWXzWqrxCKgxVx97bpXPYQxYN5dieU".parse()?,
+//! );
+//!
+//! let create = actions::create_account(
+//!     "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp".parse()?,
+//!     1_000_000_000_000_000_000_000_000, // 1 NEAR
+//! );
+//! ActionLimits::default().validate(&create)?;
+//!
+//! client
+//!     .transaction(signer, "counter.fido.testnet".parse()?)
+//!     .actions(create)
+//!     .send()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`TransactionBuilder`]: crate::transaction::TransactionBuilder
+use near_crypto::PublicKey;
+use near_primitives::account::{AccessKey, AccessKeyPermission, FunctionCallPermission};
+use near_primitives::errors::ActionsValidationError;
+use near_primitives::transaction::{
+    Action, AddKeyAction, CreateAccountAction, DeleteAccountAction, DeleteKeyAction,
+    DeployContractAction, FunctionCallAction, TransferAction,
+};
+use near_primitives::types::{AccountId, Balance, Gas};
+
+use crate::methods::EXPERIMENTAL_protocol_config::RpcProtocolConfigResponse;
+
+/// The actions creating the receiver of the transaction, adding the full access key `public_key`
+/// to it, and funding it with `deposit`.
+pub fn create_account(public_key: PublicKey, deposit: Balance) -> Vec<Action> {
+    vec![
+        Action::CreateAccount(CreateAccountAction {}),
+        add_full_access_key(public_key),
+        Action::Transfer(TransferAction { deposit }),
+    ]
+}
+
+/// The actions deploying `code` to the receiver of the transaction, then calling its
+/// initialization function `method_name`, with JSON arguments.
+pub fn deploy_and_init(
+    code: Vec<u8>,
+    method_name: impl Into<String>,
+    args: serde_json::Value,
+    gas: Gas,
+    deposit: Balance,
+) -> Vec<Action> {
+    vec![
+        Action::DeployContract(DeployContractAction { code }),
+        Action::FunctionCall(Box::new(FunctionCallAction {
+            method_name: method_name.into(),
+            args: args.to_string().into_bytes(),
+            gas,
+            deposit,
+        })),
+    ]
+}
+
+/// The action adding the full access key `public_key` to the receiver of the transaction.
+pub fn add_full_access_key(public_key: PublicKey) -> Action {
+    Action::AddKey(Box::new(AddKeyAction {
+        public_key,
+        access_key: AccessKey::full_access(),
+    }))
+}
+
+/// The action adding the access key `public_key` to the receiver of the transaction, only
+/// allowed to call the functions `method_names` of `receiver_id`, or any of its functions if
+/// empty, without attaching deposits.
+///
+/// The key can spend up to `allowance` on gas, or without limit if `None`.
+pub fn add_function_call_key(
+    public_key: PublicKey,
+    receiver_id: AccountId,
+    method_names: impl IntoIterator<Item = impl Into<String>>,
+    allowance: Option<Balance>,
+) -> Action {
+    Action::AddKey(Box::new(AddKeyAction {
+        public_key,
+        access_key: AccessKey {
+            nonce: 0,
+            permission: AccessKeyPermission::FunctionCall(FunctionCallPermission {
+                allowance,
+                receiver_id: receiver_id.into(),
+                method_names: method_names.into_iter().map(Into::into).collect(),
+            }),
+        },
+    }))
+}
+
+/// The action deleting the access key `public_key` of the receiver of the transaction.
+pub fn delete_key(public_key: PublicKey) -> Action {
+    Action::DeleteKey(Box::new(DeleteKeyAction { public_key }))
+}
+
+/// The actions replacing the full access key `old_public_key` of the receiver of the
+/// transaction with `new_public_key`.
+///
+/// Both happen atomically in the same transaction, which can be signed with the old key itself:
+/// the new key is added first, so the account is never left without the key.
+pub fn rotate_key(old_public_key: PublicKey, new_public_key: PublicKey) -> Vec<Action> {
+    vec![
+        add_full_access_key(new_public_key),
+        delete_key(old_public_key),
+    ]
+}
+
+/// The action deleting the receiver of the transaction, sending its balance to `beneficiary_id`.
+///
+/// The action must be the last of the transaction. The balance is burnt if the beneficiary
+/// doesn't exist, see [`JsonRpcClient::delete_account`](crate::JsonRpcClient::delete_account)
+/// for a checked deletion.
+pub fn delete_account(beneficiary_id: AccountId) -> Action {
+    Action::DeleteAccount(DeleteAccountAction { beneficiary_id })
+}
+
+/// The limits of the protocol on the actions of a transaction.
+///
+/// The defaults are those of the current protocol version, on mainnet and testnet.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ActionLimits {
+    /// The maximum number of actions.
+    pub max_actions: u64,
+    /// The maximum gas attached to the function calls altogether.
+    pub max_total_prepaid_gas: Gas,
+    /// The maximum size of the code of a contract deployed.
+    pub max_contract_size: u64,
+    /// The maximum length of the name of a function called.
+    pub max_method_name_length: u64,
+    /// The maximum length of the arguments of a function called.
+    pub max_arguments_length: u64,
+    /// The maximum number of bytes of the method names a function call access key is limited
+    /// to, each counting one more byte.
+    pub max_method_names_bytes: u64,
+}
+
+impl Default for ActionLimits {
+    fn default() -> Self {
+        Self {
+            max_actions: 100,
+            max_total_prepaid_gas: 300_000_000_000_000,
+            max_contract_size: 4_194_304,
+            max_method_name_length: 256,
+            max_arguments_length: 4_194_304,
+            max_method_names_bytes: 2_000,
+        }
+    }
+}
+
+impl ActionLimits {
+    /// The limits of the protocol config `config`, as viewed with the
+    /// [`EXPERIMENTAL_protocol_config`](crate::methods::EXPERIMENTAL_protocol_config) method.
+    pub fn from_config(config: &RpcProtocolConfigResponse) -> Self {
+        let limits = &config.runtime_config.wasm_config.limit_config;
+        Self {
+            max_actions: limits.max_actions_per_receipt,
+            max_total_prepaid_gas: limits.max_total_prepaid_gas,
+            max_contract_size: limits.max_contract_size,
+            max_method_name_length: limits.max_length_method_name,
+            max_arguments_length: limits.max_arguments_length,
+            max_method_names_bytes: limits.max_number_bytes_method_names,
+        }
+    }
+
+    /// Check `actions` against the limits, and the rules of the runtime on their order, failing
+    /// with the error the node would reject the transaction with.
+    pub fn validate(&self, actions: &[Action]) -> Result<(), ActionsValidationError> {
+        let total_number_of_actions = actions.len() as u64;
+        if total_number_of_actions > self.max_actions {
+            return Err(ActionsValidationError::TotalNumberOfActionsExceeded {
+                total_number_of_actions,
+                limit: self.max_actions,
+            });
+        }
+
+        let mut total_prepaid_gas: Gas = 0;
+        let mut delegate_actions = 0;
+        for (index, action) in actions.iter().enumerate() {
+            match action {
+                Action::DeleteAccount(_) if index + 1 < actions.len() => {
+                    return Err(ActionsValidationError::DeleteActionMustBeFinal);
+                }
+                Action::Delegate(_) => {
+                    delegate_actions += 1;
+                    if delegate_actions > 1 {
+                        return Err(ActionsValidationError::DelegateActionMustBeOnlyOne);
+                    }
+                }
+                Action::DeployContract(deploy) => {
+                    let size = deploy.code.len() as u64;
+                    if size > self.max_contract_size {
+                        return Err(ActionsValidationError::ContractSizeExceeded {
+                            size,
+                            limit: self.max_contract_size,
+                        });
+                    }
+                }
+                Action::FunctionCall(call) => {
+                    if call.gas == 0 {
+                        return Err(ActionsValidationError::FunctionCallZeroAttachedGas);
+                    }
+                    total_prepaid_gas = total_prepaid_gas
+                        .checked_add(call.gas)
+                        .ok_or(ActionsValidationError::IntegerOverflow)?;
+                    let length = call.method_name.len() as u64;
+                    if length > self.max_method_name_length {
+                        return Err(
+                            ActionsValidationError::FunctionCallMethodNameLengthExceeded {
+                                length,
+                                limit: self.max_method_name_length,
+                            },
+                        );
+                    }
+                    let length = call.args.len() as u64;
+                    if length > self.max_arguments_length {
+                        return Err(
+                            ActionsValidationError::FunctionCallArgumentsLengthExceeded {
+                                length,
+                                limit: self.max_arguments_length,
+                            },
+                        );
+                    }
+                }
+                Action::AddKey(add_key) => {
+                    if let AccessKeyPermission::FunctionCall(permission) =
+                        &add_key.access_key.permission
+                    {
+                        let mut total_number_of_bytes = 0;
+                        for method_name in &permission.method_names {
+                            let length = method_name.len() as u64;
+                            if length > self.max_method_name_length {
+                                return Err(
+                                    ActionsValidationError::AddKeyMethodNameLengthExceeded {
+                                        length,
+                                        limit: self.max_method_name_length,
+                                    },
+                                );
+                            }
+                            total_number_of_bytes += length + 1;
+                        }
+                        if total_number_of_bytes > self.max_method_names_bytes {
+                            return Err(
+                                ActionsValidationError::AddKeyMethodNamesNumberOfBytesExceeded {
+                                    total_number_of_bytes,
+                                    limit: self.max_method_names_bytes,
+                                },
+                            );
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if total_prepaid_gas > self.max_total_prepaid_gas {
+            return Err(ActionsValidationError::TotalPrepaidGasExceeded {
+                total_prepaid_gas,
+                limit: self.max_total_prepaid_gas,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use near_crypto::{KeyType, SecretKey};
+
+    #[test]
+    fn keys_are_rotated() {
+        let old = SecretKey::from_random(KeyType::ED25519).public_key();
+        let new = SecretKey::from_random(KeyType::ED25519).public_key();
+
+        let actions = rotate_key(old.clone(), new.clone());
+        assert_eq!(actions, [add_full_access_key(new.clone()), delete_key(old)]);
+
+        let limited = add_function_call_key(new, "app.near".parse().unwrap(), ["vote"], Some(1));
+        let Action::AddKey(add_key) = &limited else {
+            panic!("expected an AddKey action, got {:?}", limited);
+        };
+        assert_eq!(
+            add_key.access_key.permission,
+            AccessKeyPermission::FunctionCall(FunctionCallPermission {
+                allowance: Some(1),
+                receiver_id: "app.near".to_string(),
+                method_names: vec!["vote".to_string()],
+            })
+        );
+        assert_eq!(ActionLimits::default().validate(&actions), Ok(()));
+    }
+
+    #[test]
+    fn actions_are_validated() {
+        let limits = ActionLimits::default();
+        let public_key = SecretKey::from_random(KeyType::ED25519).public_key();
+
+        let mut actions = create_account(public_key, 1);
+        assert!(matches!(actions[0], Action::CreateAccount(_)));
+        assert_eq!(limits.validate(&actions), Ok(()));
+
+        actions.extend(deploy_and_init(
+            vec![0; 16],
+            "new",
+            serde_json::json!({}),
+            200_000_000_000_000,
+            0,
+        ));
+        assert_eq!(limits.validate(&actions), Ok(()));
+
+        actions.extend(deploy_and_init(
+            vec![0; 16],
+            "migrate",
+            serde_json::json!({}),
+            200_000_000_000_000,
+            0,
+        ));
+        assert_eq!(
+            limits.validate(&actions),
+            Err(ActionsValidationError::TotalPrepaidGasExceeded {
+                total_prepaid_gas: 400_000_000_000_000,
+                limit: limits.max_total_prepaid_gas,
+            })
+        );
+
+        let delete = delete_account("alice.near".parse().unwrap());
+        assert_eq!(
+            limits.validate(&[
+                delete.clone(),
+                Action::Transfer(TransferAction { deposit: 1 })
+            ]),
+            Err(ActionsValidationError::DeleteActionMustBeFinal)
+        );
+        assert_eq!(
+            ActionLimits {
+                max_actions: 1,
+                ..limits
+            }
+            .validate(&[delete.clone(), delete]),
+            Err(ActionsValidationError::TotalNumberOfActionsExceeded {
+                total_number_of_actions: 2,
+                limit: 1,
+            })
+        );
+    }
+}
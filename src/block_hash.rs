@@ -0,0 +1,149 @@
+//! Caching a recent block hash for transactions to reference.
+//!
+//! Transactions reference a recent block, and expire once the chain has moved past it by the
+//! transaction validity period, roughly a day of blocks. A [`BlockHashCache`] fetches the hash of
+//! the latest final block once, hands it out until it's
+//! [`refresh_after`](BlockHashCache::refresh_after) old, then refreshes it in the background while
+//! still handing out the cached hash, up until it's [`max_age`](BlockHashCache::max_age) old.
+//!
+//! Final blocks can't be dropped by a fork, which would leave the transactions referencing them
+//! invalid.
+//!
+//! [`NonceManager`](crate::nonce::NonceManager)s use a cache for the block hashes of the
+//! transactions they hand out nonces for.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use near_jsonrpc_client::block_hash::BlockHashCache;
+//! use near_jsonrpc_client::JsonRpcClient;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let block_hashes = BlockHashCache::new(client).refresh_after(Duration::from_secs(60));
+//!
+//! println!("recent block hash: {}", block_hashes.get().await?);
+//! # Ok(())
+//! # }
+//! ```
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use near_primitives::hash::CryptoHash;
+use near_primitives::types::{BlockReference, Finality};
+use tokio::time::Instant;
+
+use crate::errors::ViewError;
+use crate::methods::block::RpcBlockRequest;
+use crate::JsonRpcClient;
+
+/// Hands out a recent block hash, refreshing it in the background.
+///
+/// Clones share the same cached hash.
+#[derive(Clone, Debug)]
+pub struct BlockHashCache {
+    client: JsonRpcClient,
+    refresh_after: Duration,
+    max_age: Duration,
+    cached: Arc<Mutex<Option<(CryptoHash, Instant)>>>,
+    refreshing: Arc<AtomicBool>,
+}
+
+impl BlockHashCache {
+    /// Create a cache fetching the latest final block with `client`.
+    ///
+    /// The hash is refreshed after 10 minutes, and no longer handed out after an hour.
+    pub fn new(client: JsonRpcClient) -> Self {
+        Self {
+            client,
+            refresh_after: Duration::from_secs(10 * 60),
+            max_age: Duration::from_secs(60 * 60),
+            cached: Default::default(),
+            refreshing: Default::default(),
+        }
+    }
+
+    /// Refresh the hash in the background once it's `refresh_after` old.
+    pub fn refresh_after(mut self, refresh_after: Duration) -> Self {
+        self.refresh_after = refresh_after;
+        self
+    }
+
+    /// Stop handing out the hash once it's `max_age` old, fetching a new one instead.
+    ///
+    /// This must stay well under the transaction validity period.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Get a recent block hash.
+    pub async fn get(&self) -> Result<CryptoHash, ViewError> {
+        let cached = *self.cached.lock().unwrap();
+        match cached {
+            Some((block_hash, fetched_at)) if fetched_at.elapsed() < self.refresh_after => {
+                Ok(block_hash)
+            }
+            Some((block_hash, fetched_at)) if fetched_at.elapsed() < self.max_age => {
+                if !self.refreshing.swap(true, Ordering::AcqRel) {
+                    let cache = self.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = cache.refresh().await {
+                            log::debug!("failed to refresh the recent block hash: {}", err);
+                        }
+                        cache.refreshing.store(false, Ordering::Release);
+                    });
+                }
+                Ok(block_hash)
+            }
+            _ => self.refresh().await,
+        }
+    }
+
+    /// Fetch the hash of the latest final block, caching it.
+    pub async fn refresh(&self) -> Result<CryptoHash, ViewError> {
+        let block = self
+            .client
+            .call(RpcBlockRequest {
+                block_reference: BlockReference::Finality(Finality::Final),
+            })
+            .await?;
+        self.insert(block.header.hash);
+        Ok(block.header.hash)
+    }
+
+    /// Drop the cached hash, so a new one is fetched the next time, e.g. after a transaction
+    /// referencing it expired.
+    pub fn invalidate(&self) {
+        *self.cached.lock().unwrap() = None;
+    }
+
+    pub(crate) fn insert(&self, block_hash: CryptoHash) {
+        *self.cached.lock().unwrap() = Some((block_hash, Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn hashes_are_cached_until_invalidated() {
+        let cache = BlockHashCache::new(JsonRpcClient::connect("http://localhost:1"));
+        let block_hash = CryptoHash::hash_bytes(b"block");
+        cache.insert(block_hash);
+        assert_eq!(cache.get().await.unwrap(), block_hash);
+
+        // stale hashes are still handed out while refreshing
+        tokio::time::advance(Duration::from_secs(30 * 60)).await;
+        assert_eq!(cache.get().await.unwrap(), block_hash);
+
+        cache.invalidate();
+        assert!(cache.get().await.is_err());
+    }
+}
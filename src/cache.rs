@@ -0,0 +1,338 @@
+//! Response caching.
+//!
+//! Caching is opt-in and configured per method. Results can either be cached forever, which
+//! suits immutable data like blocks referenced by their height or hash, or for a limited time,
+//! which suits semi-static data like the protocol config or validators of an epoch.
+//!
+//! Cached results are kept in a [`CacheStore`], either in memory ([`MemoryStore`]) or on disk
+//! ([`DiskStore`]) so restarted processes don't need to re-fetch them.
+//!
+//! Requests that reference a block by its finality are never cached since their result changes over time.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use near_jsonrpc_client::{cache::{DiskStore, ResponseCache}, JsonRpcClient};
+//!
+//! # fn main() -> std::io::Result<()> {
+//! let cache = ResponseCache::new(DiskStore::new(std::env::temp_dir().join("near-rpc-cache"))?)
+//!     .cache_forever("block")
+//!     .cache_forever("chunk")
+//!     .cache_for("EXPERIMENTAL_protocol_config", Duration::from_secs(12 * 60 * 60))
+//!     .cache_for("validators", Duration::from_secs(60 * 60));
+//!
+//! let client = JsonRpcClient::new_client()
+//!     .cache(cache)
+//!     .connect("https://archival-rpc.mainnet.near.org");
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{fmt, fs, io, process};
+
+use serde::{Deserialize, Serialize};
+
+use crate::methods::RpcMethod;
+
+/// Storage backend for cached method call results.
+pub trait CacheStore: Send + Sync {
+    /// Returns the unexpired result stored under `key`, if any.
+    fn get(&self, key: &str) -> Option<serde_json::Value>;
+
+    /// Stores a result under `key`, expiring after `ttl` if specified.
+    fn insert(&self, key: &str, value: serde_json::Value, ttl: Option<Duration>);
+}
+
+/// An in-memory [`CacheStore`].
+///
+/// Expired entries are only evicted when they are looked up again, so this store is best
+/// suited for a bounded set of requests.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    entries: Mutex<HashMap<String, (serde_json::Value, Option<Instant>)>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes all entries from the store.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+impl CacheStore for MemoryStore {
+    fn get(&self, key: &str) -> Option<serde_json::Value> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some((_, Some(expires_at))) if *expires_at <= Instant::now() => {
+                entries.remove(key);
+                None
+            }
+            Some((value, _)) => Some(value.clone()),
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: &str, value: serde_json::Value, ttl: Option<Duration>) {
+        // TTLs too long to be represented never expire
+        let expires_at = ttl.and_then(|ttl| Instant::now().checked_add(ttl));
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), (value, expires_at));
+    }
+}
+
+/// A [`CacheStore`] persisting entries as files in a directory.
+///
+/// Failures to read or write entries are logged and otherwise ignored, the call is then
+/// simply sent to the server.
+#[derive(Debug)]
+pub struct DiskStore {
+    dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    expires_at: Option<u64>,
+    value: serde_json::Value,
+}
+
+impl DiskStore {
+    /// Creates a store in the specified directory, creating it if it doesn't exist.
+    pub fn new<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+        })
+    }
+
+    /// Returns the directory where entries are persisted.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!(
+            "{}.json",
+            near_primitives::hash::hash(key.as_bytes())
+        ))
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_secs())
+}
+
+impl CacheStore for DiskStore {
+    fn get(&self, key: &str) -> Option<serde_json::Value> {
+        let path = self.path(key);
+        let entry = match fs::read(&path) {
+            Ok(entry) => serde_json::from_slice::<DiskEntry>(&entry).ok()?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                log::debug!("failed to read cache entry {:?}: {}", path, err);
+                return None;
+            }
+        };
+        if entry
+            .expires_at
+            .map_or(false, |expires_at| expires_at <= unix_now())
+        {
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+        Some(entry.value)
+    }
+
+    fn insert(&self, key: &str, value: serde_json::Value, ttl: Option<Duration>) {
+        let entry = DiskEntry {
+            expires_at: ttl.and_then(|ttl| unix_now().checked_add(ttl.as_secs())),
+            value,
+        };
+        let path = self.path(key);
+        let written = serde_json::to_vec(&entry)
+            .map_err(io::Error::from)
+            .and_then(|entry| {
+                // write to a temporary file first, so readers never observe partial entries,
+                // unique to this write so concurrent writers of the same key don't interleave
+                static WRITES: AtomicU64 = AtomicU64::new(0);
+                let tmp_path = path.with_extension(format!(
+                    "{}.{}.tmp",
+                    process::id(),
+                    WRITES.fetch_add(1, Ordering::Relaxed)
+                ));
+                let written =
+                    fs::write(&tmp_path, entry).and_then(|_| fs::rename(&tmp_path, &path));
+                if written.is_err() {
+                    let _ = fs::remove_file(&tmp_path);
+                }
+                written
+            });
+        if let Err(err) = written {
+            log::debug!("failed to write cache entry {:?}: {}", path, err);
+        }
+    }
+}
+
+/// Cache for method call results, configured per method.
+///
+/// See the [`cache`](self) module documentation for more information.
+#[derive(Clone)]
+pub struct ResponseCache {
+    store: Arc<dyn CacheStore>,
+    methods: HashMap<String, Option<Duration>>,
+}
+
+impl ResponseCache {
+    /// Creates a cache backed by the specified store, with no methods cached.
+    pub fn new<S: CacheStore + 'static>(store: S) -> Self {
+        Self {
+            store: Arc::new(store),
+            methods: HashMap::new(),
+        }
+    }
+
+    /// Creates a cache backed by a new [`MemoryStore`], with no methods cached.
+    pub fn in_memory() -> Self {
+        Self::new(MemoryStore::new())
+    }
+
+    /// Cache results of the specified method forever.
+    pub fn cache_forever(mut self, method_name: &str) -> Self {
+        self.methods.insert(method_name.to_string(), None);
+        self
+    }
+
+    /// Cache results of the specified method for the specified duration.
+    pub fn cache_for(mut self, method_name: &str, ttl: Duration) -> Self {
+        self.methods.insert(method_name.to_string(), Some(ttl));
+        self
+    }
+
+    /// Returns the store backing this cache.
+    pub fn store(&self) -> &dyn CacheStore {
+        &*self.store
+    }
+
+    /// Returns the cache key and TTL for a call, if its result should be cached.
+    pub(crate) fn entry<M: RpcMethod>(
+        &self,
+        server_addr: &str,
+        method: &M,
+    ) -> Option<(String, Option<Duration>)> {
+        let ttl = *self.methods.get(method.method_name())?;
+        let params = method.params().ok()?;
+        if params.get("finality").is_some() || params.get("sync_checkpoint").is_some() {
+            return None;
+        }
+        Some((
+            format!("{} {} {}", server_addr, method.method_name(), params),
+            ttl,
+        ))
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<serde_json::Value> {
+        self.store.get(key)
+    }
+
+    pub(crate) fn insert(&self, key: &str, value: serde_json::Value, ttl: Option<Duration>) {
+        self.store.insert(key, value, ttl)
+    }
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("methods", &self.methods)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods;
+    use near_primitives::types::{BlockId, BlockReference, Finality};
+
+    #[test]
+    fn only_configured_methods_with_fixed_blocks_are_cached() {
+        let cache = ResponseCache::in_memory().cache_forever("block");
+
+        let by_height = methods::block::RpcBlockRequest {
+            block_reference: BlockReference::BlockId(BlockId::Height(1)),
+        };
+        let by_finality = methods::block::RpcBlockRequest {
+            block_reference: BlockReference::Finality(Finality::Final),
+        };
+
+        assert!(matches!(
+            cache.entry("http://localhost:3030", &by_height),
+            Some((_, None))
+        ));
+        assert_eq!(cache.entry("http://localhost:3030", &by_finality), None);
+        assert_eq!(
+            cache.entry("http://localhost:3030", &methods::status::RpcStatusRequest),
+            None
+        );
+    }
+
+    #[test]
+    fn entries_expire() {
+        let dir = std::env::temp_dir().join(format!("near-jsonrpc-cache-{}", std::process::id()));
+        let stores: [Box<dyn CacheStore>; 2] = [
+            Box::new(MemoryStore::new()),
+            Box::new(DiskStore::new(&dir).unwrap()),
+        ];
+
+        for store in stores {
+            store.insert("forever", serde_json::json!(1), None);
+            store.insert("expired", serde_json::json!(2), Some(Duration::ZERO));
+            store.insert("distant", serde_json::json!(3), Some(Duration::MAX));
+
+            assert_eq!(store.get("forever"), Some(serde_json::json!(1)));
+            assert_eq!(store.get("expired"), None);
+            assert_eq!(store.get("distant"), Some(serde_json::json!(3)));
+            assert_eq!(store.get("missing"), None);
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn concurrent_disk_writes_dont_interleave() {
+        let dir =
+            std::env::temp_dir().join(format!("near-jsonrpc-cache-writes-{}", std::process::id()));
+        let store = Arc::new(DiskStore::new(&dir).unwrap());
+        let value = serde_json::json!(vec!["entry"; 10_000]);
+
+        let writers = (0..8)
+            .map(|_| {
+                let (store, value) = (store.clone(), value.clone());
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        store.insert("key", value.clone(), None);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(store.get("key"), Some(value));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
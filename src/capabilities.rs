@@ -0,0 +1,183 @@
+//! Capabilities of the node a client is connected to.
+//!
+//! Nodes at different versions, and with different configurations, support different methods and
+//! retain different amounts of history. [`JsonRpcClient::capabilities`] inspects the `status` of
+//! a node, along with the methods it's known to have rejected, so applications can branch before
+//! issuing calls the node doesn't support.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::JsonRpcClient;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let capabilities = client.capabilities().await?;
+//!
+//! if !capabilities.is_archival() {
+//!     println!("historical queries need an archival node");
+//! }
+//! if !capabilities.supports("send_tx") {
+//!     println!("falling back to broadcast_tx_commit");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`JsonRpcClient::capabilities`]: crate::JsonRpcClient::capabilities
+use std::collections::HashSet;
+
+use near_primitives::views::StatusResponse;
+
+/// Node versions that introduced methods, for methods newer than the oldest supported nodes.
+const INTRODUCED_IN: [(&str, Version); 1] = [("send_tx", Version(1, 37, 0))];
+
+/// The history kept by non-archival nodes, around 5 epochs by default.
+pub(crate) const GC_WINDOW: u64 = 5 * 43_200;
+
+/// Nodes keeping well over the default history are assumed to keep all of it.
+const ARCHIVAL_RETENTION: u64 = 2 * GC_WINDOW;
+
+/// A release version of a node.
+#[derive(Eq, Copy, Clone, Debug, PartialEq, PartialOrd, Ord)]
+pub struct Version(pub u64, pub u64, pub u64);
+
+impl Version {
+    /// Parses versions like `2.3.0` or `2.3.0-rc.1`, ignoring any pre-release suffix.
+    ///
+    /// Nodes built from unreleased sources report versions like `trunk`, which aren't parsed.
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.split(['-', '+']).next()?;
+        let mut parts = version.split('.').map(|part| part.parse().ok());
+        let version = Version(parts.next()??, parts.next()??, parts.next()??);
+        parts.next().is_none().then_some(version)
+    }
+}
+
+/// What the node a client is connected to supports.
+#[derive(Debug)]
+pub struct Capabilities {
+    status: StatusResponse,
+    version: Option<Version>,
+    unsupported_methods: HashSet<String>,
+}
+
+impl Capabilities {
+    pub(crate) fn new(status: StatusResponse, unsupported_methods: HashSet<String>) -> Self {
+        Self {
+            version: Version::parse(&status.version.version),
+            status,
+            unsupported_methods,
+        }
+    }
+
+    /// The status of the node the capabilities were derived from.
+    pub fn status(&self) -> &StatusResponse {
+        &self.status
+    }
+
+    /// The release version of the node, if it's a release build.
+    pub fn version(&self) -> Option<Version> {
+        self.version
+    }
+
+    pub fn chain_id(&self) -> &str {
+        &self.status.chain_id
+    }
+
+    pub fn protocol_version(&self) -> u32 {
+        self.status.protocol_version
+    }
+
+    /// Returns true if the node keeps the history of the chain since genesis.
+    ///
+    /// This is inferred from the earliest block the node has, and may report young chains, like
+    /// those of local networks, as archival.
+    pub fn is_archival(&self) -> bool {
+        let sync_info = &self.status.sync_info;
+        if sync_info.earliest_block_hash == Some(self.status.genesis_hash) {
+            return true;
+        }
+        sync_info.earliest_block_height.map_or(false, |earliest| {
+            sync_info.latest_block_height.saturating_sub(earliest) > ARCHIVAL_RETENTION
+        })
+    }
+
+    /// Returns false if the node is known not to support the method.
+    ///
+    /// Methods are known to be unsupported once the node rejected them as unknown, or when they
+    /// were introduced after the version of the node. Methods are otherwise assumed to be
+    /// supported.
+    pub fn supports(&self, method_name: &str) -> bool {
+        if self.unsupported_methods.contains(method_name) {
+            return false;
+        }
+        match (
+            self.version,
+            INTRODUCED_IN.iter().find(|(name, _)| *name == method_name),
+        ) {
+            (Some(version), Some((_, introduced_in))) => version >= *introduced_in,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(version: &str, earliest_block_height: u64) -> StatusResponse {
+        serde_json::from_value(serde_json::json!({
+            "version": { "version": version, "build": "crates-io" },
+            "chain_id": "testnet",
+            "protocol_version": 73,
+            "latest_protocol_version": 73,
+            "validators": [],
+            "sync_info": {
+                "latest_block_hash": "11111111111111111111111111111111",
+                "latest_block_height": 180_000_000,
+                "latest_state_root": "11111111111111111111111111111111",
+                "latest_block_time": "2024-01-01T00:00:00Z",
+                "syncing": false,
+                "earliest_block_hash": null,
+                "earliest_block_height": earliest_block_height,
+                "earliest_block_time": null,
+                "epoch_id": null,
+                "epoch_start_height": null,
+            },
+            "validator_account_id": null,
+            "validator_public_key": null,
+            "node_public_key": "ed25519:9KnjTjL6vVoM8heHvCcTgLZ67FwFkiLsNtknFAVsVvYY",
+            "node_key": null,
+            "uptime_sec": 1,
+            "genesis_hash": "4vAXDBNn6D5pVGJeFZ5GAPqCLXRLUpYsgvFhNTpDD3xN",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn versions_are_parsed() {
+        assert_eq!(Version::parse("2.3.0"), Some(Version(2, 3, 0)));
+        assert_eq!(Version::parse("1.37.0-rc.2"), Some(Version(1, 37, 0)));
+        assert_eq!(Version::parse("trunk"), None);
+        assert_eq!(Version::parse("1.2"), None);
+    }
+
+    #[test]
+    fn capabilities_are_derived_from_status() {
+        let capabilities = Capabilities::new(status("1.36.1", 179_800_000), HashSet::new());
+        assert!(!capabilities.is_archival());
+        assert!(!capabilities.supports("send_tx"));
+        assert!(capabilities.supports("status"));
+
+        let capabilities = Capabilities::new(
+            status("trunk", 42_376_888),
+            ["maintenance_windows".to_string()].into(),
+        );
+        assert!(capabilities.is_archival());
+        assert!(capabilities.supports("send_tx"));
+        assert!(!capabilities.supports("maintenance_windows"));
+    }
+}
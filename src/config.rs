@@ -0,0 +1,164 @@
+//! Client configuration from the environment or configuration files.
+//!
+//! [`ClientConfig`] gathers the settings most services wire up by hand: the server address,
+//! an API key, a timeout and retries. It can be read from environment variables, or deserialized
+//! from any format supported by `serde` as part of a larger configuration.
+//!
+//! | Variable               | Setting                           |
+//! |------------------------|-----------------------------------|
+//! | `NEAR_RPC_URL`         | [`server_addr`](ClientConfig::server_addr) (required) |
+//! | `NEAR_RPC_API_KEY`     | [`api_key`](ClientConfig::api_key) |
+//! | `NEAR_RPC_TIMEOUT_MS`  | [`timeout_ms`](ClientConfig::timeout_ms) |
+//! | `NEAR_RPC_MAX_RETRIES` | [`max_retries`](ClientConfig::max_retries) |
+//!
+//! ## Example
+//!
+//! ```
+//! use near_jsonrpc_client::config::ClientConfig;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let config: ClientConfig = serde_json::from_str(
+//!     r#"{ "server_addr": "https://rpc.testnet.near.org", "timeout_ms": 5000, "max_retries": 3 }"#,
+//! )?;
+//!
+//! let client = config.connect()?;
+//! # Ok(())
+//! # }
+//! ```
+use std::env;
+use std::time::Duration;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::errors::InvalidServerAddrError;
+use crate::header::InvalidHeaderValue;
+use crate::{auth, retry, JsonRpcClient};
+
+/// Settings for constructing a [`JsonRpcClient`].
+#[derive(Clone, Deserialize)]
+pub struct ClientConfig {
+    /// Address of the RPC server.
+    pub server_addr: String,
+    /// API key sent in the `x-api-key` header.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Timeout of method calls, in milliseconds.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Maximum number of retries of failed method calls.
+    #[serde(default)]
+    pub max_retries: Option<u32>,
+}
+
+/// Potential errors returned while loading a [`ClientConfig`] or connecting with it.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// A required environment variable isn't set.
+    #[error("missing environment variable: [{0}]")]
+    MissingVar(&'static str),
+    /// An environment variable holds an invalid value.
+    #[error("invalid value for environment variable {name}: [{value}]")]
+    InvalidVar { name: &'static str, value: String },
+    /// The configured server address is invalid.
+    #[error(transparent)]
+    InvalidServerAddr(#[from] InvalidServerAddrError),
+    /// The configured API key isn't a valid header value.
+    #[error("invalid API key: [{0}]")]
+    InvalidApiKey(#[from] InvalidHeaderValue),
+}
+
+fn var(name: &'static str) -> Result<Option<String>, ConfigError> {
+    match env::var(name) {
+        Ok(value) => Ok(Some(value)),
+        Err(env::VarError::NotPresent) => Ok(None),
+        Err(env::VarError::NotUnicode(value)) => Err(ConfigError::InvalidVar {
+            name,
+            value: value.to_string_lossy().into_owned(),
+        }),
+    }
+}
+
+fn parsed_var<T: std::str::FromStr>(name: &'static str) -> Result<Option<T>, ConfigError> {
+    var(name)?
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| ConfigError::InvalidVar { name, value })
+        })
+        .transpose()
+}
+
+impl ClientConfig {
+    /// Create a configuration for the specified server, with every other setting unset.
+    pub fn new(server_addr: impl Into<String>) -> Self {
+        Self {
+            server_addr: server_addr.into(),
+            api_key: None,
+            timeout_ms: None,
+            max_retries: None,
+        }
+    }
+
+    /// Read the configuration from the `NEAR_RPC_*` environment variables.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Ok(Self {
+            server_addr: var("NEAR_RPC_URL")?.ok_or(ConfigError::MissingVar("NEAR_RPC_URL"))?,
+            api_key: var("NEAR_RPC_API_KEY")?,
+            timeout_ms: parsed_var("NEAR_RPC_TIMEOUT_MS")?,
+            max_retries: parsed_var("NEAR_RPC_MAX_RETRIES")?,
+        })
+    }
+
+    /// Connect to the configured server with the configured settings.
+    pub fn connect(&self) -> Result<JsonRpcClient, ConfigError> {
+        let mut connector = JsonRpcClient::new_client();
+        if let Some(timeout) = self.timeout_ms {
+            connector = connector.timeout(Duration::from_millis(timeout));
+        }
+        if let Some(max_retries) = self.max_retries {
+            connector = connector.retry_policy(retry::RetryPolicy::new(max_retries));
+        }
+
+        let mut client = connector.try_connect(&self.server_addr)?;
+        if let Some(api_key) = &self.api_key {
+            client = client.header(auth::ApiKey::new(api_key)?);
+        }
+        Ok(client)
+    }
+}
+
+impl std::fmt::Debug for ClientConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientConfig")
+            .field("server_addr", &self.server_addr)
+            .field("api_key", &self.api_key.as_ref().map(|_| "[redacted]"))
+            .field("timeout_ms", &self.timeout_ms)
+            .field("max_retries", &self.max_retries)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connects_with_config() -> Result<(), Box<dyn std::error::Error>> {
+        let config: ClientConfig = serde_json::from_str(
+            r#"{ "server_addr": "https://rpc.testnet.near.org", "api_key": "some-key" }"#,
+        )?;
+
+        let client = config.connect()?;
+        assert_eq!(client.headers()["x-api-key"], "some-key");
+        assert!(!format!("{:?}", config).contains("some-key"));
+
+        let config = ClientConfig::new("rpc.testnet.near.org");
+        assert!(matches!(
+            config.connect(),
+            Err(ConfigError::InvalidServerAddr(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+//! Checking the congestion of shards.
+//!
+//! Shards accepting more transactions and receipts than they can execute become congested. The
+//! nodes start rejecting transactions for the receivers of a congested shard once its congestion
+//! level, ranging from 0 to 1, reaches 1. [`JsonRpcClient::congestion_level`] looks up the shard
+//! of an account, and its congestion level at the latest block.
+//!
+//! [`TransactionBuilder`]s can check the congestion of the shard of their receiver before sending
+//! the transaction, see [`max_congestion`](crate::transaction::TransactionBuilder::max_congestion).
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::JsonRpcClient;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.mainnet.near.org");
+//!
+//! let congestion = client.congestion_level(&"game.hot.tg".parse()?).await?;
+//!
+//! println!(
+//!     "shard {} is at congestion level {}",
+//!     congestion.shard_id, congestion.congestion_level
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`TransactionBuilder`]: crate::transaction::TransactionBuilder
+use near_jsonrpc_primitives::types::chunks::ChunkReference;
+use near_primitives::hash::CryptoHash;
+use near_primitives::shard_layout::account_id_to_shard_id;
+use near_primitives::types::{AccountId, BlockId, BlockReference, ShardId};
+
+use crate::errors::JsonRpcError;
+use crate::methods::block::{RpcBlockError, RpcBlockRequest};
+use crate::methods::EXPERIMENTAL_congestion_level::{
+    RpcCongestionLevelError, RpcCongestionLevelRequest,
+};
+use crate::methods::EXPERIMENTAL_protocol_config::{
+    RpcProtocolConfigError, RpcProtocolConfigRequest,
+};
+use crate::JsonRpcClient;
+
+/// Potential errors returned while checking the congestion of a shard.
+#[derive(Debug, thiserror::Error)]
+pub enum CongestionError {
+    /// The latest block couldn't be viewed.
+    #[error(transparent)]
+    BlockError(Box<JsonRpcError<RpcBlockError>>),
+    /// The protocol config, holding the shard layout, couldn't be viewed.
+    #[error(transparent)]
+    ProtocolConfigError(Box<JsonRpcError<RpcProtocolConfigError>>),
+    /// The congestion level of the shard couldn't be viewed.
+    #[error(transparent)]
+    CongestionLevelError(Box<JsonRpcError<RpcCongestionLevelError>>),
+}
+
+impl From<JsonRpcError<RpcBlockError>> for CongestionError {
+    fn from(err: JsonRpcError<RpcBlockError>) -> Self {
+        Self::BlockError(Box::new(err))
+    }
+}
+
+impl From<JsonRpcError<RpcProtocolConfigError>> for CongestionError {
+    fn from(err: JsonRpcError<RpcProtocolConfigError>) -> Self {
+        Self::ProtocolConfigError(Box::new(err))
+    }
+}
+
+impl From<JsonRpcError<RpcCongestionLevelError>> for CongestionError {
+    fn from(err: JsonRpcError<RpcCongestionLevelError>) -> Self {
+        Self::CongestionLevelError(Box::new(err))
+    }
+}
+
+/// The congestion level of a shard at a block.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShardCongestion {
+    /// The shard.
+    pub shard_id: ShardId,
+    /// The block the congestion level was viewed at.
+    pub block_hash: CryptoHash,
+    /// The congestion level, from 0 for a shard that isn't congested, to 1 for a shard
+    /// rejecting new transactions.
+    pub congestion_level: f64,
+}
+
+impl JsonRpcClient {
+    /// View the congestion level, at the latest block, of the shard holding `account_id`.
+    pub async fn congestion_level(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<ShardCongestion, CongestionError> {
+        let block_hash = self.latest_block_hash().await?;
+        let config = self
+            .call(RpcProtocolConfigRequest {
+                block_reference: BlockReference::BlockId(BlockId::Hash(block_hash)),
+            })
+            .await?;
+        let shard_id = account_id_to_shard_id(account_id, &config.shard_layout);
+        self.shard_congestion_at(block_hash, shard_id).await
+    }
+
+    /// View the congestion level of `shard_id` at the latest block, without looking the shard
+    /// layout up again.
+    pub(crate) async fn shard_congestion(
+        &self,
+        shard_id: ShardId,
+    ) -> Result<ShardCongestion, CongestionError> {
+        let block_hash = self.latest_block_hash().await?;
+        self.shard_congestion_at(block_hash, shard_id).await
+    }
+
+    async fn latest_block_hash(&self) -> Result<CryptoHash, CongestionError> {
+        let block = self
+            .call(RpcBlockRequest {
+                block_reference: BlockReference::latest(),
+            })
+            .await?;
+        Ok(block.header.hash)
+    }
+
+    async fn shard_congestion_at(
+        &self,
+        block_hash: CryptoHash,
+        shard_id: ShardId,
+    ) -> Result<ShardCongestion, CongestionError> {
+        let response = self
+            .call(RpcCongestionLevelRequest {
+                chunk_reference: ChunkReference::BlockShardId {
+                    block_id: BlockId::Hash(block_hash),
+                    shard_id,
+                },
+            })
+            .await?;
+        Ok(ShardCongestion {
+            shard_id,
+            block_hash,
+            congestion_level: response.congestion_level,
+        })
+    }
+}
@@ -0,0 +1,154 @@
+//! Handles on contracts.
+//!
+//! A [`ContractRef`] is derived from a [`JsonRpcClient`] with [`JsonRpcClient::contract`], and
+//! bundles the views of a contract along with the actions calling it. The low-level methods are
+//! still available through the client it was derived from.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::JsonRpcClient;
+//! use serde_json::json;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.mainnet.near.org");
+//!
+//! let pool = client.contract("astro-stakers.poolv1.near".parse()?);
+//!
+//! let staked = pool
+//!     .view::<String>(
+//!         "get_account_staked_balance",
+//!         json!({ "account_id": "alice.near" }),
+//!     )
+//!     .await?;
+//! println!("staked: {} yoctoNEAR", staked.result);
+//!
+//! // an action to include in a transaction sent to the pool
+//! let deposit_and_stake = pool.call(
+//!     "deposit_and_stake",
+//!     json!({}),
+//!     50_000_000_000_000,                // 50 TeraGas
+//!     1_000_000_000_000_000_000_000_000, // 1 NEAR
+//! );
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::BTreeMap;
+
+use near_primitives::transaction::{Action, FunctionCallAction};
+use near_primitives::types::{AccountId, Balance, BlockReference, Finality, Gas};
+use serde::de::DeserializeOwned;
+
+use crate::errors::ViewError;
+use crate::views::ViewFunctionResult;
+use crate::JsonRpcClient;
+
+/// A handle on a contract.
+#[derive(Clone, Debug)]
+pub struct ContractRef {
+    client: JsonRpcClient,
+    contract_id: AccountId,
+    block_reference: BlockReference,
+}
+
+impl ContractRef {
+    pub(crate) fn new(client: JsonRpcClient, contract_id: AccountId) -> Self {
+        Self {
+            client,
+            contract_id,
+            block_reference: BlockReference::Finality(Finality::Final),
+        }
+    }
+
+    /// The account the contract is deployed to.
+    pub fn id(&self) -> &AccountId {
+        &self.contract_id
+    }
+
+    /// The block the contract is viewed at, the latest final block by default.
+    pub fn block_reference(&self) -> &BlockReference {
+        &self.block_reference
+    }
+
+    /// View the contract at the block `block_reference`.
+    pub fn at(mut self, block_reference: BlockReference) -> Self {
+        self.block_reference = block_reference;
+        self
+    }
+
+    /// Call a view function of the contract, see [`JsonRpcClient::view_function`].
+    pub async fn view<T: DeserializeOwned>(
+        &self,
+        method_name: impl Into<String>,
+        args: serde_json::Value,
+    ) -> Result<ViewFunctionResult<T>, ViewError> {
+        self.client
+            .view_function(
+                self.contract_id.clone(),
+                method_name,
+                args,
+                self.block_reference.clone(),
+            )
+            .await
+    }
+
+    /// Get the state of the contract, see [`JsonRpcClient::view_state`].
+    pub async fn state(&self, prefix: &[u8]) -> Result<BTreeMap<Vec<u8>, Vec<u8>>, ViewError> {
+        self.client
+            .view_state(
+                self.contract_id.clone(),
+                prefix,
+                self.block_reference.clone(),
+            )
+            .await
+    }
+
+    /// An action calling a function of the contract, with JSON arguments.
+    ///
+    /// The action must be included in a transaction whose receiver is the contract.
+    pub fn call(
+        &self,
+        method_name: impl Into<String>,
+        args: serde_json::Value,
+        gas: Gas,
+        deposit: Balance,
+    ) -> Action {
+        Action::FunctionCall(Box::new(FunctionCallAction {
+            method_name: method_name.into(),
+            args: args.to_string().into_bytes(),
+            gas,
+            deposit,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calls_are_function_call_actions() {
+        let client = JsonRpcClient::connect("http://localhost:3030");
+        let contract = client.contract("pool.near".parse().unwrap());
+        assert_eq!(
+            contract.block_reference(),
+            &BlockReference::Finality(Finality::Final)
+        );
+
+        let action = contract.call("ping", serde_json::json!({ "force": true }), 10, 1);
+        assert_eq!(
+            action,
+            Action::FunctionCall(Box::new(FunctionCallAction {
+                method_name: "ping".to_string(),
+                args: br#"{"force":true}"#.to_vec(),
+                gas: 10,
+                deposit: 1,
+            }))
+        );
+
+        let contract = contract.at(BlockReference::latest());
+        assert_eq!(contract.id().as_str(), "pool.near");
+        assert_eq!(contract.block_reference(), &BlockReference::latest());
+    }
+}
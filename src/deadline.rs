@@ -0,0 +1,93 @@
+//! Deadlines for method calls.
+//!
+//! A [`Deadline`] is an absolute point in time by which an answer is expected. Attaching one to a
+//! client (See [`JsonRpcClient::with_deadline`](crate::JsonRpcClient::with_deadline)) makes every
+//! call made through it, including those issued internally by retries and helpers, budget
+//! against the same total time.
+use std::time::{Duration, Instant};
+
+/// How far away a deadline too distant to be represented is set, roughly 30 years.
+const FAR_FUTURE: Duration = Duration::from_secs(86400 * 365 * 30);
+
+/// An absolute point in time by which a method call must complete.
+#[derive(Eq, Copy, Hash, Clone, Debug, PartialEq, PartialOrd, Ord)]
+pub struct Deadline(Instant);
+
+impl Deadline {
+    /// Creates a deadline that expires after the specified duration from now.
+    ///
+    /// Durations too long to be represented, like `Duration::MAX`, are capped to a deadline far
+    /// in the future.
+    pub fn after(duration: Duration) -> Self {
+        let now = Instant::now();
+        Self(
+            now.checked_add(duration)
+                .or_else(|| now.checked_add(FAR_FUTURE))
+                .unwrap_or(now),
+        )
+    }
+
+    /// Creates a deadline that expires at the specified instant.
+    pub fn at(instant: Instant) -> Self {
+        Self(instant)
+    }
+
+    /// Returns the instant at which this deadline expires.
+    pub fn instant(&self) -> Instant {
+        self.0
+    }
+
+    /// Returns the time left until this deadline expires, or `None` if it already has.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.0
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Returns `true` if this deadline has expired.
+    pub fn has_elapsed(&self) -> bool {
+        self.remaining().is_none()
+    }
+
+    /// Returns the lesser of the time left until this deadline and the specified timeout.
+    pub(crate) fn budget(&self, timeout: Option<Duration>) -> Option<Duration> {
+        let remaining = self.remaining()?;
+        Some(timeout.map_or(remaining, |timeout| timeout.min(remaining)))
+    }
+}
+
+impl From<Instant> for Deadline {
+    fn from(instant: Instant) -> Self {
+        Self::at(instant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_is_capped_by_remaining_time() {
+        let deadline = Deadline::after(Duration::from_secs(5));
+
+        assert!(!deadline.has_elapsed());
+        assert!(deadline.budget(Some(Duration::from_secs(60))).unwrap() <= Duration::from_secs(5));
+        assert_eq!(
+            deadline.budget(Some(Duration::from_secs(1))),
+            Some(Duration::from_secs(1))
+        );
+
+        let elapsed = Deadline::at(Instant::now() - Duration::from_secs(1));
+
+        assert!(elapsed.has_elapsed());
+        assert_eq!(elapsed.budget(None), None);
+    }
+
+    #[test]
+    fn distant_deadlines_saturate() {
+        let deadline = Deadline::after(Duration::MAX);
+
+        assert!(!deadline.has_elapsed());
+        assert!(deadline.remaining().unwrap() > Duration::from_secs(86400 * 365));
+    }
+}
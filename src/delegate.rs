@@ -0,0 +1,260 @@
+//! Meta transactions, as specified by [NEP-366](https://github.com/near/NEPs/blob/master/neps/nep-0366.md).
+//!
+//! In a meta transaction, a user signs the actions they want executed in a delegate action,
+//! without paying for gas. A relayer then wraps the signed delegate action into a transaction of
+//! its own, paying for its gas, and sends it.
+//!
+//! On the user side, a [`DelegateActionBuilder`] is derived from a [`JsonRpcClient`] with
+//! [`JsonRpcClient::delegate_action`]. It collects the actions, then views the access key of the
+//! user for its nonce and the latest block height, and signs the delegate action. On the relayer
+//! side, [`JsonRpcClient::relay`] checks the signature of the delegate action received, and wraps
+//! it into a [`TransactionBuilder`] signed by the relayer.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::JsonRpcClient;
+//! use near_primitives::transaction::{Action, TransferAction};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let user = near_crypto::InMemorySigner::from_secret_key(
+//!     "fido.testnet".parse()?,
+//!     "ed25519:12dhevYshfiRqFSu8DSfxA27pTkmGRv6C5qQWTJYTcBEoB7MSTyidghi5NWXzWqrxCKgxVx97bpXPYQxYN5dieU".parse()?,
+//! );
+//!
+//! // signed by the user, then handed to the relayer
+//! let signed_delegate_action = client
+//!     .delegate_action(user, "rpc_docs.testnet".parse()?)
+//!     .action(Action::Transfer(TransferAction { deposit: 1 }))
+//!     .sign()
+//!     .await?;
+//!
+//! let relayer = near_crypto::InMemorySigner::from_secret_key(
+//!     "relayer.testnet".parse()?,
+//!     "ed25519:3D4YudUahN1nawWogh8pAKSj92sUNMdbZGjn7kERKzYoTy8tnFQuwoGUC51DowKqorvkr2pytJSnwuSbsNVfqygr".parse()?,
+//! );
+//!
+//! let response = client.relay(relayer, signed_delegate_action)?.send().await?;
+//!
+//! println!("{:?}", response.final_execution_outcome);
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+use std::sync::Arc;
+
+use near_primitives::action::delegate::{DelegateAction, NonDelegateAction, SignedDelegateAction};
+use near_primitives::signable_message::{SignableMessage, SignableMessageType};
+use near_primitives::transaction::Action;
+use near_primitives::types::{AccountId, BlockHeight, BlockReference};
+
+use crate::errors::{SignerError, ViewError};
+use crate::signer::TransactionSigner;
+use crate::transaction::TransactionBuilder;
+use crate::JsonRpcClient;
+
+/// Potential errors returned while signing or relaying a delegate action.
+#[derive(Debug, thiserror::Error)]
+pub enum DelegateActionError {
+    /// The access key of the signer couldn't be viewed, to get its nonce and the latest block
+    /// height.
+    #[error("error while viewing the access key of the signer: [{0}]")]
+    AccessKeyError(ViewError),
+    /// The delegate action couldn't be signed.
+    #[error(transparent)]
+    SignError(#[from] SignerError),
+    /// Delegate actions can't contain delegate actions themselves.
+    #[error("delegate actions can't be nested")]
+    NestedDelegateAction,
+    /// The signature of the delegate action doesn't match its sender and public key.
+    #[error("the signature of the delegate action is invalid")]
+    InvalidSignature,
+}
+
+/// A delegate action being built, to be signed and handed to a relayer.
+#[derive(Clone)]
+pub struct DelegateActionBuilder {
+    client: JsonRpcClient,
+    signer: Arc<dyn TransactionSigner>,
+    receiver_id: AccountId,
+    actions: Vec<Action>,
+    ttl: BlockHeight,
+}
+
+impl DelegateActionBuilder {
+    pub(crate) fn new(
+        client: JsonRpcClient,
+        signer: Arc<dyn TransactionSigner>,
+        receiver_id: AccountId,
+    ) -> Self {
+        Self {
+            client,
+            signer,
+            receiver_id,
+            actions: Vec::new(),
+            ttl: 120,
+        }
+    }
+
+    /// Add an action to the delegate action.
+    pub fn action(mut self, action: Action) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Add actions to the delegate action.
+    pub fn actions(mut self, actions: impl IntoIterator<Item = Action>) -> Self {
+        self.actions.extend(actions);
+        self
+    }
+
+    /// Let the relayer send the delegate action for `ttl` blocks past the latest one, after which
+    /// it expires.
+    ///
+    /// Defaults to 120 blocks, roughly two minutes.
+    pub fn ttl(mut self, ttl: BlockHeight) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sign the delegate action, using the next nonce of the access key of the signer.
+    pub async fn sign(&self) -> Result<SignedDelegateAction, DelegateActionError> {
+        let actions = self
+            .actions
+            .iter()
+            .cloned()
+            .map(NonDelegateAction::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| DelegateActionError::NestedDelegateAction)?;
+        let (access_key, _, block_height) = self
+            .client
+            .view_access_key(
+                self.signer.account_id().clone(),
+                self.signer.public_key().clone(),
+                BlockReference::latest(),
+            )
+            .await
+            .map_err(DelegateActionError::AccessKeyError)?;
+        self.sign_with(
+            actions,
+            access_key.nonce + 1,
+            block_height.saturating_add(self.ttl),
+        )
+        .await
+    }
+
+    async fn sign_with(
+        &self,
+        actions: Vec<NonDelegateAction>,
+        nonce: u64,
+        max_block_height: BlockHeight,
+    ) -> Result<SignedDelegateAction, DelegateActionError> {
+        let delegate_action = DelegateAction {
+            sender_id: self.signer.account_id().clone(),
+            receiver_id: self.receiver_id.clone(),
+            actions,
+            nonce,
+            max_block_height,
+            public_key: self.signer.public_key().clone(),
+        };
+        // the signer signs the hash of the message, as NEP-461 expects
+        let message = borsh::to_vec(&SignableMessage::new(
+            &delegate_action,
+            SignableMessageType::DelegateAction,
+        ))
+        .map_err(SignerError::new)?;
+        let signature = self.signer.sign(&message).await?;
+        Ok(SignedDelegateAction {
+            delegate_action,
+            signature,
+        })
+    }
+}
+
+impl fmt::Debug for DelegateActionBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DelegateActionBuilder")
+            .field("client", &self.client)
+            .field("sender_id", self.signer.account_id())
+            .field("public_key", self.signer.public_key())
+            .field("receiver_id", &self.receiver_id)
+            .field("actions", &self.actions)
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+impl JsonRpcClient {
+    /// Start building a delegate action for `signer` to sign, executing actions on `receiver_id`
+    /// once relayed.
+    ///
+    /// See the [`delegate`](crate::delegate) module for details.
+    pub fn delegate_action(
+        &self,
+        signer: impl TransactionSigner + 'static,
+        receiver_id: AccountId,
+    ) -> DelegateActionBuilder {
+        DelegateActionBuilder::new(self.clone(), Arc::new(signer), receiver_id)
+    }
+
+    /// Wrap `signed_delegate_action` into a transaction signed by `relayer`, sent to the sender of
+    /// the delegate action, after checking its signature.
+    ///
+    /// The relayer pays for the gas of the transaction, and of the actions delegated.
+    pub fn relay(
+        &self,
+        relayer: impl TransactionSigner + 'static,
+        signed_delegate_action: SignedDelegateAction,
+    ) -> Result<TransactionBuilder, DelegateActionError> {
+        if !signed_delegate_action.verify() {
+            return Err(DelegateActionError::InvalidSignature);
+        }
+        let sender_id = signed_delegate_action.delegate_action.sender_id.clone();
+        Ok(self
+            .transaction(relayer, sender_id)
+            .action(Action::Delegate(Box::new(signed_delegate_action))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use near_crypto::{InMemorySigner, KeyType, SecretKey};
+    use near_primitives::transaction::TransferAction;
+
+    #[tokio::test]
+    async fn delegate_actions_are_relayed() {
+        let client = JsonRpcClient::connect("http://localhost:3030");
+        let user = InMemorySigner::from_secret_key(
+            "alice.near".parse().unwrap(),
+            SecretKey::from_random(KeyType::ED25519),
+        );
+        let relayer = InMemorySigner::from_secret_key(
+            "relayer.near".parse().unwrap(),
+            SecretKey::from_random(KeyType::ED25519),
+        );
+
+        let transfer = Action::Transfer(TransferAction { deposit: 1 });
+        let mut signed = client
+            .delegate_action(user, "bob.near".parse().unwrap())
+            .sign_with(vec![NonDelegateAction::try_from(transfer).unwrap()], 8, 100)
+            .await
+            .unwrap();
+        assert!(signed.verify());
+        assert_eq!(signed.delegate_action.sender_id.as_str(), "alice.near");
+        assert_eq!(signed.delegate_action.receiver_id.as_str(), "bob.near");
+
+        let relayed = client.relay(relayer.clone(), signed.clone()).unwrap();
+        assert!(format!("{:?}", relayed).contains("receiver_id: AccountId(\"alice.near\")"));
+
+        signed.delegate_action.nonce += 1;
+        assert!(matches!(
+            client.relay(relayer, signed),
+            Err(DelegateActionError::InvalidSignature)
+        ));
+    }
+}
@@ -0,0 +1,144 @@
+//! Client for the plain HTTP endpoints of a node.
+//!
+//! Besides JSON RPC, nodes serve a few endpoints over plain `GET` requests: `/status`,
+//! `/health`, `/network_info` and `/metrics`. An [`HttpClient`] is derived from a
+//! [`JsonRpcClient`], sharing its connection pool, server address, headers, timeouts and deadline.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::{auth, JsonRpcClient};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org")
+//!     .header(auth::ApiKey::new("399ba741-e939-4ffa-8c3c-306ec36fa8de")?);
+//!
+//! let http = client.http();
+//!
+//! http.health().await?;
+//!
+//! let status = http.status().await?;
+//! println!("latest block: {}", status.sync_info.latest_block_height);
+//!
+//! let metrics = http.metrics().await?;
+//! # Ok(())
+//! # }
+//! ```
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+
+use crate::errors::{HttpEndpointError, HttpError, InvalidServerAddrError};
+use crate::methods::{network_info::RpcNetworkInfoResponse, status::RpcStatusResponse};
+use crate::{transport, JsonRpcClient};
+
+/// A client for the plain HTTP endpoints of a node.
+#[derive(Clone, Debug)]
+pub struct HttpClient {
+    client: JsonRpcClient,
+}
+
+impl HttpClient {
+    pub(crate) fn new(client: JsonRpcClient) -> Self {
+        Self { client }
+    }
+
+    /// Get the status of the node, same as the `status` method.
+    pub async fn status(&self) -> Result<RpcStatusResponse, HttpEndpointError> {
+        self.get_json("status").await
+    }
+
+    /// Check the health of the node.
+    ///
+    /// Unhealthy nodes respond with a `503 Service Unavailable` status, returned as an error.
+    pub async fn health(&self) -> Result<(), HttpEndpointError> {
+        self.get("health").await.map(drop)
+    }
+
+    /// Get the state of the network connections of the node, same as the `network_info` method.
+    pub async fn network_info(&self) -> Result<RpcNetworkInfoResponse, HttpEndpointError> {
+        self.get_json("network_info").await
+    }
+
+    /// Get the metrics of the node, in the Prometheus text format.
+    pub async fn metrics(&self) -> Result<String, HttpEndpointError> {
+        let body = self.get("metrics").await?;
+        String::from_utf8(body.to_vec())
+            .map_err(|err| HttpEndpointError::InvalidResponse(Box::new(err)))
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, endpoint: &str) -> Result<T, HttpEndpointError> {
+        let body = self.get(endpoint).await?;
+        serde_json::from_slice(&body)
+            .map_err(|err| HttpEndpointError::InvalidResponse(Box::new(err)))
+    }
+
+    async fn get(&self, endpoint: &str) -> Result<Bytes, HttpEndpointError> {
+        let inner = &self.client.inner;
+        let mut url = inner
+            .server_url
+            .clone()
+            .map_err(|err| HttpEndpointError::InvalidServerAddr(err.into()))?;
+        let scheme = url.scheme().to_string();
+        // keep any path the server address has, like the API key of some providers
+        url.path_segments_mut()
+            .map_err(|_| {
+                HttpEndpointError::InvalidServerAddr(InvalidServerAddrError::UnsupportedScheme(
+                    scheme,
+                ))
+            })?
+            .pop_if_empty()
+            .push(endpoint);
+
+        let timeout = match self.client.deadline {
+            Some(deadline) => Some(
+                deadline
+                    .budget(inner.timeouts.get(endpoint))
+                    .ok_or(HttpEndpointError::DeadlineExceeded)?,
+            ),
+            None => inner.timeouts.get(endpoint),
+        };
+
+        // the URL may hold the API key of the provider, only log the redacted server address
+        log::debug!("GET /{} on {}", endpoint, inner.server_addr);
+        let response = inner
+            .client
+            .get(&url, http::HeaderMap::clone(&self.client.headers), timeout)
+            .await
+            .map_err(|err| {
+                let err = err.with_context(endpoint, &inner.server_addr);
+                self.timed_out(err, HttpEndpointError::SendError)
+            })?;
+        if response.status != http::StatusCode::OK {
+            return Err(HttpEndpointError::ResponseStatusError {
+                status: response.status,
+            });
+        }
+
+        response
+            .bytes(inner.max_response_size)
+            .await
+            .map_err(|err| match err {
+                transport::ReadError::Http(err) => self.timed_out(
+                    err.with_context(endpoint, &inner.server_addr),
+                    HttpEndpointError::RecvError,
+                ),
+                transport::ReadError::TooLarge => HttpEndpointError::ResponseTooLarge {
+                    limit: inner.max_response_size.unwrap_or(usize::MAX),
+                },
+            })
+    }
+
+    fn timed_out(
+        &self,
+        err: HttpError,
+        wrap: fn(HttpError) -> HttpEndpointError,
+    ) -> HttpEndpointError {
+        match self.client.deadline {
+            Some(deadline) if err.is_timeout() && deadline.has_elapsed() => {
+                HttpEndpointError::DeadlineExceeded
+            }
+            _ => wrap(err),
+        }
+    }
+}
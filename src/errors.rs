@@ -1,10 +1,352 @@
 //! Error types.
-use std::io;
+use std::time::Duration;
+use std::{error, fmt, io};
 
 use thiserror::Error;
 
 use near_jsonrpc_primitives::errors::{RpcError, RpcErrorKind, RpcRequestValidationErrorKind};
 use near_jsonrpc_primitives::message::{self, Message};
+use near_jsonrpc_primitives::types;
+use near_primitives::hash::CryptoHash;
+use near_primitives::types::{BlockHeight, ShardId};
+
+/// An error raised by the HTTP transport backing the client.
+///
+/// This wraps the underlying error of whichever transport backend is enabled.
+///
+/// Errors raised while making a request carry the method called and the address of the server,
+/// to tell apart failures of clients talking to several endpoints.
+pub struct HttpError {
+    kind: HttpErrorKind,
+    source: Box<dyn error::Error + Send + Sync>,
+    context: Option<Box<RequestContext>>,
+}
+
+#[derive(Debug)]
+struct RequestContext {
+    method_name: String,
+    server_addr: String,
+}
+
+/// The reason an HTTP request failed.
+#[non_exhaustive]
+#[derive(Eq, Copy, Clone, Debug, PartialEq)]
+pub enum HttpErrorKind {
+    /// The host of the server couldn't be resolved.
+    Dns,
+    /// The TLS handshake with the server failed, e.g. because of an invalid certificate.
+    Tls,
+    /// The server refused the connection.
+    ConnectionRefused,
+    /// The connection to the server couldn't be established for another reason.
+    Connect,
+    /// The request timed out before a response was received.
+    RequestTimeout,
+    /// The request timed out while reading the response body.
+    ReadTimeout,
+    /// Any other failure.
+    Other,
+}
+
+impl HttpError {
+    pub(crate) fn connect<E: Into<Box<dyn error::Error + Send + Sync>>>(source: E) -> Self {
+        let source = source.into();
+        Self {
+            kind: connect_error_kind(&*source),
+            source,
+            context: None,
+        }
+    }
+
+    pub(crate) fn timeout<E: Into<Box<dyn error::Error + Send + Sync>>>(source: E) -> Self {
+        Self {
+            kind: HttpErrorKind::RequestTimeout,
+            source: source.into(),
+            context: None,
+        }
+    }
+
+    pub(crate) fn other<E: Into<Box<dyn error::Error + Send + Sync>>>(source: E) -> Self {
+        Self {
+            kind: HttpErrorKind::Other,
+            source: source.into(),
+            context: None,
+        }
+    }
+
+    /// Marks timeouts as having happened while reading the response body.
+    pub(crate) fn while_reading(mut self) -> Self {
+        if self.kind == HttpErrorKind::RequestTimeout {
+            self.kind = HttpErrorKind::ReadTimeout;
+        }
+        self
+    }
+
+    /// Records the method called and the server it was sent to.
+    pub(crate) fn with_context(mut self, method_name: &str, server_addr: &str) -> Self {
+        self.context = Some(Box::new(RequestContext {
+            method_name: method_name.to_string(),
+            server_addr: server_addr.to_string(),
+        }));
+        self
+    }
+
+    /// Returns the reason the request failed.
+    pub fn kind(&self) -> HttpErrorKind {
+        self.kind
+    }
+
+    /// Returns the name of the method, or the HTTP endpoint, the request was made for.
+    pub fn method_name(&self) -> Option<&str> {
+        self.context
+            .as_ref()
+            .map(|context| context.method_name.as_str())
+    }
+
+    /// Returns the address of the server the request was sent to.
+    pub fn server_addr(&self) -> Option<&str> {
+        self.context
+            .as_ref()
+            .map(|context| context.server_addr.as_str())
+    }
+
+    /// Returns true if the error happened while establishing a connection to the server.
+    pub fn is_connect(&self) -> bool {
+        matches!(
+            self.kind,
+            HttpErrorKind::Dns
+                | HttpErrorKind::Tls
+                | HttpErrorKind::ConnectionRefused
+                | HttpErrorKind::Connect
+        )
+    }
+
+    /// Returns true if the request timed out.
+    pub fn is_timeout(&self) -> bool {
+        matches!(
+            self.kind,
+            HttpErrorKind::RequestTimeout | HttpErrorKind::ReadTimeout
+        )
+    }
+
+    /// Returns a reference to the error of the underlying transport backend.
+    pub fn get_ref(&self) -> &(dyn error::Error + Send + Sync + 'static) {
+        &*self.source
+    }
+}
+
+/// The message of the errors `hyper-util`'s connector fails with when a host can't be resolved.
+///
+/// The connector wraps the `io::Error` of its resolver in a private error type, so there's no
+/// typed source to match on. This is a fallback, checked against the pinned `hyper-util` version
+/// by `unresolvable_hosts_are_dns_errors`.
+const DNS_ERROR: &str = "dns error";
+
+/// Classifies a connection error by walking its chain of sources.
+fn connect_error_kind(err: &(dyn error::Error + 'static)) -> HttpErrorKind {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if is_tls_error(err) {
+            return HttpErrorKind::Tls;
+        }
+        if err.to_string() == DNS_ERROR {
+            return HttpErrorKind::Dns;
+        }
+        source = match err.downcast_ref::<io::Error>() {
+            Some(err) if err.kind() == io::ErrorKind::ConnectionRefused => {
+                return HttpErrorKind::ConnectionRefused
+            }
+            // `io::Error` doesn't report the error it wraps as its source
+            Some(err) => err
+                .get_ref()
+                .map(|err| err as &(dyn error::Error + 'static)),
+            None => err.source(),
+        };
+    }
+    HttpErrorKind::Connect
+}
+
+#[allow(unused_variables)]
+fn is_tls_error(err: &(dyn error::Error + 'static)) -> bool {
+    #[cfg(any(feature = "native-tls", feature = "hyper-tls"))]
+    if err.is::<native_tls::Error>() {
+        return true;
+    }
+    #[cfg(feature = "rustls-tls")]
+    if err.is::<rustls::Error>() {
+        return true;
+    }
+    false
+}
+
+impl fmt::Debug for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.source, f)
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.source, f)?;
+        if let Some(context) = &self.context {
+            write!(
+                f,
+                " (method: {}, server: {})",
+                context.method_name, context.server_addr
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for HttpError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.source.source()
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for HttpError {
+    fn from(err: reqwest::Error) -> Self {
+        // the URL may hold the API key of the provider, the server address is kept redacted in
+        // the context of the error instead
+        let err = err.without_url();
+        if err.is_connect() {
+            Self::connect(err)
+        } else if err.is_timeout() {
+            Self::timeout(err)
+        } else {
+            Self::other(err)
+        }
+    }
+}
+
+/// Potential errors returned when validating the address of an RPC server.
+#[derive(Debug, Error)]
+pub enum InvalidServerAddrError {
+    /// The server address isn't a valid URL.
+    #[error("invalid server address: [{0}]")]
+    InvalidUrl(#[from] url::ParseError),
+    /// The server address uses a scheme other than `http` or `https`.
+    #[error("unsupported server address scheme: [{0}], expected http or https")]
+    UnsupportedScheme(String),
+    /// The server address doesn't specify a host.
+    #[error("the server address has no host")]
+    MissingHost,
+}
+
+/// Potential errors returned by the plain HTTP endpoints of a node.
+#[derive(Debug, Error)]
+pub enum HttpEndpointError {
+    /// The server address isn't a valid `http` or `https` URL.
+    #[error(transparent)]
+    InvalidServerAddr(InvalidServerAddrError),
+    /// Client is unable to send the request to the server.
+    #[error("error while sending request: [{0}]")]
+    SendError(HttpError),
+    /// Client is unable to read the response from the server.
+    #[error("error while reading response: [{0}]")]
+    RecvError(HttpError),
+    /// The response body exceeded the maximum size configured on the client.
+    #[error("the response exceeded the maximum size of {limit} bytes")]
+    ResponseTooLarge { limit: usize },
+    /// The server returned a non-200 status code.
+    #[error("the server returned a non-OK (200) status code: [{status}]")]
+    ResponseStatusError { status: http::StatusCode },
+    /// Client fails to parse the response of the server.
+    #[error("error while parsing response: [{0}]")]
+    InvalidResponse(Box<dyn error::Error + Send + Sync>),
+    /// The deadline set on the client expired before the request completed.
+    #[error("the request deadline was exceeded")]
+    DeadlineExceeded,
+}
+
+/// Potential errors returned by the typed views of the `query` method.
+#[derive(Debug, Error)]
+pub enum ViewError {
+    /// The query failed.
+    #[error(transparent)]
+    QueryError(Box<JsonRpcError<types::query::RpcQueryError>>),
+    /// The block referenced by a transaction couldn't be viewed.
+    #[error(transparent)]
+    BlockError(Box<JsonRpcError<types::blocks::RpcBlockError>>),
+    /// The server responded with a different kind of view than the one queried.
+    #[error("expected a {expected} response, the server returned {actual}")]
+    UnexpectedResponseKind {
+        expected: &'static str,
+        actual: &'static str,
+    },
+    /// Client fails to deserialize the result of a view function.
+    #[error("error while parsing view function result: [{0}]")]
+    ResultParseError(serde_json::Error),
+}
+
+impl From<JsonRpcError<types::query::RpcQueryError>> for ViewError {
+    fn from(err: JsonRpcError<types::query::RpcQueryError>) -> Self {
+        Self::QueryError(Box::new(err))
+    }
+}
+
+impl From<JsonRpcError<types::blocks::RpcBlockError>> for ViewError {
+    fn from(err: JsonRpcError<types::blocks::RpcBlockError>) -> Self {
+        Self::BlockError(Box::new(err))
+    }
+}
+
+impl ViewError {
+    /// Returns the handler error of the query, if the query failed with one.
+    pub fn handler_error(&self) -> Option<&types::query::RpcQueryError> {
+        match self {
+            Self::QueryError(err) => err.handler_error(),
+            _ => None,
+        }
+    }
+}
+
+/// An error raised by a [`TransactionSigner`](crate::signer::TransactionSigner).
+#[derive(Debug, Error)]
+#[error("error while signing the transaction: [{0}]")]
+pub struct SignerError(Box<dyn error::Error + Send + Sync>);
+
+impl SignerError {
+    pub fn new(err: impl Into<Box<dyn error::Error + Send + Sync>>) -> Self {
+        Self(err.into())
+    }
+
+    /// Get a reference to the error raised by the signer.
+    pub fn get_ref(&self) -> &(dyn error::Error + Send + Sync + 'static) {
+        &*self.0
+    }
+}
+
+/// Potential errors returned while building and sending a transaction.
+#[derive(Debug, Error)]
+pub enum SendTransactionError {
+    /// The access key of the signer couldn't be viewed, to get its nonce and a recent block hash.
+    #[error("error while viewing the access key of the signer: [{0}]")]
+    AccessKeyError(ViewError),
+    /// The congestion of the shard of the receiver couldn't be checked.
+    #[error("error while checking the congestion of the receiver: [{0}]")]
+    CongestionError(crate::congestion::CongestionError),
+    /// The shard of the receiver was too congested to send the transaction to.
+    #[error("the shard {shard_id} of the receiver is congested, at level {congestion_level}")]
+    Congested {
+        shard_id: ShardId,
+        congestion_level: f64,
+    },
+    /// The transaction couldn't be signed.
+    #[error(transparent)]
+    SignError(#[from] SignerError),
+    /// The transaction was sent, but failed.
+    #[error(transparent)]
+    SendError(Box<JsonRpcError<types::transactions::RpcTransactionError>>),
+}
+
+impl From<JsonRpcError<types::transactions::RpcTransactionError>> for SendTransactionError {
+    fn from(err: JsonRpcError<types::transactions::RpcTransactionError>) -> Self {
+        Self::SendError(Box::new(err))
+    }
+}
 
 /// Potential errors returned while sending a request to the RPC server.
 #[derive(Debug, Error)]
@@ -14,18 +356,99 @@ pub enum JsonRpcTransportSendError {
     PayloadSerializeError(io::Error),
     /// Client is unable to send the request to the server.
     #[error("error while sending payload: [{0}]")]
-    PayloadSendError(reqwest::Error),
+    PayloadSendError(HttpError),
+}
+
+/// The raw body of a response, kept for errors raised while parsing it.
+///
+/// Bodies longer than [`RawBody::LIMIT`] bytes are truncated.
+#[derive(Clone, Eq, PartialEq)]
+pub struct RawBody {
+    body: String,
+    truncated: bool,
+}
+
+impl RawBody {
+    /// The maximum number of bytes kept.
+    pub const LIMIT: usize = 4096;
+
+    pub(crate) fn new(body: &[u8]) -> Self {
+        let truncated = body.len() > Self::LIMIT;
+        let body = &body[..body.len().min(Self::LIMIT)];
+        Self {
+            body: String::from_utf8_lossy(body).into_owned(),
+            truncated,
+        }
+    }
+
+    /// The body as received, or its first [`RawBody::LIMIT`] bytes if truncated.
+    pub fn as_str(&self) -> &str {
+        &self.body
+    }
+
+    /// Returns true if the body was longer than [`RawBody::LIMIT`] bytes.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl fmt::Debug for RawBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for RawBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.body)?;
+        if self.truncated {
+            f.write_str("...")?;
+        }
+        Ok(())
+    }
 }
 
 /// Potential errors returned when the client has an issue parsing the response of a method call.
 #[derive(Debug, Error)]
 pub enum JsonRpcTransportHandlerResponseError {
     /// Client fails to deserialize the result of a method call.
-    #[error("error while parsing method call result: [{0}]")]
-    ResultParseError(serde_json::Error),
+    #[error("error while parsing method call result: [{error}]")]
+    ResultParseError {
+        error: serde_json::Error,
+        /// The raw response body returned by the server.
+        body: RawBody,
+    },
     /// Client fails to deserialize the error message returned from a method call.
     #[error("error while parsing method call error message: [{0}]")]
     ErrorMessageParseError(serde_json::Error),
+    /// Client fails to deserialize the handler error of a method call, in both its structured
+    /// and legacy forms.
+    #[error(
+        "error while parsing method call error: [{}] in {error}",
+        handler_error_parse_failures(.handler_error, .legacy_error)
+    )]
+    HandlerErrorParseError {
+        /// The failure to parse the structured error, the `cause` in `error_struct`.
+        handler_error: Option<serde_json::Error>,
+        /// The failure to parse the legacy error, the `data` of the error.
+        legacy_error: Option<serde_json::Error>,
+        /// The error as returned by the server.
+        error: Box<RpcError>,
+    },
+}
+
+fn handler_error_parse_failures(
+    handler_error: &Option<serde_json::Error>,
+    legacy_error: &Option<serde_json::Error>,
+) -> String {
+    match (handler_error, legacy_error) {
+        (Some(handler_error), Some(legacy_error)) => {
+            format!("handler error: {handler_error}; legacy error: {legacy_error}")
+        }
+        (Some(handler_error), None) => format!("handler error: {handler_error}"),
+        (None, Some(legacy_error)) => format!("legacy error: {legacy_error}"),
+        (None, None) => "unrecognized error".to_string(),
+    }
 }
 
 /// Potential errors returned while receiving responses from an RPC server.
@@ -36,13 +459,28 @@ pub enum JsonRpcTransportRecvError {
     UnexpectedServerResponse(Message),
     /// Client is unable to read the response from the RPC server.
     #[error("error while reading response: [{0}]")]
-    PayloadRecvError(reqwest::Error),
+    PayloadRecvError(HttpError),
+    /// The response body exceeded the maximum size configured on the client.
+    #[error("the response exceeded the maximum size of {limit} bytes")]
+    ResponseTooLarge { limit: usize },
     /// The base response structure is malformed e.g. meta properties like RPC version are missing.
-    #[error("error while parsing server response: [{0:?}]")]
-    PayloadParseError(message::Broken),
+    #[error("error while parsing server response: [{error:?}]")]
+    PayloadParseError {
+        error: message::Broken,
+        /// The raw response body returned by the server.
+        body: RawBody,
+    },
     /// Potential errors returned when the client has an issue parsing the response of a method call.
     #[error(transparent)]
     ResponseParseError(JsonRpcTransportHandlerResponseError),
+    /// The server returned a response that isn't JSON, like the HTML pages of gateways and CDNs
+    /// in front of the node, e.g. maintenance pages or bot challenges.
+    #[error("the server returned a non-JSON response with content type {content_type:?}")]
+    NonJsonResponse {
+        content_type: Option<String>,
+        /// The raw response body returned by the server.
+        body: RawBody,
+    },
 }
 
 /// Potential errors returned while sending requests to or receiving responses from the RPC server.
@@ -54,6 +492,35 @@ pub enum RpcTransportError {
     /// Potential errors returned while receiving a response from an RPC server.
     #[error(transparent)]
     RecvError(JsonRpcTransportRecvError),
+    /// The method call was cancelled by the client before it completed.
+    #[error("the request was cancelled")]
+    Cancelled,
+    /// The deadline set on the client expired before the method call completed.
+    #[error("the request deadline was exceeded")]
+    DeadlineExceeded,
+    /// The transaction wasn't sent, the server is on another network than the client expects.
+    ///
+    /// See [`JsonRpcClient::with_expected_chain_id`](crate::JsonRpcClient::with_expected_chain_id).
+    #[error("refused to send a transaction for {expected} to a server on {actual}")]
+    WrongNetwork { expected: String, actual: String },
+    /// The transaction wasn't sent, the chain id of the server couldn't be verified.
+    #[error("failed to verify the chain id of the server: {0}")]
+    ChainIdUnverified(Box<NearRpcError>),
+}
+
+/// The rate-limit headers of a response.
+///
+/// Each is `None` if the server didn't send it, or sent an invalid value.
+#[derive(Eq, Copy, Clone, Debug, Default, PartialEq)]
+pub struct RateLimit {
+    /// How long to wait before sending another request, from the `Retry-After` header.
+    pub retry_after: Option<Duration>,
+    /// The number of requests allowed in the current window, from `X-RateLimit-Limit`.
+    pub limit: Option<u64>,
+    /// The number of requests left in the current window, from `X-RateLimit-Remaining`.
+    pub remaining: Option<u64>,
+    /// How long until the current window resets, from `X-RateLimit-Reset`.
+    pub reset: Option<Duration>,
 }
 
 /// Unexpected status codes returned by the RPC server.
@@ -63,17 +530,37 @@ pub enum JsonRpcServerResponseStatusError {
     #[error("this client is unauthorized")]
     Unauthorized,
     /// The RPC client exceeds the rate limit by sending too many requests.
-    #[error("this client has exceeded the rate limit")]
-    TooManyRequests,
+    #[error("this client has exceeded the rate limit of {server_addr}")]
+    TooManyRequests {
+        rate_limit: RateLimit,
+        /// The address of the server that rejected the call.
+        server_addr: String,
+    },
     #[error("the server returned status code 400 - bad request")]
     BadRequest,
     #[error("the request failed with timeout error")]
     TimeoutError,
     #[error("the server is unavailable")]
     ServiceUnavailable,
+    /// A gateway in front of the RPC server failed to get a response from it.
+    #[error("the server returned status code 502 - bad gateway")]
+    BadGateway {
+        /// The body of the response, often naming the failing gateway.
+        body: RawBody,
+    },
+    /// A gateway in front of the RPC server timed out waiting for a response from it.
+    #[error("the server returned status code 504 - gateway timeout")]
+    GatewayTimeout {
+        /// The body of the response, often naming the failing gateway.
+        body: RawBody,
+    },
     /// The RPC server returned a non-200 status code.
     #[error("the server returned a non-OK (200) status code: [{status}]")]
-    Unexpected { status: reqwest::StatusCode },
+    Unexpected {
+        status: http::StatusCode,
+        /// The body of the response, often explaining why the call was rejected.
+        body: RawBody,
+    },
 }
 
 /// Potential errors returned by the RPC server.
@@ -87,13 +574,25 @@ pub enum JsonRpcServerError<E> {
     HandlerError(E),
     /// The RPC server returned an internal server error.
     #[error("internal error: [{info:?}]")]
-    InternalError { info: Option<String> },
+    InternalError {
+        info: Option<String>,
+        /// The error as returned by the server, absent if the server only failed with a status code.
+        error: Option<Box<RpcError>>,
+    },
     /// The RPC server returned a response without context i.e. a response the client doesn't expect.
     #[error("error response lacks context: {0}")]
     NonContextualError(RpcError),
     /// Unexpected status codes returned by the RPC server.
     #[error(transparent)]
     ResponseStatusError(JsonRpcServerResponseStatusError),
+    /// The block the method was called for was garbage collected by the node, which isn't archival.
+    ///
+    /// The node reported the block as unknown, with `error` as its handler error.
+    #[error("block #{block_height} was garbage collected by the node, use an archival node to fetch historical data")]
+    BlockGarbageCollected {
+        block_height: near_primitives::types::BlockHeight,
+        error: E,
+    },
 }
 
 /// Potential errors returned by the RPC client.
@@ -107,18 +606,460 @@ pub enum JsonRpcError<E> {
     ServerError(JsonRpcServerError<E>),
 }
 
+impl<E> JsonRpcServerError<E> {
+    /// The error as returned by the server, for errors that weren't classified any further.
+    ///
+    /// This is only available for [`InternalError`](Self::InternalError)s reported by the server
+    /// and for [`NonContextualError`](Self::NonContextualError)s.
+    pub fn rpc_error(&self) -> Option<&RpcError> {
+        match self {
+            Self::InternalError { error, .. } => error.as_deref(),
+            Self::NonContextualError(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// The JSON-RPC error code returned by the server, see [`rpc_error`](Self::rpc_error).
+    pub fn code(&self) -> Option<i64> {
+        self.rpc_error().map(|err| err.code)
+    }
+
+    /// The JSON-RPC error message returned by the server, see [`rpc_error`](Self::rpc_error).
+    pub fn message(&self) -> Option<&str> {
+        self.rpc_error().map(|err| err.message.as_str())
+    }
+
+    /// The JSON-RPC error data returned by the server, see [`rpc_error`](Self::rpc_error).
+    pub fn data(&self) -> Option<&serde_json::Value> {
+        self.rpc_error().and_then(|err| err.data.as_ref())
+    }
+}
+
 impl<E> JsonRpcError<E> {
     pub fn handler_error(&self) -> Option<&E> {
-        if let Self::ServerError(JsonRpcServerError::HandlerError(err)) = self {
-            return Some(err);
+        match self {
+            Self::ServerError(JsonRpcServerError::HandlerError(err))
+            | Self::ServerError(JsonRpcServerError::BlockGarbageCollected { error: err, .. }) => {
+                Some(err)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if the server doesn't support the called method.
+    pub fn is_method_not_found(&self) -> bool {
+        match self {
+            Self::ServerError(JsonRpcServerError::RequestValidationError(
+                RpcRequestValidationErrorKind::MethodNotFound { .. },
+            )) => true,
+            // older nodes only report the JSON-RPC error code
+            err => err.rpc_error().map_or(false, |err| err.code == -32601),
+        }
+    }
+
+    /// The error as returned by the server, see [`JsonRpcServerError::rpc_error`].
+    pub fn rpc_error(&self) -> Option<&RpcError> {
+        match self {
+            Self::ServerError(err) => err.rpc_error(),
+            Self::TransportError(_) => None,
+        }
+    }
+}
+
+impl<E> JsonRpcError<E> {
+    /// Returns true if the call failed for a transient reason, and may succeed if sent again.
+    ///
+    /// This covers timeouts, dropped connections, rate limiting (`429`) and servers that are
+    /// unavailable (`503`) or behind a failing gateway (`502`, `504`). Handler errors, invalid
+    /// requests and exceeded deadlines are permanent.
+    ///
+    /// Unlike the client's own [`RetryPolicy`](crate::retry::RetryPolicy), this doesn't account
+    /// for whether the method is safe to resend.
+    pub fn is_retryable(&self) -> bool {
+        crate::retry::is_retryable(self, true)
+    }
+
+    /// Returns true if the call failed for a reason that won't go away by sending it again.
+    pub fn is_permanent(&self) -> bool {
+        !self.is_retryable()
+    }
+
+    /// Returns the error raised by the HTTP transport, if the call failed because of one.
+    pub fn http_error(&self) -> Option<&HttpError> {
+        match self {
+            Self::TransportError(RpcTransportError::SendError(
+                JsonRpcTransportSendError::PayloadSendError(err),
+            ))
+            | Self::TransportError(RpcTransportError::RecvError(
+                JsonRpcTransportRecvError::PayloadRecvError(err),
+            )) => Some(err),
+            _ => None,
         }
+    }
+
+    /// Returns true if the call timed out, or exceeded the deadline set on the client.
+    ///
+    /// This includes gateways in front of the server timing out (`504`).
+    pub fn is_timeout(&self) -> bool {
+        matches!(
+            self,
+            Self::TransportError(RpcTransportError::DeadlineExceeded)
+                | Self::ServerError(JsonRpcServerError::ResponseStatusError(
+                    JsonRpcServerResponseStatusError::GatewayTimeout { .. }
+                ))
+        ) || self.http_error().map_or(false, HttpError::is_timeout)
+    }
+
+    /// Returns true if the call failed while establishing a connection to the server.
+    pub fn is_connect(&self) -> bool {
+        self.http_error().map_or(false, HttpError::is_connect)
+    }
+}
+
+impl JsonRpcError<near_jsonrpc_primitives::types::transactions::RpcTransactionError> {
+    /// Returns true if a transaction status call should be sent again while polling for the
+    /// outcome of a transaction.
+    ///
+    /// Besides [transient failures](JsonRpcError::is_retryable), this covers transactions not yet
+    /// known to the node (`UNKNOWN_TRANSACTION`) and the node timing out while waiting for them.
+    pub fn is_retryable_while_polling(&self) -> bool {
+        use near_jsonrpc_primitives::types::transactions::RpcTransactionError;
+
+        self.is_retryable()
+            || matches!(
+                self.handler_error(),
+                Some(
+                    RpcTransactionError::UnknownTransaction { .. }
+                        | RpcTransactionError::TimeoutError
+                )
+            )
+    }
+
+    /// The reason the node rejected the transaction as invalid, if it did.
+    pub fn invalid_tx_error(&self) -> Option<&near_primitives::errors::InvalidTxError> {
+        match self.handler_error()? {
+            types::transactions::RpcTransactionError::InvalidTransaction { context } => {
+                Some(context)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if the transaction was rejected for a nonce already used by its access key.
+    pub fn is_invalid_nonce(&self) -> bool {
+        self.expected_nonce().is_some()
+    }
+
+    /// Returns true if the transaction was rejected for referencing a block hash that's too old.
+    ///
+    /// Such transactions must be signed again, referencing a recent block hash.
+    pub fn is_expired(&self) -> bool {
+        matches!(
+            self.invalid_tx_error(),
+            Some(near_primitives::errors::InvalidTxError::Expired)
+        )
+    }
+
+    /// The lowest nonce the access key accepts, if the transaction was rejected for a nonce
+    /// already used by it.
+    ///
+    /// Transactions signed with this nonce, or any higher one, pass the nonce check. Returns
+    /// `None` as well if the access key already used the highest nonce.
+    pub fn expected_nonce(&self) -> Option<near_primitives::types::Nonce> {
+        match self.invalid_tx_error()? {
+            near_primitives::errors::InvalidTxError::InvalidNonce { ak_nonce, .. } => {
+                ak_nonce.checked_add(1)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<E> JsonRpcError<E> {
+    /// Converts the handler error with `f`.
+    pub fn map_handler_error<F>(self, f: impl FnOnce(E) -> F) -> JsonRpcError<F> {
+        self.try_map_handler_error(|err| Ok(f(err)))
+    }
+
+    /// Converts the handler error with `parse`, reporting parse failures as transport errors.
+    pub(crate) fn try_map_handler_error<F>(
+        self,
+        parse: impl FnOnce(E) -> Result<F, serde_json::Error>,
+    ) -> JsonRpcError<F> {
+        let err = match self {
+            Self::TransportError(err) => return JsonRpcError::TransportError(err),
+            Self::ServerError(err) => err,
+        };
+        JsonRpcError::ServerError(match err {
+            JsonRpcServerError::HandlerError(err) => match parse(err) {
+                Ok(err) => JsonRpcServerError::HandlerError(err),
+                Err(err) => {
+                    return JsonRpcError::TransportError(RpcTransportError::RecvError(
+                        JsonRpcTransportRecvError::ResponseParseError(
+                            JsonRpcTransportHandlerResponseError::ErrorMessageParseError(err),
+                        ),
+                    ))
+                }
+            },
+            JsonRpcServerError::RequestValidationError(err) => {
+                JsonRpcServerError::RequestValidationError(err)
+            }
+            JsonRpcServerError::InternalError { info, error } => {
+                JsonRpcServerError::InternalError { info, error }
+            }
+            JsonRpcServerError::NonContextualError(err) => {
+                JsonRpcServerError::NonContextualError(err)
+            }
+            JsonRpcServerError::ResponseStatusError(err) => {
+                JsonRpcServerError::ResponseStatusError(err)
+            }
+            JsonRpcServerError::BlockGarbageCollected {
+                block_height,
+                error,
+            } => match parse(error) {
+                Ok(error) => JsonRpcServerError::BlockGarbageCollected {
+                    block_height,
+                    error,
+                },
+                Err(err) => {
+                    return JsonRpcError::TransportError(RpcTransportError::RecvError(
+                        JsonRpcTransportRecvError::ResponseParseError(
+                            JsonRpcTransportHandlerResponseError::ErrorMessageParseError(err),
+                        ),
+                    ))
+                }
+            },
+        })
+    }
+}
+
+/// A handler error of any method, with its type erased.
+///
+/// The original error can be recovered with [`downcast_ref`](AnyHandlerError::downcast_ref) or
+/// [`downcast`](AnyHandlerError::downcast).
+pub struct AnyHandlerError {
+    error: Box<dyn error::Error + Send + Sync>,
+    name: String,
+    is_unknown_block: bool,
+    block_context: Option<BlockContext>,
+}
+
+/// An error returned by any method, see [`AnyHandlerError`].
+///
+/// Errors of different methods convert into it with `?` or [`From`], so they can be stored
+/// together, e.g. sent over the same channel.
+///
+/// ## Example
+///
+/// ```no_run
+/// use near_jsonrpc_client::errors::NearRpcError;
+/// use near_jsonrpc_client::methods;
+/// use near_jsonrpc_primitives::types::blocks::RpcBlockError;
+/// use near_primitives::types::{BlockReference, Finality};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), NearRpcError> {
+/// let client = near_jsonrpc_client::JsonRpcClient::connect("https://rpc.testnet.near.org");
+///
+/// let status = client.call(methods::status::RpcStatusRequest).await?;
+/// println!("chain: {}", status.chain_id);
+///
+/// let block = client
+///     .call(methods::block::RpcBlockRequest {
+///         block_reference: BlockReference::Finality(Finality::Final),
+///     })
+///     .await
+///     .map_err(NearRpcError::from);
+///
+/// if let Err(err) = block {
+///     if let Some(RpcBlockError::UnknownBlock { .. }) =
+///         err.handler_error().and_then(|err| err.downcast_ref())
+///     {
+///         println!("block not found");
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub type NearRpcError = JsonRpcError<AnyHandlerError>;
+
+impl AnyHandlerError {
+    pub fn new<E: NearHandlerError>(err: E) -> Self {
+        Self {
+            name: err.name(),
+            is_unknown_block: crate::methods::RpcHandlerError::is_unknown_block(&err),
+            block_context: err.block_context(),
+            error: Box::new(err),
+        }
+    }
+
+    /// The name of the error, see [`NearHandlerError::name`].
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// See [`RpcHandlerError::is_unknown_block`](crate::methods::RpcHandlerError::is_unknown_block).
+    pub fn is_unknown_block(&self) -> bool {
+        self.is_unknown_block
+    }
+
+    /// See [`NearHandlerError::block_context`].
+    pub fn block_context(&self) -> Option<BlockContext> {
+        self.block_context
+    }
+
+    /// Returns true if the handler error is of type `E`.
+    pub fn is<E: error::Error + 'static>(&self) -> bool {
+        self.error.is::<E>()
+    }
+
+    /// Returns a reference to the handler error, if it's of type `E`.
+    pub fn downcast_ref<E: error::Error + 'static>(&self) -> Option<&E> {
+        self.error.downcast_ref()
+    }
+
+    /// Recovers the handler error, if it's of type `E`.
+    pub fn downcast<E: error::Error + 'static>(self) -> Result<E, Self> {
+        match self.error.downcast() {
+            Ok(err) => Ok(*err),
+            Err(error) => Err(Self { error, ..self }),
+        }
+    }
+
+    /// Returns a reference to the handler error.
+    pub fn get_ref(&self) -> &(dyn error::Error + Send + Sync + 'static) {
+        &*self.error
+    }
+}
+
+impl fmt::Debug for AnyHandlerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.error, f)
+    }
+}
+
+impl fmt::Display for AnyHandlerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl<E: NearHandlerError> From<JsonRpcError<E>> for NearRpcError {
+    fn from(err: JsonRpcError<E>) -> Self {
+        err.map_handler_error(AnyHandlerError::new)
+    }
+}
+
+/// The block a handler error was raised for.
+#[derive(Eq, Copy, Clone, Debug, PartialEq)]
+pub struct BlockContext {
+    pub height: BlockHeight,
+    pub hash: CryptoHash,
+}
+
+/// Accessors common to the handler errors of all methods.
+///
+/// This lets generic code, like retry or reporting layers, inspect handler errors without knowing
+/// which method they came from. Handler errors implementing it convert into [`NearRpcError`].
+///
+/// Whether the block the method was called for is unknown to the node is reported by
+/// [`RpcHandlerError::is_unknown_block`](crate::methods::RpcHandlerError::is_unknown_block).
+pub trait NearHandlerError:
+    crate::methods::RpcHandlerError + error::Error + Send + Sync + 'static
+{
+    /// The name of the error, as reported by the server, e.g. `UNKNOWN_BLOCK`.
+    fn name(&self) -> String;
+
+    /// The block the error was raised for, if the server reported it.
+    fn block_context(&self) -> Option<BlockContext> {
         None
     }
 }
 
+/// Reads the name of a handler error from its serialized form, tagged by the server's `name`.
+fn serialized_name<E: serde::Serialize>(err: &E) -> String {
+    serde_json::to_value(err)
+        .ok()
+        .and_then(|value| value["name"].as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+macro_rules! impl_near_handler_error {
+    (@uninhabited $($error:ty),+ $(,)?) => {
+        $(
+            impl NearHandlerError for $error {
+                fn name(&self) -> String {
+                    match *self {}
+                }
+            }
+        )+
+    };
+    ($($error:ty $({ $($body:tt)* })?),+ $(,)?) => {
+        $(
+            impl NearHandlerError for $error {
+                fn name(&self) -> String {
+                    serialized_name(self)
+                }
+
+                $($($body)*)?
+            }
+        )+
+    };
+}
+
+impl_near_handler_error! {
+    types::blocks::RpcBlockError,
+    types::chunks::RpcChunkError,
+    types::changes::RpcStateChangesError,
+    types::config::RpcProtocolConfigError,
+    types::gas_price::RpcGasPriceError,
+    types::light_client::RpcLightClientProofError,
+    types::light_client::RpcLightClientNextBlockError,
+    types::query::RpcQueryError {
+        fn block_context(&self) -> Option<BlockContext> {
+            use types::query::RpcQueryError::*;
+
+            match self {
+                GarbageCollectedBlock { block_height, block_hash }
+                | InvalidAccount { block_height, block_hash, .. }
+                | UnknownAccount { block_height, block_hash, .. }
+                | NoContractCode { block_height, block_hash, .. }
+                | TooLargeContractState { block_height, block_hash, .. }
+                | UnknownAccessKey { block_height, block_hash, .. }
+                | ContractExecutionError { block_height, block_hash, .. } => Some(BlockContext {
+                    height: *block_height,
+                    hash: *block_hash,
+                }),
+                NoSyncedBlocks | UnavailableShard { .. } | UnknownBlock { .. } | InternalError { .. } => None,
+            }
+        }
+    },
+    types::client_config::RpcClientConfigError,
+    types::maintenance::RpcMaintenanceWindowsError,
+    types::network_info::RpcNetworkInfoError,
+    types::receipts::RpcReceiptError,
+    types::split_storage::RpcSplitStorageInfoError,
+    types::status::RpcStatusError,
+    types::transactions::RpcTransactionError,
+    types::validator::RpcValidatorError,
+}
+
+// these methods never fail with a handler error, so their errors have no variants to name
+impl_near_handler_error! { @uninhabited
+    crate::methods::broadcast_tx_async::RpcBroadcastTxAsyncError,
+    crate::methods::EXPERIMENTAL_genesis_config::RpcGenesisConfigError,
+}
+
+#[cfg(feature = "sandbox")]
+impl_near_handler_error! {
+    types::sandbox::RpcSandboxFastForwardError,
+    types::sandbox::RpcSandboxPatchStateError,
+}
+
 impl<E: super::methods::RpcHandlerError> From<RpcError> for JsonRpcError<E> {
     fn from(err: RpcError) -> Self {
         let mut handler_parse_error = None;
+        let mut legacy_parse_error = None;
         match err.error_struct {
             Some(RpcErrorKind::HandlerError(ref handler_error)) => {
                 match E::parse(handler_error.clone()) {
@@ -135,12 +1076,13 @@ impl<E: super::methods::RpcHandlerError> From<RpcError> for JsonRpcError<E> {
             Some(RpcErrorKind::RequestValidationError(err)) => {
                 return JsonRpcError::ServerError(JsonRpcServerError::RequestValidationError(err));
             }
-            Some(RpcErrorKind::InternalError(err)) => {
+            Some(RpcErrorKind::InternalError(ref internal_error)) => {
                 return JsonRpcError::ServerError(JsonRpcServerError::InternalError {
-                    info: err["info"]["error_message"]
+                    info: internal_error["info"]["error_message"]
                         .as_str()
                         .map(|info| info.to_string()),
-                })
+                    error: Some(Box::new(err)),
+                });
             }
             None => {}
         }
@@ -152,18 +1094,354 @@ impl<E: super::methods::RpcHandlerError> From<RpcError> for JsonRpcError<E> {
                     ))
                 }
                 Some(Err(err)) => {
-                    handler_parse_error.replace(err);
+                    legacy_parse_error.replace(err);
                 }
                 None => {}
             }
         }
-        if let Some(err) = handler_parse_error {
+        if handler_parse_error.is_some() || legacy_parse_error.is_some() {
             return JsonRpcError::TransportError(RpcTransportError::RecvError(
                 JsonRpcTransportRecvError::ResponseParseError(
-                    JsonRpcTransportHandlerResponseError::ErrorMessageParseError(err),
+                    JsonRpcTransportHandlerResponseError::HandlerErrorParseError {
+                        handler_error: handler_parse_error,
+                        legacy_error: legacy_parse_error,
+                        error: Box::new(err),
+                    },
                 ),
             ));
         }
         JsonRpcError::ServerError(JsonRpcServerError::NonContextualError(err))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unclassified_errors_keep_the_rpc_error() {
+        use near_jsonrpc_primitives::types::blocks::RpcBlockError;
+
+        let err = JsonRpcError::<RpcBlockError>::from(RpcError {
+            error_struct: Some(RpcErrorKind::InternalError(serde_json::json!({
+                "name": "INTERNAL_ERROR",
+                "info": { "error_message": "storage error" }
+            }))),
+            code: -32000,
+            message: "Server error".to_string(),
+            data: Some(serde_json::json!("storage error")),
+        });
+        let JsonRpcError::ServerError(err) = err else {
+            panic!("expected a server error");
+        };
+        assert!(matches!(
+            err,
+            JsonRpcServerError::InternalError { info: Some(ref info), .. } if info == "storage error"
+        ));
+        assert_eq!(err.code(), Some(-32000));
+        assert_eq!(err.message(), Some("Server error"));
+        assert_eq!(err.data(), Some(&serde_json::json!("storage error")));
+
+        let err = JsonRpcError::<RpcBlockError>::from(RpcError::new(
+            -32601,
+            "Method not found".to_string(),
+            None,
+        ));
+        assert_eq!(err.rpc_error().map(|err| err.code), Some(-32601));
+        assert!(err.is_method_not_found());
+        assert!(matches!(
+            err,
+            JsonRpcError::ServerError(JsonRpcServerError::NonContextualError(_))
+        ));
+    }
+
+    #[test]
+    fn unparsable_handler_errors_keep_both_failures() {
+        use near_jsonrpc_primitives::types::transactions::RpcTransactionError;
+
+        let err = JsonRpcError::<RpcTransactionError>::from(RpcError {
+            error_struct: Some(RpcErrorKind::HandlerError(serde_json::json!({
+                "name": "NEW_ERROR",
+                "cause": { "name": "NEW_ERROR", "info": {} }
+            }))),
+            code: -32000,
+            message: "Server error".to_string(),
+            data: Some(serde_json::json!("a new error")),
+        });
+        let JsonRpcError::TransportError(RpcTransportError::RecvError(
+            JsonRpcTransportRecvError::ResponseParseError(
+                JsonRpcTransportHandlerResponseError::HandlerErrorParseError {
+                    handler_error: Some(_),
+                    legacy_error: Some(_),
+                    ref error,
+                },
+            ),
+        )) = err
+        else {
+            panic!("expected a handler error parse error, got {:?}", err);
+        };
+        assert_eq!(error.data, Some(serde_json::json!("a new error")));
+        let message = err.to_string();
+        assert!(message.contains("handler error: "));
+        assert!(message.contains("legacy error: "));
+        assert!(message.contains("NEW_ERROR"));
+    }
+
+    #[test]
+    fn transient_errors_are_retryable() {
+        use near_jsonrpc_primitives::types::transactions::RpcTransactionError;
+
+        let err = JsonRpcError::<RpcTransactionError>::ServerError(
+            JsonRpcServerError::ResponseStatusError(
+                JsonRpcServerResponseStatusError::TooManyRequests {
+                    rate_limit: RateLimit::default(),
+                    server_addr: "https://rpc.testnet.near.org".to_string(),
+                },
+            ),
+        );
+        assert!(err.is_retryable());
+
+        let err =
+            JsonRpcError::<RpcTransactionError>::TransportError(RpcTransportError::RecvError(
+                JsonRpcTransportRecvError::PayloadRecvError(HttpError::other("connection reset")),
+            ));
+        assert!(err.is_retryable());
+
+        let err = JsonRpcError::<RpcTransactionError>::ServerError(
+            JsonRpcServerError::ResponseStatusError(
+                JsonRpcServerResponseStatusError::GatewayTimeout {
+                    body: RawBody::new(b"<html>504 Gateway Time-out</html>"),
+                },
+            ),
+        );
+        assert!(err.is_retryable());
+        assert!(err.is_timeout());
+        assert!(!crate::retry::is_retryable(&err, false));
+
+        let err = JsonRpcError::<RpcTransactionError>::ServerError(
+            JsonRpcServerError::ResponseStatusError(JsonRpcServerResponseStatusError::BadGateway {
+                body: RawBody::new(b"<html>502 Bad Gateway</html>"),
+            }),
+        );
+        assert!(err.is_retryable());
+        assert!(!err.is_timeout());
+        assert_eq!(
+            err.to_string(),
+            "the server returned status code 502 - bad gateway"
+        );
+
+        let err = JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+            RpcTransactionError::UnknownTransaction {
+                requested_transaction_hash: Default::default(),
+            },
+        ));
+        assert!(err.is_permanent());
+        assert!(err.is_retryable_while_polling());
+
+        let err = JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+            RpcTransactionError::DoesNotTrackShard,
+        ));
+        assert!(!err.is_retryable_while_polling());
+    }
+
+    #[test]
+    fn invalid_nonces_are_detected() {
+        use near_jsonrpc_primitives::types::transactions::RpcTransactionError;
+        use near_primitives::errors::InvalidTxError;
+
+        let err = JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+            RpcTransactionError::InvalidTransaction {
+                context: InvalidTxError::InvalidNonce {
+                    tx_nonce: 5,
+                    ak_nonce: 7,
+                },
+            },
+        ));
+        assert!(err.is_invalid_nonce());
+        assert_eq!(err.expected_nonce(), Some(8));
+
+        let err = JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+            RpcTransactionError::InvalidTransaction {
+                context: InvalidTxError::InvalidNonce {
+                    tx_nonce: 5,
+                    ak_nonce: u64::MAX,
+                },
+            },
+        ));
+        assert_eq!(err.expected_nonce(), None);
+
+        let err = JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+            RpcTransactionError::InvalidTransaction {
+                context: InvalidTxError::NonceTooLarge {
+                    tx_nonce: 5,
+                    upper_bound: 4,
+                },
+            },
+        ));
+        assert!(err.invalid_tx_error().is_some());
+        assert!(!err.is_invalid_nonce());
+        assert_eq!(err.expected_nonce(), None);
+    }
+
+    #[test]
+    fn expired_transactions_are_detected() {
+        use near_jsonrpc_primitives::types::transactions::RpcTransactionError;
+        use near_primitives::errors::InvalidTxError;
+
+        let err = JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+            RpcTransactionError::InvalidTransaction {
+                context: InvalidTxError::Expired,
+            },
+        ));
+        assert!(err.is_expired());
+        assert!(!err.is_invalid_nonce());
+
+        let err = JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+            RpcTransactionError::InvalidTransaction {
+                context: InvalidTxError::InvalidChain,
+            },
+        ));
+        assert!(!err.is_expired());
+    }
+
+    #[test]
+    fn handler_errors_can_be_erased() {
+        use near_jsonrpc_primitives::types::blocks::RpcBlockError;
+        use near_jsonrpc_primitives::types::transactions::RpcTransactionError;
+
+        let errors: Vec<NearRpcError> = vec![
+            JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+                RpcBlockError::NotSyncedYet,
+            ))
+            .into(),
+            JsonRpcError::<RpcTransactionError>::TransportError(RpcTransportError::Cancelled)
+                .into(),
+        ];
+
+        let err = errors[0].handler_error().unwrap();
+        assert_eq!(err.name(), "NOT_SYNCED_YET");
+        assert!(!err.is_unknown_block());
+        assert!(err.is::<RpcBlockError>());
+        assert!(matches!(
+            err.downcast_ref::<RpcBlockError>(),
+            Some(RpcBlockError::NotSyncedYet)
+        ));
+        assert!(errors[1].handler_error().is_none());
+    }
+
+    #[test]
+    fn garbage_collected_blocks_keep_the_handler_error() {
+        use near_jsonrpc_primitives::types::query::RpcQueryError;
+
+        let err = JsonRpcError::ServerError(JsonRpcServerError::BlockGarbageCollected {
+            block_height: 1,
+            error: RpcQueryError::UnknownBlock {
+                block_reference: near_primitives::types::BlockReference::BlockId(
+                    near_primitives::types::BlockId::Height(1),
+                ),
+            },
+        });
+        assert!(err.to_string().contains("use an archival node"));
+        assert!(matches!(
+            err.handler_error(),
+            Some(RpcQueryError::UnknownBlock { .. })
+        ));
+
+        let err = NearRpcError::from(err);
+        assert!(err.handler_error().unwrap().is_unknown_block());
+    }
+
+    #[test]
+    fn handler_errors_share_accessors() {
+        use crate::methods::RpcHandlerError;
+        use near_jsonrpc_primitives::types::query::RpcQueryError;
+
+        let err = RpcQueryError::UnknownAccount {
+            requested_account_id: "alice.near".parse().unwrap(),
+            block_height: 1,
+            block_hash: CryptoHash::default(),
+        };
+        assert_eq!(err.name(), "UNKNOWN_ACCOUNT");
+        assert_eq!(
+            err.block_context(),
+            Some(BlockContext {
+                height: 1,
+                hash: CryptoHash::default(),
+            })
+        );
+
+        let err = RpcQueryError::UnknownBlock {
+            block_reference: near_primitives::types::BlockReference::latest(),
+        };
+        assert!(err.is_unknown_block());
+        assert_eq!(err.block_context(), None);
+    }
+
+    #[test]
+    fn connect_errors_are_classified() {
+        let refused = io::Error::from(io::ErrorKind::ConnectionRefused);
+        assert_eq!(
+            HttpError::connect(refused).kind(),
+            HttpErrorKind::ConnectionRefused
+        );
+
+        let err = HttpError::connect(io::Error::new(io::ErrorKind::Other, "unreachable"));
+        assert_eq!(err.kind(), HttpErrorKind::Connect);
+        assert!(err.is_connect());
+
+        let err = HttpError::timeout("elapsed")
+            .while_reading()
+            .with_context("status", "https://rpc.testnet.near.org");
+        assert_eq!(err.kind(), HttpErrorKind::ReadTimeout);
+        assert_eq!(
+            err.to_string(),
+            "elapsed (method: status, server: https://rpc.testnet.near.org)"
+        );
+
+        let err = JsonRpcError::<()>::TransportError(RpcTransportError::RecvError(
+            JsonRpcTransportRecvError::PayloadRecvError(err),
+        ));
+        assert!(err.is_timeout());
+        assert!(!err.is_connect());
+    }
+
+    #[test]
+    fn raw_bodies_are_capped() {
+        let body = RawBody::new(br#"{"jsonrpc":"2.0"}"#);
+        assert_eq!(body.as_str(), r#"{"jsonrpc":"2.0"}"#);
+        assert!(!body.is_truncated());
+
+        let body = RawBody::new(&[b'a'; RawBody::LIMIT + 1]);
+        assert_eq!(body.as_str().len(), RawBody::LIMIT);
+        assert!(body.is_truncated());
+        assert!(body.to_string().ends_with("a..."));
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[tokio::test]
+    async fn transport_errors_leave_out_the_url() {
+        let err = reqwest::Client::new()
+            .post("http://127.0.0.1:1/s3cr3t/")
+            .send()
+            .await
+            .unwrap_err();
+        let err = HttpError::from(err).with_context("status", "http://127.0.0.1:1/[redacted]/");
+
+        assert!(err.is_connect());
+        assert!(!format!("{:?}", err).contains("s3cr3t"));
+        assert!(!err.to_string().contains("s3cr3t"));
+    }
+
+    #[cfg(any(feature = "reqwest", feature = "hyper-transport"))]
+    #[tokio::test]
+    async fn unresolvable_hosts_are_dns_errors() {
+        let err = crate::JsonRpcClient::connect("http://rpc.near.invalid")
+            .call(crate::methods::status::RpcStatusRequest)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.http_error().map(HttpError::kind),
+            Some(HttpErrorKind::Dns)
+        );
+    }
+}
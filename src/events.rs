@@ -0,0 +1,258 @@
+//! Events emitted by contracts, as specified by [NEP-297](https://github.com/near/NEPs/blob/master/neps/nep-0297.md).
+//!
+//! Contracts emit events as logs prefixed with `EVENT_JSON:`, holding the standard and version
+//! the event follows, its name, and its data. [`events`] extracts the events emitted by the
+//! receipts of a transaction from its [`FinalExecutionOutcomeView`].
+//!
+//! The events of fungible tokens ([NEP-141](https://github.com/near/NEPs/blob/master/neps/nep-0141.md))
+//! and non-fungible tokens ([NEP-171](https://github.com/near/NEPs/blob/master/neps/nep-0171.md))
+//! are parsed into [`FtEvent`]s and [`NftEvent`]s with [`Event::ft`] and [`Event::nft`].
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::events::{self, FtEvent};
+//! use near_jsonrpc_client::{methods, JsonRpcClient};
+//! use near_primitives::views::TxExecutionStatus;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! let client = JsonRpcClient::connect("https://archival-rpc.mainnet.near.org");
+//!
+//! let response = client
+//!     .call(methods::tx::RpcTransactionStatusRequest {
+//!         transaction_info: methods::tx::TransactionInfo::TransactionId {
+//!             tx_hash: "9FtHUFBQsZ2MG77K3x3MJ9wjX3UT8zE1TczCrhZEcG8U".parse()?,
+//!             sender_account_id: "miraclx.near".parse()?,
+//!         },
+//!         wait_until: TxExecutionStatus::Executed,
+//!     })
+//!     .await?;
+//!
+//! if let Some(outcome) = response.final_execution_outcome {
+//!     for emitted in events::events(&outcome.into_outcome()) {
+//!         if let Some(Ok(FtEvent::Transfer(transfers))) = emitted.event.ft() {
+//!             for transfer in transfers {
+//!                 println!(
+//!                     "{} {} -> {}",
+//!                     transfer.amount, transfer.old_owner_id, transfer.new_owner_id
+//!                 );
+//!             }
+//!         }
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use near_primitives::hash::CryptoHash;
+use near_primitives::serialize::dec_format;
+use near_primitives::types::{AccountId, Balance};
+use near_primitives::views::FinalExecutionOutcomeView;
+use serde::{Deserialize, Serialize};
+
+use crate::logs::OutcomeLogs;
+
+/// The prefix of the logs holding events.
+pub const EVENT_LOG_PREFIX: &str = "EVENT_JSON:";
+
+/// An event emitted by a contract.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Event {
+    /// The standard the event follows, like `nep141`.
+    pub standard: String,
+    /// The version of the standard.
+    pub version: String,
+    /// The name of the event, like `ft_transfer`.
+    pub event: String,
+    /// The data of the event, as defined by the standard.
+    #[serde(default)]
+    pub data: serde_json::Value,
+}
+
+/// An event, along with the receipt that emitted it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmittedEvent {
+    /// The receipt whose execution emitted the event.
+    pub receipt_id: CryptoHash,
+    /// The contract that emitted the event.
+    pub executor_id: AccountId,
+    /// The event.
+    pub event: Event,
+}
+
+/// Parse the event held by `log`, or return `None` if it isn't an event log.
+pub fn parse_event(log: &str) -> Option<Result<Event, serde_json::Error>> {
+    log.strip_prefix(EVENT_LOG_PREFIX)
+        .map(|json| serde_json::from_str(json.trim()))
+}
+
+/// Extract the events emitted by the receipts of a transaction, in the order they were executed.
+///
+/// Malformed event logs are skipped.
+pub fn events(outcome: &FinalExecutionOutcomeView) -> Vec<EmittedEvent> {
+    outcome
+        .logs()
+        .into_iter()
+        .filter_map(|log| match parse_event(&log.log)? {
+            Ok(event) => Some(EmittedEvent {
+                receipt_id: log.receipt_id,
+                executor_id: log.executor_id,
+                event,
+            }),
+            Err(err) => {
+                log::debug!("malformed event log from {}: {}", log.receipt_id, err);
+                None
+            }
+        })
+        .collect()
+}
+
+/// An event of a fungible token, as defined by NEP-141.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FtEvent {
+    /// Tokens were minted.
+    Mint(Vec<FtMint>),
+    /// Tokens were transferred.
+    Transfer(Vec<FtTransfer>),
+    /// Tokens were burnt.
+    Burn(Vec<FtBurn>),
+}
+
+/// Fungible tokens minted.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct FtMint {
+    pub owner_id: AccountId,
+    #[serde(with = "dec_format")]
+    pub amount: Balance,
+    pub memo: Option<String>,
+}
+
+/// Fungible tokens transferred.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct FtTransfer {
+    pub old_owner_id: AccountId,
+    pub new_owner_id: AccountId,
+    #[serde(with = "dec_format")]
+    pub amount: Balance,
+    pub memo: Option<String>,
+}
+
+/// Fungible tokens burnt.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct FtBurn {
+    pub owner_id: AccountId,
+    #[serde(with = "dec_format")]
+    pub amount: Balance,
+    pub memo: Option<String>,
+}
+
+/// An event of a non-fungible token, as defined by NEP-171.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NftEvent {
+    /// Tokens were minted.
+    Mint(Vec<NftMint>),
+    /// Tokens were transferred.
+    Transfer(Vec<NftTransfer>),
+    /// Tokens were burnt.
+    Burn(Vec<NftBurn>),
+}
+
+/// Non-fungible tokens minted.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct NftMint {
+    pub owner_id: AccountId,
+    pub token_ids: Vec<String>,
+    pub memo: Option<String>,
+}
+
+/// Non-fungible tokens transferred.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct NftTransfer {
+    pub authorized_id: Option<AccountId>,
+    pub old_owner_id: AccountId,
+    pub new_owner_id: AccountId,
+    pub token_ids: Vec<String>,
+    pub memo: Option<String>,
+}
+
+/// Non-fungible tokens burnt.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct NftBurn {
+    pub owner_id: AccountId,
+    pub authorized_id: Option<AccountId>,
+    pub token_ids: Vec<String>,
+    pub memo: Option<String>,
+}
+
+impl Event {
+    /// Returns true if the event follows `standard`, like `nep141`.
+    pub fn is(&self, standard: &str) -> bool {
+        self.standard == standard
+    }
+
+    /// Parse the event of a fungible token, or return `None` if the event isn't one.
+    pub fn ft(&self) -> Option<Result<FtEvent, serde_json::Error>> {
+        if !self.is("nep141") {
+            return None;
+        }
+        let data = self.data.clone();
+        Some(match self.event.as_str() {
+            "ft_mint" => serde_json::from_value(data).map(FtEvent::Mint),
+            "ft_transfer" => serde_json::from_value(data).map(FtEvent::Transfer),
+            "ft_burn" => serde_json::from_value(data).map(FtEvent::Burn),
+            _ => return None,
+        })
+    }
+
+    /// Parse the event of a non-fungible token, or return `None` if the event isn't one.
+    pub fn nft(&self) -> Option<Result<NftEvent, serde_json::Error>> {
+        if !self.is("nep171") {
+            return None;
+        }
+        let data = self.data.clone();
+        Some(match self.event.as_str() {
+            "nft_mint" => serde_json::from_value(data).map(NftEvent::Mint),
+            "nft_transfer" => serde_json::from_value(data).map(NftEvent::Transfer),
+            "nft_burn" => serde_json::from_value(data).map(NftEvent::Burn),
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_logs_are_parsed() {
+        assert!(parse_event("Transfer 1 from alice.near to bob.near").is_none());
+        assert!(matches!(parse_event("EVENT_JSON:{"), Some(Err(_))));
+
+        let event = parse_event(
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"alice.near","new_owner_id":"bob.near","amount":"1000"}]}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(event.event, "ft_transfer");
+        assert!(event.nft().is_none());
+        assert_eq!(
+            event.ft().unwrap().unwrap(),
+            FtEvent::Transfer(vec![FtTransfer {
+                old_owner_id: "alice.near".parse().unwrap(),
+                new_owner_id: "bob.near".parse().unwrap(),
+                amount: 1000,
+                memo: None,
+            }])
+        );
+
+        let event = parse_event(
+            r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_mint","data":[{"owner_id":"alice.near","token_ids":["1","2"]}]}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(matches!(
+            event.nft(),
+            Some(Ok(NftEvent::Mint(mints))) if mints[0].token_ids == ["1", "2"]
+        ));
+    }
+}
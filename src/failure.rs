@@ -0,0 +1,193 @@
+//! Failures of function calls.
+//!
+//! When a function call fails, the reason is nested deep in the outcome of the transaction: a
+//! [`TxExecutionError::ActionError`], whose kind is an [`ActionErrorKind::FunctionCallError`],
+//! whose message may in turn have to be parsed. Depending on the version of the node, a contract
+//! panicking is reported either as a [`HostError::GuestPanic`] or as an
+//! [`ExecutionError`](FunctionCallError::ExecutionError) holding its message.
+//!
+//! [`FunctionCallFailure`] digs the reason out, and tells the common cases apart: the contract
+//! panicking, the method called not existing, and the call running out of gas.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::failure::FunctionCallFailure;
+//! use near_jsonrpc_client::JsonRpcClient;
+//! use serde_json::json;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let signer = near_crypto::InMemorySigner::from_secret_key(
+//!     "fido.testnet".parse()?,
+//!     "ed25519:12dhevYshfiRqFSu8DSfxA27pTkmGRv6C5qQWTJYTcBEoB7MSTyidghi5NWXzWqrxCKgxVx97bpXPYQxYN5dieU".parse()?,
+//! );
+//!
+//! let contract = client.contract("nosedive.testnet".parse()?);
+//!
+//! let response = client
+//!     .transaction(signer, contract.id().clone())
+//!     .action(contract.call(
+//!         "set_status",
+//!         json!({ "message": "working on it" }),
+//!         10_000_000_000_000, // 10 TeraGas
+//!         0,
+//!     ))
+//!     .send()
+//!     .await?;
+//!
+//! match response
+//!     .final_execution_outcome
+//!     .as_ref()
+//!     .and_then(FunctionCallFailure::from_outcome)
+//! {
+//!     Some(FunctionCallFailure::Panic { message }) => println!("panicked: {}", message),
+//!     Some(failure) => println!("failed: {}", failure),
+//!     None => println!("no function call failed"),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use near_primitives::errors::{
+    ActionErrorKind, FunctionCallError, HostError, MethodResolveError, TxExecutionError,
+};
+use near_primitives::views::{FinalExecutionOutcomeViewEnum, FinalExecutionStatus};
+
+/// The message prefix of contract panics reported as execution errors.
+const PANIC_PREFIX: &str = "Smart contract panicked: ";
+
+/// The message of gas exhaustion reported as an execution error.
+const GAS_EXCEEDED: &str = "Exceeded the prepaid gas.";
+
+/// The reason a function call failed.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum FunctionCallFailure {
+    /// The contract panicked.
+    #[error("the contract panicked: {message}")]
+    Panic { message: String },
+    /// The method called doesn't exist on the contract.
+    #[error("the method called doesn't exist")]
+    MethodNotFound,
+    /// The call ran out of the gas attached.
+    #[error("the call ran out of the gas attached")]
+    GasExceeded,
+    /// The call failed for another reason.
+    #[error("the call failed: {0:?}")]
+    Other(FunctionCallError),
+}
+
+impl FunctionCallFailure {
+    /// The reason of `error`.
+    pub fn from_error(error: &FunctionCallError) -> Self {
+        match error {
+            FunctionCallError::HostError(HostError::GuestPanic { panic_msg }) => Self::Panic {
+                message: panic_msg.clone(),
+            },
+            FunctionCallError::HostError(HostError::GasExceeded) => Self::GasExceeded,
+            FunctionCallError::MethodResolveError(MethodResolveError::MethodNotFound) => {
+                Self::MethodNotFound
+            }
+            FunctionCallError::ExecutionError(message) => {
+                if let Some(message) = message.strip_prefix(PANIC_PREFIX) {
+                    Self::Panic {
+                        message: message.to_string(),
+                    }
+                } else if message == GAS_EXCEEDED {
+                    Self::GasExceeded
+                } else {
+                    Self::Other(error.clone())
+                }
+            }
+            _ => Self::Other(error.clone()),
+        }
+    }
+
+    /// The reason of the function call failing in `error`, or `None` if `error` isn't a function
+    /// call failing.
+    pub fn from_tx_error(error: &TxExecutionError) -> Option<Self> {
+        match error {
+            TxExecutionError::ActionError(err) => match &err.kind {
+                ActionErrorKind::FunctionCallError(err) => Some(Self::from_error(err)),
+                _ => None,
+            },
+            TxExecutionError::InvalidTxError(_) => None,
+        }
+    }
+
+    /// The reason of the function call failing the transaction of `outcome`, or `None` if the
+    /// transaction didn't fail for a function call.
+    pub fn from_outcome(outcome: &FinalExecutionOutcomeViewEnum) -> Option<Self> {
+        let status = match outcome {
+            FinalExecutionOutcomeViewEnum::FinalExecutionOutcome(outcome) => &outcome.status,
+            FinalExecutionOutcomeViewEnum::FinalExecutionOutcomeWithReceipt(outcome) => {
+                &outcome.final_outcome.status
+            }
+        };
+        match status {
+            FinalExecutionStatus::Failure(err) => Self::from_tx_error(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use near_primitives::errors::ActionError;
+
+    #[test]
+    fn function_call_errors_are_decoded() {
+        let failure = |error| {
+            FunctionCallFailure::from_tx_error(&TxExecutionError::ActionError(ActionError {
+                index: Some(0),
+                kind: ActionErrorKind::FunctionCallError(error),
+            }))
+        };
+
+        assert_eq!(
+            failure(FunctionCallError::ExecutionError(
+                "Smart contract panicked: not enough balance".to_string()
+            )),
+            Some(FunctionCallFailure::Panic {
+                message: "not enough balance".to_string()
+            })
+        );
+        assert_eq!(
+            failure(FunctionCallError::HostError(HostError::GuestPanic {
+                panic_msg: "not enough balance".to_string()
+            })),
+            Some(FunctionCallFailure::Panic {
+                message: "not enough balance".to_string()
+            })
+        );
+        assert_eq!(
+            failure(FunctionCallError::ExecutionError(
+                "Exceeded the prepaid gas.".to_string()
+            )),
+            Some(FunctionCallFailure::GasExceeded)
+        );
+        assert_eq!(
+            failure(FunctionCallError::MethodResolveError(
+                MethodResolveError::MethodNotFound
+            )),
+            Some(FunctionCallFailure::MethodNotFound)
+        );
+        assert!(matches!(
+            failure(FunctionCallError::WasmUnknownError),
+            Some(FunctionCallFailure::Other(_))
+        ));
+
+        assert_eq!(
+            FunctionCallFailure::from_tx_error(&TxExecutionError::ActionError(ActionError {
+                index: Some(0),
+                kind: ActionErrorKind::AccountDoesNotExist {
+                    account_id: "alice.near".parse().unwrap()
+                },
+            })),
+            None
+        );
+    }
+}
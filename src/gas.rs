@@ -0,0 +1,306 @@
+//! Estimating the gas of transactions.
+//!
+//! A [`GasEstimator`] prices the actions of a transaction with the costs of the protocol config,
+//! for the gas burnt converting the transaction and executing its actions. The gas of function
+//! calls also depends on the code they run, which only executing them tells: with a
+//! [`dry_run`](GasEstimator::dry_run), the transaction is first executed on a sandbox, like a
+//! local `near-sandbox` node or a fork of the network, and the gas burnt there suggests the gas to
+//! attach to the function calls, plus a [`margin`](GasEstimator::margin).
+//!
+//! View calls don't report the gas they burn, so they can't be used for a dry run.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::gas::GasEstimator;
+//! use near_jsonrpc_client::JsonRpcClient;
+//! use serde_json::json;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//! let sandbox = JsonRpcClient::connect("http://localhost:3030");
+//!
+//! let signer = near_crypto::InMemorySigner::from_secret_key(
+//!     "fido.testnet".parse()?,
+//!     "ed25519:12dhevYshfiRqFSu8DSfxA27pTkmGRv6C5qQWTJYTcBEoB7MSTyidghi5NWXzWqrxCKgxVx97bpXPYQxYN5dieU".parse()?,
+//! );
+//!
+//! let estimator = GasEstimator::new(client.clone()).dry_run(sandbox, signer);
+//!
+//! let counter = client.contract("counter.testnet".parse()?);
+//! let increment = counter.call("increment", json!({}), 0, 0);
+//!
+//! let estimate = estimator.estimate(counter.id(), &[increment]).await?;
+//! println!(
+//!     "fees: {} gas, attach: {:?} gas",
+//!     estimate.fees, estimate.attached_gas
+//! );
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+use std::sync::Arc;
+
+use near_primitives::account::AccessKeyPermission;
+use near_primitives::errors::TxExecutionError;
+use near_primitives::transaction::Action;
+use near_primitives::types::{AccountId, BlockReference, Finality, Gas};
+use near_primitives::views::FinalExecutionStatus;
+use tokio::sync::OnceCell;
+
+use crate::errors::{JsonRpcError, SendTransactionError};
+use crate::methods::EXPERIMENTAL_protocol_config::{
+    RpcProtocolConfigError, RpcProtocolConfigRequest, RpcProtocolConfigResponse,
+};
+use crate::signer::TransactionSigner;
+use crate::transaction::TransactionBuilder;
+use crate::JsonRpcClient;
+
+/// Potential errors returned while estimating the gas of a transaction.
+#[derive(Debug, thiserror::Error)]
+pub enum GasEstimateError {
+    /// The protocol config, holding the costs of actions, couldn't be viewed.
+    #[error(transparent)]
+    ProtocolConfigError(Box<JsonRpcError<RpcProtocolConfigError>>),
+    /// The transaction couldn't be sent to the sandbox.
+    #[error("error while sending the dry run: [{0}]")]
+    DryRunError(Box<SendTransactionError>),
+    /// The transaction failed on the sandbox.
+    #[error("the dry run failed: [{0}]")]
+    DryRunFailed(TxExecutionError),
+}
+
+impl From<JsonRpcError<RpcProtocolConfigError>> for GasEstimateError {
+    fn from(err: JsonRpcError<RpcProtocolConfigError>) -> Self {
+        Self::ProtocolConfigError(Box::new(err))
+    }
+}
+
+/// The estimated gas of a transaction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GasEstimate {
+    /// The gas burnt converting the transaction and executing its actions, as priced by the
+    /// protocol config, excluding the gas burnt running contract code.
+    pub fees: Gas,
+    /// The gas burnt by the receipts of the dry run, if any.
+    pub dry_run: Option<Gas>,
+    /// The gas to attach to the function calls of the transaction altogether, if it has any and
+    /// was dry run.
+    pub attached_gas: Option<Gas>,
+}
+
+/// Estimates the gas of transactions.
+///
+/// Clones share the same protocol config, viewed once.
+#[derive(Clone)]
+pub struct GasEstimator {
+    client: JsonRpcClient,
+    config: Arc<OnceCell<RpcProtocolConfigResponse>>,
+    sandbox: Option<(JsonRpcClient, Arc<dyn TransactionSigner>)>,
+    margin: u64,
+}
+
+impl GasEstimator {
+    /// Create an estimator viewing the protocol config with `client`.
+    pub fn new(client: JsonRpcClient) -> Self {
+        Self {
+            client,
+            config: Default::default(),
+            sandbox: None,
+            margin: 20,
+        }
+    }
+
+    /// Execute the transactions estimated on `sandbox` first, signed by `signer`, to measure the
+    /// gas their function calls burn.
+    ///
+    /// The signer must exist on the sandbox, along with the receivers of the transactions.
+    pub fn dry_run(
+        mut self,
+        sandbox: JsonRpcClient,
+        signer: impl TransactionSigner + 'static,
+    ) -> Self {
+        self.sandbox = Some((sandbox, Arc::new(signer)));
+        self
+    }
+
+    /// Attach `percent` more gas than the dry run burnt. Defaults to 20%.
+    pub fn margin(mut self, percent: u64) -> Self {
+        self.margin = percent;
+        self
+    }
+
+    /// Estimate the gas of a transaction sending `actions` to `receiver_id`.
+    pub async fn estimate(
+        &self,
+        receiver_id: &AccountId,
+        actions: &[Action],
+    ) -> Result<GasEstimate, GasEstimateError> {
+        let config = self
+            .config
+            .get_or_try_init(|| async {
+                self.client
+                    .call(RpcProtocolConfigRequest {
+                        block_reference: BlockReference::Finality(Finality::Final),
+                    })
+                    .await
+            })
+            .await?;
+        let fees = transaction_fees(config, actions);
+
+        let calls = actions
+            .iter()
+            .filter(|action| matches!(action, Action::FunctionCall(_)))
+            .count() as u64;
+        let (Some((sandbox, signer)), true) = (&self.sandbox, calls > 0) else {
+            return Ok(GasEstimate {
+                fees,
+                dry_run: None,
+                attached_gas: None,
+            });
+        };
+
+        let max_gas = config
+            .runtime_config
+            .wasm_config
+            .limit_config
+            .max_total_prepaid_gas;
+        let actions = actions.iter().cloned().map(|mut action| {
+            if let Action::FunctionCall(call) = &mut action {
+                call.gas = max_gas / calls;
+            }
+            action
+        });
+        let response =
+            TransactionBuilder::new(sandbox.clone(), signer.clone(), receiver_id.clone())
+                .actions(actions)
+                .send()
+                .await
+                .map_err(|err| GasEstimateError::DryRunError(Box::new(err)))?;
+        let burnt = match response.final_execution_outcome {
+            Some(outcome) => {
+                let outcome = outcome.into_outcome();
+                if let FinalExecutionStatus::Failure(err) = outcome.status {
+                    return Err(GasEstimateError::DryRunFailed(err));
+                }
+                outcome
+                    .receipts_outcome
+                    .iter()
+                    .map(|receipt| receipt.outcome.gas_burnt)
+                    .sum()
+            }
+            None => 0,
+        };
+
+        Ok(GasEstimate {
+            fees,
+            dry_run: Some(burnt),
+            attached_gas: Some((burnt.saturating_mul(100 + self.margin) / 100).min(max_gas)),
+        })
+    }
+}
+
+impl fmt::Debug for GasEstimator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GasEstimator")
+            .field("client", &self.client)
+            .field(
+                "sandbox",
+                &self.sandbox.as_ref().map(|(sandbox, _)| sandbox),
+            )
+            .field("margin", &self.margin)
+            .finish()
+    }
+}
+
+/// The gas burnt converting a transaction with `actions` into a receipt, then executing it,
+/// assuming the receipt crosses shards.
+fn transaction_fees(config: &RpcProtocolConfigResponse, actions: &[Action]) -> Gas {
+    // the fee types aren't exported by near-primitives, only their fields
+    macro_rules! cost {
+        ($fee:expr) => {
+            $fee.send_not_sir + $fee.execution
+        };
+        ($fee:expr, $per_byte:expr, $bytes:expr) => {
+            cost!($fee) + $bytes as u64 * cost!($per_byte)
+        };
+    }
+
+    let costs = &config.runtime_config.transaction_costs;
+    let fees = &costs.action_creation_config;
+    let actions = actions.iter().map(|action| match action {
+        Action::CreateAccount(_) => cost!(fees.create_account_cost),
+        Action::DeployContract(deploy) => cost!(
+            fees.deploy_contract_cost,
+            fees.deploy_contract_cost_per_byte,
+            deploy.code.len()
+        ),
+        Action::FunctionCall(call) => cost!(
+            fees.function_call_cost,
+            fees.function_call_cost_per_byte,
+            call.method_name.len() + call.args.len()
+        ),
+        Action::Transfer(_) => cost!(fees.transfer_cost),
+        Action::Stake(_) => cost!(fees.stake_cost),
+        Action::AddKey(add_key) => match &add_key.access_key.permission {
+            AccessKeyPermission::FunctionCall(permission) => cost!(
+                fees.add_key_cost.function_call_cost,
+                fees.add_key_cost.function_call_cost_per_byte,
+                permission
+                    .method_names
+                    .iter()
+                    .map(|name| name.len() + 1)
+                    .sum::<usize>()
+            ),
+            AccessKeyPermission::FullAccess => cost!(fees.add_key_cost.full_access_cost),
+        },
+        Action::DeleteKey(_) => cost!(fees.delete_key_cost),
+        Action::DeleteAccount(_) => cost!(fees.delete_account_cost),
+        // the delegated actions are executed in a receipt of their own
+        Action::Delegate(delegate) => {
+            cost!(fees.delegate_cost)
+                + transaction_fees(config, &delegate.delegate_action.get_actions())
+        }
+    });
+    cost!(costs.action_receipt_creation_config) + actions.sum::<Gas>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use near_chain_configs::{GenesisConfig, ProtocolConfig};
+    use near_parameters::RuntimeConfig;
+    use near_primitives::transaction::{FunctionCallAction, TransferAction};
+
+    #[test]
+    fn fees_are_priced_per_action() {
+        let config = RpcProtocolConfigResponse::from(ProtocolConfig {
+            genesis_config: GenesisConfig::default(),
+            runtime_config: RuntimeConfig::test(),
+        });
+        let costs = &config.runtime_config.transaction_costs;
+        let receipt = costs.action_receipt_creation_config.send_not_sir
+            + costs.action_receipt_creation_config.execution;
+        let fees = &costs.action_creation_config;
+
+        let transfer = fees.transfer_cost.send_not_sir + fees.transfer_cost.execution;
+        assert_eq!(
+            transaction_fees(&config, &[Action::Transfer(TransferAction { deposit: 1 })]),
+            receipt + transfer
+        );
+
+        let call = Action::FunctionCall(Box::new(FunctionCallAction {
+            method_name: "increment".to_string(),
+            args: b"{}".to_vec(),
+            gas: 0,
+            deposit: 0,
+        }));
+        let call_fee = fees.function_call_cost.send_not_sir
+            + fees.function_call_cost.execution
+            + 11 * (fees.function_call_cost_per_byte.send_not_sir
+                + fees.function_call_cost_per_byte.execution);
+        assert_eq!(transaction_fees(&config, &[call]), receipt + call_fee);
+    }
+}
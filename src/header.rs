@@ -2,9 +2,50 @@
 //!
 //! This module includes everything you need to build valid header entries.
 
+use std::fmt;
 use std::marker::PhantomData;
 
-pub use reqwest::header::{HeaderName, HeaderValue, InvalidHeaderValue, ToStrError};
+pub use http::header::{HeaderMap, HeaderName, HeaderValue, InvalidHeaderValue, ToStrError};
+
+/// Headers whose values are always redacted from logs and `Debug` output.
+///
+/// Values marked as sensitive (See [`HeaderValue::set_sensitive`]) and headers registered with
+/// [`JsonRpcClient::sensitive_header`](crate::JsonRpcClient::sensitive_header) are redacted as well.
+pub const SENSITIVE_HEADERS: [HeaderName; 5] = [
+    http::header::AUTHORIZATION,
+    http::header::PROXY_AUTHORIZATION,
+    http::header::COOKIE,
+    http::header::SET_COOKIE,
+    HeaderName::from_static("x-api-key"),
+];
+
+/// `Debug` view of a header map with the values of sensitive headers redacted.
+pub(crate) struct Redacted<'a> {
+    pub(crate) headers: &'a HeaderMap,
+    pub(crate) sensitive: &'a [HeaderName],
+}
+
+impl fmt::Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct RedactedValue;
+
+        impl fmt::Debug for RedactedValue {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("[redacted]")
+            }
+        }
+
+        f.debug_map()
+            .entries(self.headers.iter().map(|(name, value)| {
+                let redact = value.is_sensitive()
+                    || SENSITIVE_HEADERS.contains(name)
+                    || self.sensitive.contains(name);
+                let value: &dyn fmt::Debug = if redact { &RedactedValue } else { value };
+                (name, value)
+            }))
+            .finish()
+    }
+}
 
 /// [`HeaderEntry`] attribute identifying those that have been prevalidated.
 ///
@@ -121,7 +162,7 @@ where
 
 pub use discriminant::HeaderEntryDiscriminant;
 mod discriminant {
-    use reqwest::header::IntoHeaderName;
+    use http::header::IntoHeaderName;
 
     use super::{super::JsonRpcClient, HeaderEntry, HeaderValue, Postvalidated, Prevalidated};
 
@@ -144,7 +185,7 @@ mod discriminant {
 
         fn apply(mut client: JsonRpcClient, entry: T) -> Self::Output {
             let (k, v) = entry.header_pair();
-            client.headers.insert(k, v);
+            client.headers_mut().insert(k, v);
             client
         }
     }
@@ -160,7 +201,7 @@ mod discriminant {
 
         fn apply(mut client: JsonRpcClient, entry: T) -> Self::Output {
             let (k, v) = entry.header_pair();
-            client.headers.insert(k, v.try_into()?);
+            client.headers_mut().insert(k, v.try_into()?);
             Ok(client)
         }
     }
@@ -195,3 +236,24 @@ mod discriminant {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::JsonRpcClient;
+
+    #[test]
+    fn sensitive_headers_are_redacted() -> Result<(), Box<dyn std::error::Error>> {
+        let client = JsonRpcClient::connect("https://rpc.testnet.near.org")
+            .header(("x-api-key", "api-secret"))?
+            .header(("x-custom-token", "custom-secret"))?
+            .header(("x-tenant", "tenant-a"))?
+            .sensitive_header(super::HeaderName::from_static("x-custom-token"));
+
+        let debug = format!("{:?}", client);
+        assert!(!debug.contains("api-secret"), "{}", debug);
+        assert!(!debug.contains("custom-secret"), "{}", debug);
+        assert!(debug.contains("tenant-a"), "{}", debug);
+
+        Ok(())
+    }
+}
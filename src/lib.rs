@@ -58,22 +58,78 @@
 //!    # Ok(())
 //!    # }
 //!    ```
-use std::{fmt, sync::Arc};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::{fmt, time::Duration};
 
 use lazy_static::lazy_static;
 
+#[cfg(feature = "abi")]
+pub mod abi;
+pub mod account;
+pub mod actions;
 pub mod auth;
+pub mod block_hash;
+pub mod cache;
+pub mod capabilities;
+pub mod config;
+pub mod congestion;
+pub mod contract;
+mod deadline;
+pub mod delegate;
+pub mod endpoints;
 pub mod errors;
+pub mod events;
+pub mod failure;
+pub mod gas;
 pub mod header;
+pub mod logs;
 pub mod methods;
+pub mod nonce;
+pub mod providers;
+pub mod proxy;
+pub mod retry;
+pub mod signer;
+pub mod signing;
+pub mod staking;
+pub mod stats;
+pub mod storage;
+#[cfg(feature = "opentelemetry")]
+mod telemetry;
+mod throttle;
+pub mod tls;
+pub mod transaction;
+mod transport;
+pub mod tx_status;
+pub mod views;
+
+#[cfg(not(any(feature = "reqwest", feature = "hyper-transport")))]
+compile_error!(
+    "a transport backend is required, enable either the `reqwest` feature (plain HTTP), or the \
+     `native-tls`, `rustls-tls` or `hyper-transport` feature"
+);
 
 use errors::*;
 
+pub use deadline::Deadline;
+pub use tokio_util::sync::CancellationToken;
+
 pub const NEAR_MAINNET_RPC_URL: &str = "https://rpc.mainnet.near.org";
 pub const NEAR_TESTNET_RPC_URL: &str = "https://rpc.testnet.near.org";
 pub const NEAR_MAINNET_ARCHIVAL_RPC_URL: &str = "https://archival-rpc.mainnet.near.org";
 pub const NEAR_TESTNET_ARCHIVAL_RPC_URL: &str = "https://archival-rpc.testnet.near.org";
 
+type RequestIdGenerator = Arc<dyn Fn() -> String + Send + Sync>;
+
+type PayloadHook = Arc<dyn Fn(&str, &[u8]) + Send + Sync>;
+
+/// Closures invoked with the raw payloads exchanged with the server.
+#[derive(Clone, Default)]
+struct Hooks {
+    on_request: Option<PayloadHook>,
+    on_response: Option<PayloadHook>,
+}
+
 lazy_static! {
     static ref DEFAULT_CONNECTOR: JsonRpcClientConnector = JsonRpcClient::new_client();
 }
@@ -81,27 +137,524 @@ lazy_static! {
 /// NEAR JSON RPC client connector.
 #[derive(Clone)]
 pub struct JsonRpcClientConnector {
-    client: reqwest::Client,
+    client: transport::Transport,
+    timeouts: MethodTimeouts,
+    retry_policy: retry::RetryPolicy,
+    retry_budget: Option<retry::RetryBudget>,
+    throttle: bool,
+    cache: Option<cache::ResponseCache>,
+    signer: Option<signing::HmacSigner>,
+    max_response_size: Option<usize>,
+    request_ids: Option<RequestIdGenerator>,
+    correlation_header: Option<header::HeaderName>,
+    slow_calls: Option<stats::SlowCallWatch>,
+    method_fallback: bool,
+    hooks: Hooks,
 }
 
 impl JsonRpcClientConnector {
+    /// Return a JsonRpcClient that connects to the specified server, after validating its address.
+    ///
+    /// See [`JsonRpcClient::try_connect`].
+    pub fn try_connect<U: AsUrl>(
+        &self,
+        server_addr: U,
+    ) -> Result<JsonRpcClient, InvalidServerAddrError> {
+        let server_addr = server_addr.to_addr();
+        let server_url = parse_server_addr(&server_addr)?;
+        Ok(self.new_client(server_addr, Ok(server_url)))
+    }
+
+    /// Like [`try_connect`](Self::try_connect), for a server address holding a secret, like the
+    /// API key of some providers.
+    ///
+    /// `display_addr` is shown in place of the server address wherever it's printed.
+    pub(crate) fn try_connect_redacted(
+        &self,
+        server_addr: &str,
+        display_addr: String,
+    ) -> Result<JsonRpcClient, InvalidServerAddrError> {
+        let server_url = parse_server_addr(server_addr)?;
+        Ok(self.new_client(display_addr, Ok(server_url)))
+    }
+
     /// Return a JsonRpcClient that connects to the specified server.
     pub fn connect<U: AsUrl>(&self, server_addr: U) -> JsonRpcClient {
+        let server_addr = server_addr.to_addr();
+        let server_url = url::Url::parse(&server_addr);
+        self.new_client(server_addr, server_url)
+    }
+
+    fn new_client(
+        &self,
+        server_addr: String,
+        server_url: Result<url::Url, url::ParseError>,
+    ) -> JsonRpcClient {
         log::debug!("returned a new JSONRPC client handle");
 
         JsonRpcClient {
             inner: Arc::new(JsonRpcInnerClient {
-                server_addr: server_addr.to_string(),
+                server_addr,
+                server_url,
                 client: self.client.clone(),
+                timeouts: self.timeouts.clone(),
+                retry_policy: self.retry_policy,
+                retry_budget: self.retry_budget.clone(),
+                throttle: self.throttle.then(throttle::Throttle::default),
+                stats: stats::StatsRecorder::default(),
+                cache: self.cache.clone(),
+                signer: self.signer.clone(),
+                max_response_size: self.max_response_size,
+                request_ids: self.request_ids.clone(),
+                correlation_header: self.correlation_header.clone(),
+                slow_calls: self.slow_calls.clone(),
+                method_fallback: self.method_fallback,
+                unsupported_methods: Mutex::default(),
+                chain_id: Mutex::default(),
+                hooks: self.hooks.clone(),
             }),
-            headers: reqwest::header::HeaderMap::new(),
+            headers: Arc::default(),
+            deadline: None,
+            request_id: None,
+            sensitive_headers: Arc::default(),
+            archival_fallback: None,
+            expected_chain_id: None,
         }
     }
+
+    /// Set the default timeout for method calls made by clients of this connector.
+    ///
+    /// This applies to every method without a dedicated timeout (See [`method_timeout`](Self::method_timeout)).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use near_jsonrpc_client::JsonRpcClient;
+    ///
+    /// let client = JsonRpcClient::new_client()
+    ///     .timeout(Duration::from_secs(10))
+    ///     .connect("https://rpc.testnet.near.org");
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.default = Some(timeout);
+        self
+    }
+
+    /// Set the timeout for calls to a specific method, overriding the default timeout.
+    ///
+    /// Not all NEAR methods are equally fast, a transaction commit could take much longer than a
+    /// `status` request, so you can budget for each one individually.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use near_jsonrpc_client::JsonRpcClient;
+    ///
+    /// let client = JsonRpcClient::new_client()
+    ///     .method_timeout("broadcast_tx_commit", Duration::from_secs(60))
+    ///     .method_timeout("send_tx", Duration::from_secs(60))
+    ///     .method_timeout("query", Duration::from_secs(10))
+    ///     .method_timeout("status", Duration::from_secs(5))
+    ///     .connect("https://rpc.testnet.near.org");
+    /// ```
+    pub fn method_timeout(mut self, method_name: &str, timeout: Duration) -> Self {
+        self.timeouts
+            .methods
+            .insert(method_name.to_string(), timeout);
+        self
+    }
+
+    /// Set the policy for retrying failed method calls.
+    ///
+    /// Failures where the request is known not to have been processed by the server, e.g connection
+    /// failures and `429 Too Many Requests` responses, are always retried. Other transient failures
+    /// like timeouts are only retried for [idempotent](methods::RpcMethod::is_idempotent) methods.
+    ///
+    /// See the [`retry`] module documentation for more information.
+    pub fn retry_policy(mut self, policy: retry::RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Set a budget limiting the proportion of method calls that may be retries.
+    ///
+    /// See the [`retry`] module documentation for more information.
+    pub fn retry_budget(mut self, budget: retry::RetryBudget) -> Self {
+        self.retry_budget = Some(budget);
+        self
+    }
+
+    /// Enable or disable adaptive throttling, enabled by default.
+    ///
+    /// When the server advertises its rate limits via `x-ratelimit-*` or `Retry-After` headers,
+    /// clients track the remaining quota and delay subsequent calls to stay under the limit.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::JsonRpcClient;
+    ///
+    /// let client = JsonRpcClient::new_client()
+    ///     .adaptive_throttling(false)
+    ///     .connect("https://rpc.testnet.near.org");
+    /// ```
+    pub fn adaptive_throttling(mut self, enabled: bool) -> Self {
+        self.throttle = enabled;
+        self
+    }
+
+    /// Sign the body of every request, as required by some self-hosted RPC gateways.
+    ///
+    /// See the [`signing`] module documentation for more information.
+    pub fn sign_requests(mut self, signer: signing::HmacSigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Set the maximum size of a response body, in bytes.
+    ///
+    /// Reading a response is aborted as soon as it exceeds this size, and the call fails with
+    /// [`JsonRpcTransportRecvError::ResponseTooLarge`]. This guards against buffering unexpectedly
+    /// large responses into memory, like the entire state of a huge contract.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::JsonRpcClient;
+    ///
+    /// let client = JsonRpcClient::new_client()
+    ///     .max_response_size(16 * 1024 * 1024)
+    ///     .connect("https://rpc.testnet.near.org");
+    /// ```
+    pub fn max_response_size(mut self, limit: usize) -> Self {
+        self.max_response_size = Some(limit);
+        self
+    }
+
+    /// Generate the JSON RPC ids of requests with `generator`, instead of random strings.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    ///
+    /// use near_jsonrpc_client::JsonRpcClient;
+    ///
+    /// let counter = AtomicU64::new(0);
+    ///
+    /// let client = JsonRpcClient::new_client()
+    ///     .request_ids(move || format!("indexer-{}", counter.fetch_add(1, Ordering::Relaxed)))
+    ///     .connect("https://rpc.testnet.near.org");
+    /// ```
+    pub fn request_ids<F>(mut self, generator: F) -> Self
+    where
+        F: Fn() -> String + Send + Sync + 'static,
+    {
+        self.request_ids = Some(Arc::new(generator));
+        self
+    }
+
+    /// Attach the id of every request in the specified header.
+    ///
+    /// Unlike the id in the JSON RPC payload, headers are visible to gateways and load balancers,
+    /// so this lets logs on the client, gateway and node be joined.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::{header::HeaderName, JsonRpcClient};
+    ///
+    /// let client = JsonRpcClient::new_client()
+    ///     .correlation_header(HeaderName::from_static("x-request-id"))
+    ///     .connect("https://rpc.testnet.near.org")
+    ///     .with_request_id("import-job-42");
+    /// ```
+    pub fn correlation_header(mut self, header: header::HeaderName) -> Self {
+        self.correlation_header = Some(header);
+        self
+    }
+
+    /// Log a warning for every method call that takes longer than `threshold`, including retries.
+    ///
+    /// The warning includes the method name and the server address, to help investigate
+    /// slowness in production without enabling debug logs.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use near_jsonrpc_client::JsonRpcClient;
+    ///
+    /// let client = JsonRpcClient::new_client()
+    ///     .slow_call_threshold(Duration::from_secs(2))
+    ///     .connect("https://rpc.testnet.near.org");
+    /// ```
+    pub fn slow_call_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_calls = Some(stats::SlowCallWatch::new(threshold, None));
+        self
+    }
+
+    /// Invoke `callback`, instead of logging, for every method call that takes longer than `threshold`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use near_jsonrpc_client::JsonRpcClient;
+    ///
+    /// let client = JsonRpcClient::new_client()
+    ///     .on_slow_call(Duration::from_secs(2), |call| {
+    ///         eprintln!("{} on {} took {:?}", call.method_name, call.server_addr, call.elapsed);
+    ///     })
+    ///     .connect("https://rpc.testnet.near.org");
+    /// ```
+    pub fn on_slow_call<F>(mut self, threshold: Duration, callback: F) -> Self
+    where
+        F: Fn(&stats::SlowCall<'_>) + Send + Sync + 'static,
+    {
+        self.slow_calls = Some(stats::SlowCallWatch::new(
+            threshold,
+            Some(Arc::new(callback)),
+        ));
+        self
+    }
+
+    /// Invoke `hook` with the method name and serialized payload of every request, right before it's sent.
+    ///
+    /// This is useful for ad-hoc debugging, persisting requests or custom metrics.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::JsonRpcClient;
+    ///
+    /// let client = JsonRpcClient::new_client()
+    ///     .on_request(|method_name, payload| {
+    ///         eprintln!("-> {}: {}", method_name, String::from_utf8_lossy(payload));
+    ///     })
+    ///     .on_response(|method_name, body| {
+    ///         eprintln!("<- {}: {} bytes", method_name, body.len());
+    ///     })
+    ///     .connect("https://rpc.testnet.near.org");
+    /// ```
+    pub fn on_request<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&str, &[u8]) + Send + Sync + 'static,
+    {
+        self.hooks.on_request = Some(Arc::new(hook));
+        self
+    }
+
+    /// Invoke `hook` with the method name and raw body of every response, before it's parsed.
+    ///
+    /// See [`on_request`](Self::on_request) for an example.
+    pub fn on_response<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&str, &[u8]) + Send + Sync + 'static,
+    {
+        self.hooks.on_response = Some(Arc::new(hook));
+        self
+    }
+
+    /// Cache the results of method calls.
+    ///
+    /// See the [`cache`] module documentation for more information.
+    pub fn cache(mut self, cache: cache::ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Fall back to equivalent methods on nodes that don't support the called one, disabled by default.
+    ///
+    /// Nodes at different versions may only support either the experimental or the stabilized name
+    /// of a method, e.g. `EXPERIMENTAL_tx_status` and `tx`. With this enabled, calls rejected by
+    /// the node as an unknown method are resent under the other name, which is then used for all
+    /// subsequent calls to that node.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::JsonRpcClient;
+    ///
+    /// let client = JsonRpcClient::new_client()
+    ///     .method_fallback(true)
+    ///     .connect("https://rpc.testnet.near.org");
+    /// ```
+    pub fn method_fallback(mut self, enabled: bool) -> Self {
+        self.method_fallback = enabled;
+        self
+    }
+}
+
+/// Builder for a [`JsonRpcClientConnector`] with a tuned web client.
+///
+/// See [`JsonRpcClient::builder`].
+#[derive(Debug)]
+pub struct JsonRpcClientConnectorBuilder {
+    config: transport::Config,
+}
+
+impl JsonRpcClientConnectorBuilder {
+    /// Set the maximum number of idle connections kept alive per host.
+    ///
+    /// High-throughput clients should raise this to avoid reconnecting under load.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.config.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set how long an idle connection is kept alive in the pool, `None` disables the timeout.
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.config.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the interval of TCP keep-alive probes on open connections, `None` disables them.
+    pub fn tcp_keepalive(mut self, interval: Option<Duration>) -> Self {
+        self.config.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Set a timeout for establishing new connections.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Trust an additional root certificate, e.g. the CA of a private RPC gateway.
+    ///
+    /// See the [`tls`] module for an example.
+    pub fn add_root_certificate(mut self, cert: tls::Certificate) -> Self {
+        self.config.root_certificates.push(cert);
+        self
+    }
+
+    /// Set the client certificate presented to servers that require mutual TLS.
+    ///
+    /// See the [`tls`] module for an example.
+    pub fn identity(mut self, identity: tls::Identity) -> Self {
+        self.config.identity = Some(identity);
+        self
+    }
+
+    /// Set the `User-Agent` header sent with every request.
+    ///
+    /// Defaults to the name and version of this crate, e.g. `near-jsonrpc-client/x.y.z`.
+    /// RPC providers rely on identifiable user agents for per-app quotas and abuse investigations.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::{header::HeaderValue, JsonRpcClient};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = JsonRpcClient::builder()
+    ///     .user_agent(HeaderValue::from_static("my-indexer/1.2.0"))
+    ///     .build()?
+    ///     .connect("https://rpc.testnet.near.org");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn user_agent(mut self, user_agent: header::HeaderValue) -> Self {
+        self.config.user_agent = Some(user_agent);
+        self
+    }
+
+    /// Route requests through a proxy, this can be called multiple times to add more proxies.
+    ///
+    /// See the [`proxy`] module for an example.
+    pub fn proxy(mut self, proxy: proxy::Proxy) -> Self {
+        self.config.proxies.push(proxy);
+        self
+    }
+
+    /// Use the minimal transport built directly on `hyper` instead of `reqwest`.
+    ///
+    /// This is the only transport available when the crate is built with the `hyper-transport`
+    /// feature and without `native-tls` or `rustls-tls`. HTTPS support requires the `hyper-tls` feature.
+    #[cfg(feature = "hyper-transport")]
+    pub fn hyper(mut self) -> Self {
+        self.config.backend = transport::Backend::Hyper;
+        self
+    }
+
+    /// Build the client connector.
+    pub fn build(self) -> Result<JsonRpcClientConnector, HttpError> {
+        Ok(JsonRpcClient::with_transport(transport::Transport::new(
+            self.config,
+        )?))
+    }
+}
+
+/// Timeouts applied to method calls, keyed by method name.
+#[derive(Clone, Debug, Default)]
+struct MethodTimeouts {
+    default: Option<Duration>,
+    methods: HashMap<String, Duration>,
+}
+
+impl MethodTimeouts {
+    fn get(&self, method_name: &str) -> Option<Duration> {
+        self.methods.get(method_name).copied().or(self.default)
+    }
 }
 
 struct JsonRpcInnerClient {
     server_addr: String,
-    client: reqwest::Client,
+    server_url: Result<url::Url, url::ParseError>,
+    client: transport::Transport,
+    timeouts: MethodTimeouts,
+    retry_policy: retry::RetryPolicy,
+    retry_budget: Option<retry::RetryBudget>,
+    throttle: Option<throttle::Throttle>,
+    stats: stats::StatsRecorder,
+    cache: Option<cache::ResponseCache>,
+    signer: Option<signing::HmacSigner>,
+    max_response_size: Option<usize>,
+    request_ids: Option<RequestIdGenerator>,
+    correlation_header: Option<header::HeaderName>,
+    slow_calls: Option<stats::SlowCallWatch>,
+    method_fallback: bool,
+    /// Methods the server rejected as unknown, which are sent under their fallback names instead.
+    unsupported_methods: Mutex<HashSet<String>>,
+    /// The chain id of the server, once known.
+    chain_id: Mutex<Option<Arc<str>>>,
+    hooks: Hooks,
+}
+
+/// State collected while exchanging a single request and response with the server.
+#[derive(Default)]
+struct Exchange {
+    usage: stats::MethodStats,
+    /// Whether the raw result of a successful call should be kept.
+    keep_result: bool,
+    result: Option<serde_json::Value>,
+    #[cfg(feature = "opentelemetry")]
+    span: Option<telemetry::RequestSpan>,
+}
+
+/// Returns true if the payload looks like a JSON object or array, regardless of its content type.
+fn is_json(payload: &[u8]) -> bool {
+    payload
+        .iter()
+        .find(|byte| !byte.is_ascii_whitespace())
+        .map_or(false, |byte| matches!(byte, b'{' | b'['))
+}
+
+/// The envelope of a response, borrowing the raw result so it's only parsed once.
+#[derive(serde::Deserialize)]
+struct RawResponse<'a> {
+    jsonrpc: &'a str,
+    #[serde(borrow, default)]
+    result: Option<&'a serde_json::value::RawValue>,
+    #[serde(borrow, default)]
+    error: Option<&'a serde_json::value::RawValue>,
 }
 
 #[derive(Clone)]
@@ -131,167 +684,997 @@ struct JsonRpcInnerClient {
 /// ```
 pub struct JsonRpcClient {
     inner: Arc<JsonRpcInnerClient>,
-    headers: reqwest::header::HeaderMap,
+    /// Shared with the clients derived from this one, until modified.
+    headers: Arc<http::header::HeaderMap>,
+    deadline: Option<Deadline>,
+    request_id: Option<Arc<str>>,
+    sensitive_headers: Arc<Vec<header::HeaderName>>,
+    archival_fallback: Option<Arc<JsonRpcClient>>,
+    expected_chain_id: Option<Arc<str>>,
 }
 
-pub type MethodCallResult<T, E> = Result<T, JsonRpcError<E>>;
+pub type MethodCallResult<T, E> = Result<T, JsonRpcError<E>>;
+
+impl JsonRpcClient {
+    /// Connect to a JSON RPC server using the default connector.
+    ///
+    /// It's virtually the same as calling `new_client().connect(server_addr)`.
+    /// Only, this method optimally reuses the same connector across invocations.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::JsonRpcClient;
+    ///
+    /// let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+    /// ```
+    pub fn connect<U: AsUrl>(server_addr: U) -> JsonRpcClient {
+        DEFAULT_CONNECTOR.connect(server_addr)
+    }
+
+    /// Connect to a JSON RPC server using the default connector, after validating its address.
+    ///
+    /// Unlike [`connect`](JsonRpcClient::connect), which accepts any address and only fails
+    /// once a call is made, this checks upfront that the address is a valid `http` or `https` URL.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::{errors::InvalidServerAddrError, JsonRpcClient};
+    ///
+    /// let client = JsonRpcClient::try_connect("https://rpc.testnet.near.org");
+    /// assert!(client.is_ok());
+    ///
+    /// let client = JsonRpcClient::try_connect("rpc.testnet.near.org");
+    /// assert!(matches!(client, Err(InvalidServerAddrError::InvalidUrl(_))));
+    ///
+    /// let client = JsonRpcClient::try_connect("ws://rpc.testnet.near.org");
+    /// assert!(matches!(client, Err(InvalidServerAddrError::UnsupportedScheme(_))));
+    /// ```
+    pub fn try_connect<U: AsUrl>(server_addr: U) -> Result<JsonRpcClient, InvalidServerAddrError> {
+        DEFAULT_CONNECTOR.try_connect(server_addr)
+    }
+
+    /// Connect to the JSON RPC server configured in the `NEAR_RPC_*` environment variables.
+    ///
+    /// See the [`config`] module for the variables read.
+    pub fn from_env() -> Result<JsonRpcClient, config::ConfigError> {
+        config::ClientConfig::from_env()?.connect()
+    }
+
+    /// Get the server address the client connects to.
+    ///
+    /// It basically returns the server address passed to `connect()`. For providers taking their
+    /// API key in the path of the URL, the key is redacted, see [`server_url`](Self::server_url).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use near_jsonrpc_client::JsonRpcClient;
+    /// let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+    ///
+    /// assert_eq!(client.server_addr(), "https://rpc.testnet.near.org");
+    /// ```
+    pub fn server_addr(&self) -> &str {
+        &self.inner.server_addr
+    }
+
+    /// Get the parsed URL of the server the client connects to.
+    ///
+    /// Unlike [`server_addr`](Self::server_addr), it includes any API key in the path of the URL.
+    ///
+    /// Returns `None` if the server address passed to `connect()` isn't a valid URL,
+    /// in which case all method calls fail (See [`try_connect`](JsonRpcClient::try_connect)).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use near_jsonrpc_client::JsonRpcClient;
+    /// let client = JsonRpcClient::connect(("localhost", 3030));
+    ///
+    /// let url = client.server_url().unwrap();
+    /// assert_eq!(url.host_str(), Some("localhost"));
+    /// assert_eq!(url.port(), Some(3030));
+    /// ```
+    pub fn server_url(&self) -> Option<&url::Url> {
+        self.inner.server_url.as_ref().ok()
+    }
+
+    /// RPC method executor for the client.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::{methods, JsonRpcClient};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+    ///
+    /// let request = methods::status::RpcStatusRequest;
+    /// let response = client.call(request).await?;
+    ///
+    /// assert!(matches!(
+    ///     response,
+    ///     methods::status::RpcStatusResponse { .. }
+    /// ));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn call<M>(&self, method: M) -> MethodCallResult<M::Response, M::Error>
+    where
+        M: methods::RpcMethod,
+    {
+        self.call_with_headers(method)
+            .await
+            .map(|(response, _)| response)
+    }
+
+    /// RPC method executor that also returns the headers of the server response.
+    ///
+    /// This is useful for inspecting provider-specific headers like `x-ratelimit-remaining`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::{methods, JsonRpcClient};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+    ///
+    /// let request = methods::status::RpcStatusRequest;
+    /// let (response, headers) = client.call_with_headers(request).await?;
+    ///
+    /// if let Some(remaining) = headers.get("x-ratelimit-remaining") {
+    ///     println!("requests remaining: {:?}", remaining);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn call_with_headers<M>(
+        &self,
+        method: M,
+    ) -> MethodCallResult<(M::Response, http::header::HeaderMap), M::Error>
+    where
+        M: methods::RpcMethod,
+    {
+        if let Some(expected) = &self.expected_chain_id {
+            if methods::submits_transaction(method.method_name()) {
+                self.verify_chain_id(expected).await?;
+            }
+        }
+
+        let started = std::time::Instant::now();
+        let result = match self.execute(&method).await {
+            Err(err) => self.check_garbage_collected(&method, err).await,
+            result => result,
+        };
+        if let Some(slow_calls) = &self.inner.slow_calls {
+            slow_calls.observe(
+                method.method_name(),
+                &self.inner.server_addr,
+                started.elapsed(),
+            );
+        }
+        result
+    }
+
+    async fn execute<M>(
+        &self,
+        method: &M,
+    ) -> MethodCallResult<(M::Response, http::header::HeaderMap), M::Error>
+    where
+        M: methods::RpcMethod,
+    {
+        let cache_entry = self.inner.cache.as_ref().and_then(|cache| {
+            cache
+                .entry(&self.inner.server_addr, method)
+                .map(|(key, ttl)| (cache, key, ttl))
+        });
+        if let Some((cache, key, _)) = &cache_entry {
+            if let Some(Ok(Ok(response))) = cache.get(key).map(M::parse_handler_response) {
+                log::debug!("cache hit for {}", method.method_name());
+                return Ok((response, http::header::HeaderMap::new()));
+            }
+        }
+
+        let policy = &self.inner.retry_policy;
+        let budget = self.inner.retry_budget.as_ref();
+
+        if let Some(budget) = budget {
+            budget.deposit();
+        }
+
+        let mut retries = 0;
+        loop {
+            let mut exchange = Exchange {
+                keep_result: cache_entry.is_some(),
+                ..Exchange::default()
+            };
+            let err = match self.send_or_fall_back(method, &mut exchange).await {
+                Err(err)
+                    if retries < policy.max_retries()
+                        && retry::is_retryable(&err, method.is_idempotent()) =>
+                {
+                    err
+                }
+                result => {
+                    if let (Some((cache, key, ttl)), Some(value)) = (&cache_entry, exchange.result)
+                    {
+                        cache.insert(key, value, *ttl);
+                    }
+                    return result;
+                }
+            };
+            retries += 1;
+            let backoff = policy.backoff(retries);
+            if self
+                .deadline
+                .map_or(false, |deadline| deadline.budget(None) <= Some(backoff))
+            {
+                return Err(err);
+            }
+            if !budget.map_or(true, |budget| budget.try_withdraw()) {
+                log::debug!("retry budget exhausted, not retrying");
+                return Err(err);
+            }
+            log::debug!("retrying method call in {:?} (retry {})", backoff, retries);
+            tokio::time::sleep(backoff).await;
+        }
+    }
+
+    async fn verify_chain_id<E>(&self, expected: &str) -> Result<(), JsonRpcError<E>> {
+        let actual = self.chain_id().await.map_err(|err| {
+            JsonRpcError::TransportError(RpcTransportError::ChainIdUnverified(Box::new(err.into())))
+        })?;
+        if &*actual != expected {
+            return Err(JsonRpcError::TransportError(
+                RpcTransportError::WrongNetwork {
+                    expected: expected.to_string(),
+                    actual: actual.to_string(),
+                },
+            ));
+        }
+        Ok(())
+    }
+
+    /// The chain id of the server, fetched with the `status` method the first time it's needed.
+    async fn chain_id(&self) -> MethodCallResult<Arc<str>, methods::status::RpcStatusError> {
+        if let Some(chain_id) = self.inner.chain_id.lock().unwrap().clone() {
+            return Ok(chain_id);
+        }
+        let (status, _) = self.execute(&methods::status::RpcStatusRequest).await?;
+        Ok(self.observe_chain_id(&status.chain_id))
+    }
+
+    fn observe_chain_id(&self, chain_id: &str) -> Arc<str> {
+        let chain_id = Arc::<str>::from(chain_id);
+        *self.inner.chain_id.lock().unwrap() = Some(chain_id.clone());
+        chain_id
+    }
+
+    /// Distinguishes unknown blocks that were garbage collected by the node, retrying the call
+    /// against the archival fallback if there's one.
+    async fn check_garbage_collected<M>(
+        &self,
+        method: &M,
+        err: JsonRpcError<M::Error>,
+    ) -> MethodCallResult<(M::Response, http::header::HeaderMap), M::Error>
+    where
+        M: methods::RpcMethod,
+    {
+        use methods::RpcHandlerError;
+
+        let (block_height, error) = match (method.block_height(), err) {
+            (
+                Some(block_height),
+                JsonRpcError::ServerError(JsonRpcServerError::HandlerError(error)),
+            ) if error.is_unknown_block() => (block_height, error),
+            (_, err) => return Err(err),
+        };
+        let garbage_collected = match self.execute(&methods::status::RpcStatusRequest).await {
+            Ok((status, _)) => match status.sync_info.earliest_block_height {
+                Some(earliest_block_height) => block_height < earliest_block_height,
+                None => {
+                    status
+                        .sync_info
+                        .latest_block_height
+                        .saturating_sub(block_height)
+                        > capabilities::GC_WINDOW
+                }
+            },
+            Err(_) => false,
+        };
+        if !garbage_collected {
+            return Err(JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+                error,
+            )));
+        }
+        if let Some(archival) = &self.archival_fallback {
+            log::debug!(
+                "block #{} was garbage collected, retrying {} against {}",
+                block_height,
+                method.method_name(),
+                archival.server_addr()
+            );
+            return archival.execute(method).await;
+        }
+        Err(JsonRpcError::ServerError(
+            JsonRpcServerError::BlockGarbageCollected {
+                block_height,
+                error,
+            },
+        ))
+    }
+
+    async fn send_or_fall_back<M>(
+        &self,
+        method: &M,
+        exchange: &mut Exchange,
+    ) -> MethodCallResult<(M::Response, http::header::HeaderMap), M::Error>
+    where
+        M: methods::RpcMethod,
+    {
+        let fallback = method
+            .fallback_method_name()
+            .filter(|_| self.inner.method_fallback)
+            .map(|method_name| methods::Renamed {
+                method,
+                method_name,
+            });
+        if let Some(fallback) = &fallback {
+            if self.is_unsupported(method.method_name()) {
+                return self.send(fallback, exchange).await;
+            }
+        }
+
+        match (self.send(method, exchange).await, fallback) {
+            (Err(err), fallback) if err.is_method_not_found() => {
+                // remembered for falling back right away, and for reporting capabilities
+                self.inner
+                    .unsupported_methods
+                    .lock()
+                    .unwrap()
+                    .insert(method.method_name().to_string());
+                let Some(fallback) = fallback else {
+                    return Err(err);
+                };
+                log::debug!(
+                    "{} is not supported by the server, falling back to {}",
+                    method.method_name(),
+                    fallback.method_name
+                );
+                *exchange = Exchange {
+                    keep_result: exchange.keep_result,
+                    ..Exchange::default()
+                };
+                self.send(&fallback, exchange).await
+            }
+            (result, _) => result,
+        }
+    }
+
+    fn is_unsupported(&self, method_name: &str) -> bool {
+        self.inner
+            .unsupported_methods
+            .lock()
+            .unwrap()
+            .contains(method_name)
+    }
+
+    async fn send<M>(
+        &self,
+        method: &M,
+        exchange: &mut Exchange,
+    ) -> MethodCallResult<(M::Response, http::header::HeaderMap), M::Error>
+    where
+        M: methods::RpcMethod,
+    {
+        #[cfg(feature = "opentelemetry")]
+        {
+            exchange.span = Some(telemetry::RequestSpan::start(
+                method.method_name(),
+                &self.inner.server_addr,
+            ));
+        }
+        let result = self.send_once(method, exchange).await;
+        #[cfg(feature = "opentelemetry")]
+        if let Some(span) = exchange.span.take() {
+            span.end(&result);
+        }
+        exchange.usage.errors = result.is_err() as u64;
+        self.inner
+            .stats
+            .record(method.method_name(), &exchange.usage);
+        result
+    }
+
+    async fn send_once<M>(
+        &self,
+        method: &M,
+        exchange: &mut Exchange,
+    ) -> MethodCallResult<(M::Response, http::header::HeaderMap), M::Error>
+    where
+        M: methods::RpcMethod,
+    {
+        if let Some(delay) = self.inner.throttle.as_ref().and_then(|t| t.delay()) {
+            if self
+                .deadline
+                .map_or(false, |deadline| deadline.budget(None) <= Some(delay))
+            {
+                return Err(JsonRpcError::TransportError(
+                    RpcTransportError::DeadlineExceeded,
+                ));
+            }
+            log::debug!("throttling method call for {:?}", delay);
+            tokio::time::sleep(delay).await;
+        }
+
+        let timeout = match self.deadline {
+            Some(deadline) => Some(
+                deadline
+                    .budget(self.inner.timeouts.get(method.method_name()))
+                    .ok_or(JsonRpcError::TransportError(
+                        RpcTransportError::DeadlineExceeded,
+                    ))?,
+            ),
+            None => self.inner.timeouts.get(method.method_name()),
+        };
+        let timed_out = |err: &HttpError| {
+            err.is_timeout()
+                && self
+                    .deadline
+                    .map_or(false, |deadline| deadline.has_elapsed())
+        };
+
+        let request_id = match (&self.request_id, &self.inner.request_ids) {
+            (Some(id), _) => id.to_string(),
+            (None, Some(generate)) => generate(),
+            (None, None) => methods::generate_request_id(),
+        };
+
+        let mut request_payload = Vec::new();
+        methods::to_writer_with_id(method, &request_id, &mut request_payload).map_err(|err| {
+            JsonRpcError::TransportError(RpcTransportError::SendError(
+                JsonRpcTransportSendError::PayloadSerializeError(err),
+            ))
+        })?;
+
+        log::debug!(
+            "request payload: {}",
+            String::from_utf8_lossy(&request_payload)
+        );
+        log::debug!("request headers: {:#?}", self.redacted(&self.headers));
+        if let Some(hook) = &self.inner.hooks.on_request {
+            hook(method.method_name(), &request_payload);
+        }
+
+        exchange.usage.requests = 1;
+        exchange.usage.bytes_sent = request_payload.len() as u64;
+
+        // the web client takes ownership of the request headers,
+        // so this is the only copy of them made for every request
+        let mut headers = http::header::HeaderMap::clone(&self.headers);
+        if let Some(name) = &self.inner.correlation_header {
+            match header::HeaderValue::from_str(&request_id) {
+                Ok(value) => {
+                    headers.insert(name, value);
+                }
+                Err(_) => log::warn!("request id {:?} is not a valid header value", request_id),
+            }
+        }
+        #[cfg(feature = "opentelemetry")]
+        if let Some(span) = &exchange.span {
+            span.inject(&mut headers);
+        }
+        if let Some(signer) = &self.inner.signer {
+            let (name, signature) = signer.signature_header(&request_payload);
+            headers.insert(name, signature);
+        }
+
+        let mut response = self
+            .inner
+            .client
+            .post(&self.inner.server_url, headers, request_payload, timeout)
+            .await
+            .map_err(|err| {
+                if timed_out(&err) {
+                    return JsonRpcError::TransportError(RpcTransportError::DeadlineExceeded);
+                }
+                JsonRpcError::TransportError(RpcTransportError::SendError(
+                    JsonRpcTransportSendError::PayloadSendError(
+                        err.with_context(method.method_name(), &self.inner.server_addr),
+                    ),
+                ))
+            })?;
+        log::debug!("response headers: {:#?}", self.redacted(&response.headers));
+        if let Some(throttle) = &self.inner.throttle {
+            throttle.observe(response.status, &response.headers);
+        }
+        match response.status {
+            http::StatusCode::OK => {}
+            non_ok_status => {
+                return Err(JsonRpcError::ServerError(match non_ok_status {
+                    http::StatusCode::UNAUTHORIZED => JsonRpcServerError::ResponseStatusError(
+                        JsonRpcServerResponseStatusError::Unauthorized,
+                    ),
+                    http::StatusCode::TOO_MANY_REQUESTS => JsonRpcServerError::ResponseStatusError(
+                        JsonRpcServerResponseStatusError::TooManyRequests {
+                            rate_limit: throttle::rate_limit(&response.headers),
+                            server_addr: self.inner.server_addr.clone(),
+                        },
+                    ),
+                    http::StatusCode::BAD_REQUEST => JsonRpcServerError::ResponseStatusError(
+                        JsonRpcServerResponseStatusError::BadRequest,
+                    ),
+                    http::StatusCode::INTERNAL_SERVER_ERROR => JsonRpcServerError::InternalError {
+                        info: Some(String::from("Internal server error")),
+                        error: None,
+                    },
+                    http::StatusCode::SERVICE_UNAVAILABLE => {
+                        JsonRpcServerError::ResponseStatusError(
+                            JsonRpcServerResponseStatusError::ServiceUnavailable,
+                        )
+                    }
+                    http::StatusCode::REQUEST_TIMEOUT => JsonRpcServerError::ResponseStatusError(
+                        JsonRpcServerResponseStatusError::TimeoutError,
+                    ),
+                    http::StatusCode::BAD_GATEWAY => JsonRpcServerError::ResponseStatusError(
+                        JsonRpcServerResponseStatusError::BadGateway {
+                            body: RawBody::new(&response.prefix(RawBody::LIMIT).await),
+                        },
+                    ),
+                    http::StatusCode::GATEWAY_TIMEOUT => JsonRpcServerError::ResponseStatusError(
+                        JsonRpcServerResponseStatusError::GatewayTimeout {
+                            body: RawBody::new(&response.prefix(RawBody::LIMIT).await),
+                        },
+                    ),
+                    unexpected => JsonRpcServerError::ResponseStatusError(
+                        JsonRpcServerResponseStatusError::Unexpected {
+                            status: unexpected,
+                            body: RawBody::new(&response.prefix(RawBody::LIMIT).await),
+                        },
+                    ),
+                }));
+            }
+        }
+        let response_headers = std::mem::take(&mut response.headers);
+        let max_response_size = self.inner.max_response_size;
+        let response_payload = response.bytes(max_response_size).await.map_err(|err| {
+            JsonRpcError::TransportError(match err {
+                transport::ReadError::Http(err) if timed_out(&err) => {
+                    RpcTransportError::DeadlineExceeded
+                }
+                transport::ReadError::Http(err) => {
+                    RpcTransportError::RecvError(JsonRpcTransportRecvError::PayloadRecvError(
+                        err.with_context(method.method_name(), &self.inner.server_addr),
+                    ))
+                }
+                transport::ReadError::TooLarge => {
+                    RpcTransportError::RecvError(JsonRpcTransportRecvError::ResponseTooLarge {
+                        limit: max_response_size.unwrap_or(usize::MAX),
+                    })
+                }
+            })
+        })?;
+        exchange.usage.bytes_received = response_payload.len() as u64;
+        if let Some(hook) = &self.inner.hooks.on_response {
+            hook(method.method_name(), &response_payload);
+        }
+        log::debug!(
+            "response payload: {}",
+            String::from_utf8_lossy(&response_payload)
+        );
+
+        let parse_error = |error, body: &[u8]| {
+            JsonRpcError::TransportError(RpcTransportError::RecvError(
+                JsonRpcTransportRecvError::ResponseParseError(
+                    JsonRpcTransportHandlerResponseError::ResultParseError {
+                        error,
+                        body: RawBody::new(body),
+                    },
+                ),
+            ))
+        };
+
+        // successful responses are parsed straight into the method's response type, everything
+        // else goes through the complete message parser for accurate errors
+        if let Ok(RawResponse {
+            jsonrpc: "2.0",
+            result: Some(result),
+            error: None,
+        }) = serde_json::from_slice(&response_payload)
+        {
+            let raw_result = match exchange.keep_result {
+                true => serde_json::from_str(result.get()).ok(),
+                false => None,
+            };
+            let response = M::parse_raw_handler_response(result)
+                .map_err(|err| parse_error(err, &response_payload))?;
+            if response.is_ok() {
+                exchange.result = raw_result;
+            }
+            return response
+                .map(|response| (response, response_headers))
+                .map_err(|err| JsonRpcError::ServerError(JsonRpcServerError::HandlerError(err)));
+        }
+
+        let response_message = near_jsonrpc_primitives::message::decoded_to_parsed(
+            serde_json::from_slice::<serde_json::Value>(&response_payload)
+                .and_then(serde_json::from_value),
+        )
+        .map_err(|error| {
+            let body = RawBody::new(&response_payload);
+            JsonRpcError::TransportError(RpcTransportError::RecvError(
+                match is_json(&response_payload) {
+                    true => JsonRpcTransportRecvError::PayloadParseError { error, body },
+                    false => JsonRpcTransportRecvError::NonJsonResponse {
+                        content_type: response_headers
+                            .get(http::header::CONTENT_TYPE)
+                            .and_then(|value| value.to_str().ok())
+                            .map(str::to_string),
+                        body,
+                    },
+                },
+            ))
+        })?;
+
+        if let near_jsonrpc_primitives::message::Message::Response(response) = response_message {
+            let result = response.result?;
+            let raw_result = exchange.keep_result.then(|| result.clone());
+            let response = M::parse_handler_response(result)
+                .map_err(|err| parse_error(err, &response_payload))?;
+            if response.is_ok() {
+                exchange.result = raw_result;
+            }
+            return response
+                .map(|response| (response, response_headers))
+                .map_err(|err| JsonRpcError::ServerError(JsonRpcServerError::HandlerError(err)));
+        }
+        Err(JsonRpcError::TransportError(RpcTransportError::RecvError(
+            JsonRpcTransportRecvError::UnexpectedServerResponse(response_message),
+        )))
+    }
+
+    /// RPC method executor that aborts the call as soon as the cancellation token is triggered.
+    ///
+    /// Unlike dropping the future returned by [`call`](JsonRpcClient::call), this lets you
+    /// cancel slow requests deterministically from anywhere the token is shared, resolving the
+    /// call with a [`Cancelled`](RpcTransportError::Cancelled) error.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::{errors::{JsonRpcError, RpcTransportError}, methods, CancellationToken, JsonRpcClient};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+    ///
+    /// let token = CancellationToken::new();
+    /// token.cancel();
+    ///
+    /// let response = client
+    ///     .call_with_cancellation(methods::status::RpcStatusRequest, &token)
+    ///     .await;
+    ///
+    /// assert!(matches!(
+    ///     response,
+    ///     Err(JsonRpcError::TransportError(RpcTransportError::Cancelled))
+    /// ));
+    /// # }
+    /// ```
+    pub async fn call_with_cancellation<M>(
+        &self,
+        method: M,
+        token: &CancellationToken,
+    ) -> MethodCallResult<M::Response, M::Error>
+    where
+        M: methods::RpcMethod,
+    {
+        match token.run_until_cancelled(self.call(method)).await {
+            Some(result) => result,
+            None => {
+                log::debug!("method call cancelled");
+                Err(JsonRpcError::TransportError(RpcTransportError::Cancelled))
+            }
+        }
+    }
+
+    /// RPC method executor that falls back to raw JSON for responses and handler errors that
+    /// fail to parse.
+    ///
+    /// Useful to keep working with nodes that return enum variants newer than the ones this
+    /// crate knows of. See [`tolerant`](methods::tolerant) for details.
+    pub async fn call_tolerant<M>(
+        &self,
+        method: M,
+    ) -> MethodCallResult<methods::Tolerant<M::Response>, methods::Tolerant<M::Error>>
+    where
+        M: methods::RpcMethod,
+    {
+        self.call(methods::tolerant(method)).await
+    }
+
+    /// Call a method by name, without a typed wrapper.
+    ///
+    /// Useful for endpoints of the node that don't have a typed wrapper in [`methods`] yet. The
+    /// result and the handler error are returned as plain JSON, see [`call_raw_as`](JsonRpcClient::call_raw_as)
+    /// to deserialize them into other types.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::JsonRpcClient;
+    /// use serde_json::json;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+    ///
+    /// let status = client.call_raw("status", json!(null)).await?;
+    ///
+    /// println!("chain: {}", status["chain_id"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn call_raw(
+        &self,
+        method_name: &str,
+        params: serde_json::Value,
+    ) -> MethodCallResult<serde_json::Value, serde_json::Value> {
+        self.call_raw_as(method_name, params).await
+    }
+
+    /// Call a method by name, deserializing its result into `T` and its handler error into `E`.
+    ///
+    /// See the [`any`](methods::any) method for how to implement [`RpcHandlerResponse`](methods::RpcHandlerResponse)
+    /// on custom types.
+    pub async fn call_raw_as<T, E>(
+        &self,
+        method_name: &str,
+        params: serde_json::Value,
+    ) -> MethodCallResult<T, E>
+    where
+        T: methods::RpcHandlerResponse,
+        E: methods::RpcHandlerError,
+    {
+        self.call(methods::any::<Result<T, E>>(method_name, params))
+            .await
+    }
+
+    /// Call an arbitrary method, parsing its response with the parsers of the request.
+    ///
+    /// See [`any_with_parser`](methods::any_with_parser) for an example.
+    pub async fn call_with_parser<T, E>(
+        &self,
+        request: &methods::RpcAnyWithParserRequest<T, E>,
+    ) -> MethodCallResult<T, E> {
+        match self.call(request.untyped()).await {
+            Ok(result) => {
+                let body = serde_json::to_vec(&result).unwrap_or_default();
+                request.parse_result(result).map_err(|error| {
+                    JsonRpcError::TransportError(RpcTransportError::RecvError(
+                        JsonRpcTransportRecvError::ResponseParseError(
+                            JsonRpcTransportHandlerResponseError::ResultParseError {
+                                error,
+                                body: RawBody::new(&body),
+                            },
+                        ),
+                    ))
+                })
+            }
+            Err(err) => Err(err.try_map_handler_error(|err| request.parse_error(err))),
+        }
+    }
 
-impl JsonRpcClient {
-    /// Connect to a JSON RPC server using the default connector.
+    /// Make several queries against the same block, concurrently.
     ///
-    /// It's virtually the same as calling `new_client().connect(server_addr)`.
-    /// Only, this method optimally reuses the same connector across invocations.
+    /// Separate queries made with a [`Finality`](near_primitives::types::Finality) may each be
+    /// answered from a different block as the chain advances. This first resolves
+    /// `block_reference` to a block hash, then pins every query to that block, so all
+    /// responses reflect the same state.
+    ///
+    /// Fails only if the block can't be resolved, the result of each query is reported separately.
     ///
     /// ## Example
     ///
-    /// ```
+    /// ```no_run
     /// use near_jsonrpc_client::JsonRpcClient;
+    /// use near_primitives::{types::{BlockReference, Finality}, views::QueryRequest};
     ///
-    /// let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// let client = JsonRpcClient::connect("https://rpc.mainnet.near.org");
+    ///
+    /// let pinned = client
+    ///     .query_pinned(
+    ///         BlockReference::Finality(Finality::Final),
+    ///         [
+    ///             QueryRequest::ViewAccount { account_id: "alice.near".parse()? },
+    ///             QueryRequest::ViewAccount { account_id: "bob.near".parse()? },
+    ///         ],
+    ///     )
+    ///     .await?;
+    ///
+    /// for response in pinned.responses {
+    ///     assert_eq!(response?.block_hash, pinned.block_hash);
+    /// }
+    /// # Ok(())
+    /// # }
     /// ```
-    pub fn connect<U: AsUrl>(server_addr: U) -> JsonRpcClient {
-        DEFAULT_CONNECTOR.connect(server_addr)
+    pub async fn query_pinned(
+        &self,
+        block_reference: near_primitives::types::BlockReference,
+        requests: impl IntoIterator<Item = near_primitives::views::QueryRequest>,
+    ) -> MethodCallResult<methods::query::RpcPinnedQueryResponses, methods::block::RpcBlockError>
+    {
+        use near_primitives::types::{BlockId, BlockReference};
+
+        let block_hash = match block_reference {
+            BlockReference::BlockId(BlockId::Hash(hash)) => hash,
+            block_reference => {
+                self.call(methods::block::RpcBlockRequest { block_reference })
+                    .await?
+                    .header
+                    .hash
+            }
+        };
+
+        let responses = futures_util::future::join_all(requests.into_iter().map(|request| {
+            self.call(methods::query::RpcQueryRequest {
+                block_reference: BlockReference::BlockId(BlockId::Hash(block_hash)),
+                request,
+            })
+        }))
+        .await;
+
+        Ok(methods::query::RpcPinnedQueryResponses {
+            block_hash,
+            responses,
+        })
     }
 
-    /// Get the server address the client connects to.
+    /// Get the network the server is on.
     ///
-    /// It basically returns the server address passed to `connect()`.
+    /// This calls the `status` method the first time, the chain id of the server is cached from
+    /// then on, and shared with the clients derived from this one.
     ///
     /// ## Example
     ///
-    /// ```
-    /// # use near_jsonrpc_client::JsonRpcClient;
+    /// ```no_run
+    /// use near_jsonrpc_client::{providers::Network, JsonRpcClient};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
     ///
-    /// assert_eq!(client.server_addr(), "https://rpc.testnet.near.org");
+    /// let registrar = match client.network().await? {
+    ///     Network::Mainnet => "near",
+    ///     Network::Testnet => "testnet",
+    ///     network => Err(format!("no top-level account registrar on {}", network))?,
+    /// };
+    /// # Ok(())
+    /// # }
     /// ```
-    pub fn server_addr(&self) -> &str {
-        &self.inner.server_addr
+    pub async fn network(
+        &self,
+    ) -> MethodCallResult<providers::Network, methods::status::RpcStatusError> {
+        self.chain_id()
+            .await
+            .map(|chain_id| providers::Network::from_chain_id(&chain_id))
     }
 
-    /// RPC method executor for the client.
+    /// Get the capabilities of the node, like its version and whether it's archival.
+    ///
+    /// This calls the `status` method. See the [`capabilities`] module documentation for more
+    /// information.
+    pub async fn capabilities(
+        &self,
+    ) -> MethodCallResult<capabilities::Capabilities, methods::status::RpcStatusError> {
+        let status = self.call(methods::status::RpcStatusRequest).await?;
+        self.observe_chain_id(&status.chain_id);
+        let unsupported_methods = self.inner.unsupported_methods.lock().unwrap().clone();
+        Ok(capabilities::Capabilities::new(status, unsupported_methods))
+    }
+
+    /// Get a snapshot of the per-method usage statistics of this client.
+    ///
+    /// Clones of a client, and clients derived from it (e.g with [`header`](JsonRpcClient::header)), share the same statistics.
+    ///
+    /// See the [`stats`] module documentation for more information.
+    pub fn stats(&self) -> stats::ClientStats {
+        self.inner.stats.snapshot()
+    }
+
+    /// Get the request id used for method calls made by this client, if any.
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    /// Use the specified id for all method calls made by this client.
+    ///
+    /// By default, every request gets a new id from the connector
+    /// (See [`JsonRpcClientConnector::request_ids`]).
+    pub fn with_request_id(mut self, id: impl Into<String>) -> Self {
+        self.request_id = Some(id.into().into());
+        self
+    }
+
+    /// Get the deadline applied to method calls made by this client, if any.
+    pub fn deadline(&self) -> Option<Deadline> {
+        self.deadline
+    }
+
+    /// Set a deadline that all method calls made by this client must complete by.
+    ///
+    /// Each call's timeout is capped by the time left until the deadline, so retries and
+    /// polling helpers that go through this client all budget against the same total time.
+    /// Once the deadline expires, calls return a [`DeadlineExceeded`](RpcTransportError::DeadlineExceeded) error.
     ///
     /// ## Example
     ///
     /// ```
-    /// use near_jsonrpc_client::{methods, JsonRpcClient};
+    /// use std::time::Duration;
+    ///
+    /// use near_jsonrpc_client::{methods, Deadline, JsonRpcClient};
     ///
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
-    ///
-    /// let request = methods::status::RpcStatusRequest;
-    /// let response = client.call(request).await?;
+    /// let client = JsonRpcClient::connect("https://rpc.testnet.near.org")
+    ///     .with_deadline(Deadline::after(Duration::from_secs(30)));
     ///
-    /// assert!(matches!(
-    ///     response,
-    ///     methods::status::RpcStatusResponse { .. }
-    /// ));
+    /// let status = client.call(methods::status::RpcStatusRequest).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn call<M>(&self, method: M) -> MethodCallResult<M::Response, M::Error>
-    where
-        M: methods::RpcMethod,
-    {
-        let request_payload = methods::to_json(&method).map_err(|err| {
-            JsonRpcError::TransportError(RpcTransportError::SendError(
-                JsonRpcTransportSendError::PayloadSerializeError(err),
-            ))
-        })?;
-
-        log::debug!("request payload: {:#}", request_payload);
-        log::debug!("request headers: {:#?}", self.headers());
-
-        let request_payload = serde_json::to_vec(&request_payload).map_err(|err| {
-            JsonRpcError::TransportError(RpcTransportError::SendError(
-                JsonRpcTransportSendError::PayloadSerializeError(err.into()),
-            ))
-        })?;
-
-        let request = self
-            .inner
-            .client
-            .post(&self.inner.server_addr)
-            .headers(self.headers.clone())
-            .body(request_payload);
-
-        let response = request.send().await.map_err(|err| {
-            JsonRpcError::TransportError(RpcTransportError::SendError(
-                JsonRpcTransportSendError::PayloadSendError(err),
-            ))
-        })?;
-        log::debug!("response headers: {:#?}", response.headers());
-        match response.status() {
-            reqwest::StatusCode::OK => {}
-            non_ok_status => {
-                return Err(JsonRpcError::ServerError(match non_ok_status {
-                    reqwest::StatusCode::UNAUTHORIZED => JsonRpcServerError::ResponseStatusError(
-                        JsonRpcServerResponseStatusError::Unauthorized,
-                    ),
-                    reqwest::StatusCode::TOO_MANY_REQUESTS => {
-                        JsonRpcServerError::ResponseStatusError(
-                            JsonRpcServerResponseStatusError::TooManyRequests,
-                        )
-                    }
-                    reqwest::StatusCode::BAD_REQUEST => JsonRpcServerError::ResponseStatusError(
-                        JsonRpcServerResponseStatusError::BadRequest,
-                    ),
-                    reqwest::StatusCode::INTERNAL_SERVER_ERROR => {
-                        JsonRpcServerError::InternalError {
-                            info: Some(String::from("Internal server error")),
-                        }
-                    }
-                    reqwest::StatusCode::SERVICE_UNAVAILABLE => {
-                        JsonRpcServerError::ResponseStatusError(
-                            JsonRpcServerResponseStatusError::ServiceUnavailable,
-                        )
-                    }
-                    reqwest::StatusCode::REQUEST_TIMEOUT => {
-                        JsonRpcServerError::ResponseStatusError(
-                            JsonRpcServerResponseStatusError::TimeoutError,
-                        )
-                    }
-                    unexpected => JsonRpcServerError::ResponseStatusError(
-                        JsonRpcServerResponseStatusError::Unexpected { status: unexpected },
-                    ),
-                }));
-            }
-        }
-        let response_payload = response.bytes().await.map_err(|err| {
-            JsonRpcError::TransportError(RpcTransportError::RecvError(
-                JsonRpcTransportRecvError::PayloadRecvError(err),
-            ))
-        })?;
-        let response_payload = serde_json::from_slice::<serde_json::Value>(&response_payload);
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
 
-        if let Ok(ref response_payload) = response_payload {
-            log::debug!("response payload: {:#}", response_payload);
-        }
+    /// Refuse to send transactions to servers on another network than `chain_id`, e.g. `mainnet`.
+    ///
+    /// The chain id of the server is fetched with the `status` method before the first
+    /// transaction is sent, and cached from then on. Transactions sent to a server on another
+    /// network fail with a [`WrongNetwork`](RpcTransportError::WrongNetwork) error instead.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::{JsonRpcClient, NEAR_MAINNET_RPC_URL};
+    ///
+    /// let client = JsonRpcClient::connect(NEAR_MAINNET_RPC_URL).with_expected_chain_id("mainnet");
+    /// ```
+    pub fn with_expected_chain_id(mut self, chain_id: impl Into<String>) -> Self {
+        self.expected_chain_id = Some(Arc::from(chain_id.into()));
+        self
+    }
 
-        let response_message = near_jsonrpc_primitives::message::decoded_to_parsed(
-            response_payload.and_then(serde_json::from_value),
-        )
-        .map_err(|err| {
-            JsonRpcError::TransportError(RpcTransportError::RecvError(
-                JsonRpcTransportRecvError::PayloadParseError(err),
-            ))
-        })?;
+    /// The chain id transactions are restricted to, see [`with_expected_chain_id`](Self::with_expected_chain_id).
+    pub fn expected_chain_id(&self) -> Option<&str> {
+        self.expected_chain_id.as_deref()
+    }
 
-        if let near_jsonrpc_primitives::message::Message::Response(response) = response_message {
-            return M::parse_handler_response(response.result?)
-                .map_err(|err| {
-                    JsonRpcError::TransportError(RpcTransportError::RecvError(
-                        JsonRpcTransportRecvError::ResponseParseError(
-                            JsonRpcTransportHandlerResponseError::ResultParseError(err),
-                        ),
-                    ))
-                })?
-                .map_err(|err| JsonRpcError::ServerError(JsonRpcServerError::HandlerError(err)));
-        }
-        Err(JsonRpcError::TransportError(RpcTransportError::RecvError(
-            JsonRpcTransportRecvError::UnexpectedServerResponse(response_message),
-        )))
+    /// Retry calls for blocks garbage collected by the node against an archival node.
+    ///
+    /// Non-archival nodes only keep the last few epochs of history, and report older blocks as
+    /// unknown. Calls pinned to such blocks, like queries at a past height, fail with a
+    /// [`BlockGarbageCollected`](JsonRpcServerError::BlockGarbageCollected) error, unless an
+    /// archival fallback is set, in which case they're sent to it instead.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::{JsonRpcClient, NEAR_MAINNET_ARCHIVAL_RPC_URL, NEAR_MAINNET_RPC_URL};
+    ///
+    /// let client = JsonRpcClient::connect(NEAR_MAINNET_RPC_URL)
+    ///     .with_archival_fallback(JsonRpcClient::connect(NEAR_MAINNET_ARCHIVAL_RPC_URL));
+    /// ```
+    pub fn with_archival_fallback(mut self, archival: JsonRpcClient) -> Self {
+        self.archival_fallback = Some(Arc::new(archival));
+        self
     }
 
     /// Add a header to this request.
@@ -325,14 +1708,107 @@ impl JsonRpcClient {
         D::apply(self, entry)
     }
 
+    /// Derive a client carrying additional headers.
+    ///
+    /// The derived client shares the connection pool, configuration and statistics of this
+    /// client, so it's cheap to create one per tenant or request stream. Headers already set on
+    /// this client are replaced by any `headers` with the same name.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::{header::{HeaderMap, HeaderValue}, JsonRpcClient};
+    ///
+    /// let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("x-api-key", HeaderValue::from_static("tenant-a-key"));
+    ///
+    /// let tenant_client = client.with_headers(headers);
+    ///
+    /// assert!(client.headers().get("x-api-key").is_none());
+    /// assert_eq!(tenant_client.headers()["x-api-key"], "tenant-a-key");
+    /// ```
+    pub fn with_headers(&self, headers: http::header::HeaderMap) -> Self {
+        let mut client = self.clone();
+        client.headers_mut().extend(headers);
+        client
+    }
+
+    /// Redact the value of the specified header from logs and `Debug` output.
+    ///
+    /// Common credential headers like `Authorization` and `x-api-key` are always redacted
+    /// (See [`header::SENSITIVE_HEADERS`]).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use near_jsonrpc_client::{header::HeaderName, JsonRpcClient};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = JsonRpcClient::connect("https://rpc.testnet.near.org")
+    ///     .header(("x-gateway-token", "secret"))?
+    ///     .sensitive_header(HeaderName::from_static("x-gateway-token"));
+    ///
+    /// assert!(!format!("{:?}", client).contains("secret"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sensitive_header(mut self, name: header::HeaderName) -> Self {
+        Arc::make_mut(&mut self.sensitive_headers).push(name);
+        self
+    }
+
+    fn redacted<'a>(&'a self, headers: &'a http::header::HeaderMap) -> header::Redacted<'a> {
+        header::Redacted {
+            headers,
+            sensitive: &self.sensitive_headers,
+        }
+    }
+
     /// Get a shared reference to the headers.
-    pub fn headers(&self) -> &reqwest::header::HeaderMap {
+    pub fn headers(&self) -> &http::header::HeaderMap {
         &self.headers
     }
 
     /// Get an exclusive reference to the headers.
-    pub fn headers_mut(&mut self) -> &mut reqwest::header::HeaderMap {
-        &mut self.headers
+    pub fn headers_mut(&mut self) -> &mut http::header::HeaderMap {
+        Arc::make_mut(&mut self.headers)
+    }
+
+    /// Get a client for the plain HTTP endpoints of the server, like `/status` and `/metrics`.
+    ///
+    /// See the [`endpoints`] module for details.
+    pub fn http(&self) -> endpoints::HttpClient {
+        endpoints::HttpClient::new(self.clone())
+    }
+
+    /// Get a handle on the account `account_id`.
+    ///
+    /// See the [`account`] module for details.
+    pub fn account(&self, account_id: near_primitives::types::AccountId) -> account::AccountRef {
+        account::AccountRef::new(self.clone(), account_id)
+    }
+
+    /// Start building a transaction signed by `signer`, to `receiver_id`.
+    ///
+    /// See the [`transaction`] module for details.
+    pub fn transaction(
+        &self,
+        signer: impl signer::TransactionSigner + 'static,
+        receiver_id: near_primitives::types::AccountId,
+    ) -> transaction::TransactionBuilder {
+        transaction::TransactionBuilder::new(self.clone(), Arc::new(signer), receiver_id)
+    }
+
+    /// Get a handle on the contract deployed to `contract_id`.
+    ///
+    /// See the [`contract`] module for details.
+    pub fn contract(
+        &self,
+        contract_id: near_primitives::types::AccountId,
+    ) -> contract::ContractRef {
+        contract::ContractRef::new(self.clone(), contract_id)
     }
 
     /// Manually create a new client connector.
@@ -353,18 +1829,36 @@ impl JsonRpcClient {
     /// let testnet_client = client_connector.connect("https://rpc.testnet.near.org");
     /// ```
     pub fn new_client() -> JsonRpcClientConnector {
-        let mut headers = reqwest::header::HeaderMap::with_capacity(2);
-        headers.insert(
-            reqwest::header::CONTENT_TYPE,
-            reqwest::header::HeaderValue::from_static("application/json"),
-        );
+        Self::builder()
+            .build()
+            .expect("failed to initialize the web client")
+    }
 
-        log::debug!("initialized a new JSONRPC client connector");
-        JsonRpcClientConnector {
-            client: reqwest::Client::builder()
-                .default_headers(headers)
-                .build()
-                .unwrap(),
+    /// Create a builder for a new client connector.
+    ///
+    /// This allows tuning the underlying web client, like its connection pool, without losing
+    /// the defaults this crate applies to it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use near_jsonrpc_client::JsonRpcClient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client_connector = JsonRpcClient::builder()
+    ///     .pool_max_idle_per_host(64)
+    ///     .pool_idle_timeout(Some(Duration::from_secs(30)))
+    ///     .build()?;
+    ///
+    /// let client = client_connector.connect("https://rpc.mainnet.near.org");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> JsonRpcClientConnectorBuilder {
+        JsonRpcClientConnectorBuilder {
+            config: transport::Config::default(),
         }
     }
 
@@ -387,8 +1881,28 @@ impl JsonRpcClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "reqwest")]
     pub fn with(client: reqwest::Client) -> JsonRpcClientConnector {
-        JsonRpcClientConnector { client }
+        Self::with_transport(transport::Transport::Reqwest(client))
+    }
+
+    fn with_transport(client: transport::Transport) -> JsonRpcClientConnector {
+        log::debug!("initialized a new JSONRPC client connector");
+        JsonRpcClientConnector {
+            client,
+            timeouts: MethodTimeouts::default(),
+            retry_policy: retry::RetryPolicy::default(),
+            retry_budget: None,
+            throttle: true,
+            cache: None,
+            signer: None,
+            max_response_size: None,
+            request_ids: None,
+            correlation_header: None,
+            slow_calls: None,
+            method_fallback: false,
+            hooks: Hooks::default(),
+        }
     }
 }
 
@@ -396,38 +1910,197 @@ impl fmt::Debug for JsonRpcClient {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut builder = f.debug_struct("JsonRpcClient");
         builder.field("server_addr", &self.inner.server_addr);
-        builder.field("headers", &self.headers);
+        builder.field("headers", &self.redacted(&self.headers));
+        builder.field("deadline", &self.deadline);
+        builder.field("request_id", &self.request_id);
+        builder.field("expected_chain_id", &self.expected_chain_id);
+        builder.field(
+            "archival_fallback",
+            &self
+                .archival_fallback
+                .as_ref()
+                .map(|archival| archival.server_addr()),
+        );
+        builder.field("timeouts", &self.inner.timeouts);
         builder.field("client", &self.inner.client);
         builder.finish()
     }
 }
 
+/// Parse a server address, checking it's an HTTP(S) URL with a host.
+fn parse_server_addr(server_addr: &str) -> Result<url::Url, InvalidServerAddrError> {
+    let url = url::Url::parse(server_addr)?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(InvalidServerAddrError::UnsupportedScheme(
+            url.scheme().to_string(),
+        ));
+    }
+    if !url.has_host() {
+        return Err(InvalidServerAddrError::MissingHost);
+    }
+    Ok(url)
+}
+
 mod private {
-    pub trait Sealed: ToString {}
+    pub trait Sealed {
+        /// The server address, as a URL string.
+        fn to_addr(&self) -> String;
+    }
 }
 
+/// Types that can be used as the address of an RPC server.
+///
+/// Besides URLs and strings, this is implemented for socket addresses and `(host, port)` pairs,
+/// both of which are connected to over plain `http`.
 pub trait AsUrl: private::Sealed {}
 
-impl private::Sealed for String {}
+macro_rules! impl_as_url_for_strings {
+    ($($ty:ty),+) => {
+        $(
+            impl private::Sealed for $ty {
+                fn to_addr(&self) -> String {
+                    self.to_string()
+                }
+            }
+
+            impl AsUrl for $ty {}
+        )+
+    };
+}
+
+impl_as_url_for_strings!(String, &String, &str, std::borrow::Cow<'_, str>, url::Url);
 
-impl AsUrl for String {}
+impl private::Sealed for std::net::SocketAddr {
+    fn to_addr(&self) -> String {
+        format!("http://{}", self)
+    }
+}
 
-impl private::Sealed for &String {}
+impl AsUrl for std::net::SocketAddr {}
 
-impl AsUrl for &String {}
+fn host_port_addr(host: &str, port: u16) -> String {
+    // IPv6 addresses must be bracketed in URLs
+    if host.contains(':') && !host.starts_with('[') {
+        format!("http://[{}]:{}", host, port)
+    } else {
+        format!("http://{}:{}", host, port)
+    }
+}
 
-impl private::Sealed for &str {}
+impl private::Sealed for (&str, u16) {
+    fn to_addr(&self) -> String {
+        host_port_addr(self.0, self.1)
+    }
+}
 
-impl AsUrl for &str {}
+impl AsUrl for (&str, u16) {}
 
-impl private::Sealed for reqwest::Url {}
+impl private::Sealed for (String, u16) {
+    fn to_addr(&self) -> String {
+        host_port_addr(&self.0, self.1)
+    }
+}
 
-impl AsUrl for reqwest::Url {}
+impl AsUrl for (String, u16) {}
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use crate::{methods, JsonRpcClient};
 
+    #[test]
+    fn server_addresses_are_parsed() {
+        let client = JsonRpcClient::connect(std::net::SocketAddr::from(([127, 0, 0, 1], 3030)));
+        assert_eq!(client.server_addr(), "http://127.0.0.1:3030");
+
+        let client = JsonRpcClient::connect(("::1", 3030));
+        assert_eq!(client.server_url().unwrap().as_str(), "http://[::1]:3030/");
+
+        let client = JsonRpcClient::connect(std::borrow::Cow::from("not a url"));
+        assert!(client.server_url().is_none());
+    }
+
+    #[test]
+    fn gateway_pages_are_not_json() {
+        assert!(super::is_json(b" {\"jsonrpc\":\"2.0\"}"));
+        assert!(!super::is_json(
+            b"<!DOCTYPE html><title>Just a moment...</title>"
+        ));
+        assert!(!super::is_json(b""));
+    }
+
+    #[test]
+    fn method_timeouts_override_default() {
+        let connector = JsonRpcClient::new_client()
+            .timeout(Duration::from_secs(10))
+            .method_timeout("send_tx", Duration::from_secs(60));
+
+        assert_eq!(
+            connector.timeouts.get("send_tx"),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            connector.timeouts.get("status"),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(JsonRpcClient::new_client().timeouts.get("status"), None);
+    }
+
+    #[test]
+    fn experimental_methods_fall_back_to_stable() {
+        use methods::RpcMethod;
+
+        let request = methods::EXPERIMENTAL_tx_status::RpcTransactionStatusRequest {
+            transaction_info: methods::EXPERIMENTAL_tx_status::TransactionInfo::TransactionId {
+                tx_hash: Default::default(),
+                sender_account_id: "alice.near".parse().unwrap(),
+            },
+            wait_until: Default::default(),
+        };
+        assert_eq!(request.fallback_method_name(), Some("tx"));
+
+        let request = methods::block_effects::RpcBlockEffectsRequest {
+            block_reference: near_primitives::types::BlockReference::latest(),
+        };
+        assert_eq!(
+            request.fallback_method_name(),
+            Some("EXPERIMENTAL_changes_in_block")
+        );
+        assert_eq!(
+            methods::status::RpcStatusRequest.fallback_method_name(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn transactions_are_pinned_to_the_expected_network() {
+        use near_crypto::{KeyType, Signature};
+        use near_primitives::transaction::{SignedTransaction, Transaction, TransactionV0};
+
+        let client = JsonRpcClient::connect("http://127.0.0.1:1").with_expected_chain_id("mainnet");
+        client.observe_chain_id("testnet");
+
+        let request =
+            methods::broadcast_tx_async::RpcBroadcastTxAsyncRequest::new(SignedTransaction::new(
+                Signature::empty(KeyType::ED25519),
+                Transaction::V0(TransactionV0 {
+                    signer_id: "alice.near".parse().unwrap(),
+                    public_key: near_crypto::PublicKey::empty(KeyType::ED25519),
+                    nonce: 1,
+                    receiver_id: "bob.near".parse().unwrap(),
+                    block_hash: Default::default(),
+                    actions: vec![],
+                }),
+            ));
+        assert!(matches!(
+            client.call(request).await,
+            Err(crate::errors::JsonRpcError::TransportError(
+                crate::errors::RpcTransportError::WrongNetwork { ref expected, ref actual }
+            )) if expected == "mainnet" && actual == "testnet"
+        ));
+    }
+
     #[tokio::test]
     async fn chk_status_testnet() {
         let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
@@ -442,7 +2115,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[cfg(feature = "any")]
     async fn any_typed_ok() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let client = JsonRpcClient::connect("https://archival-rpc.mainnet.near.org");
 
@@ -460,8 +2132,8 @@ mod tests {
             matches!(
                 tx_status,
                 Ok(methods::tx::RpcTransactionResponse { ref final_execution_outcome, .. })
-                if final_execution_outcome.unwrap().into_outcome().transaction.signer_id == "miraclx.near"
-                && final_execution_outcome.unwrap().into_outcome().transaction.hash == "9FtHUFBQsZ2MG77K3x3MJ9wjX3UT8zE1TczCrhZEcG8U".parse()?
+                if final_execution_outcome.clone().unwrap().into_outcome().transaction.signer_id == "miraclx.near"
+                && final_execution_outcome.clone().unwrap().into_outcome().transaction.hash == "9FtHUFBQsZ2MG77K3x3MJ9wjX3UT8zE1TczCrhZEcG8U".parse()?
             ),
             "expected an Ok(RpcTransactionStatusResponse) with matching signer_id + hash, found [{:?}]",
             tx_status
@@ -471,7 +2143,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[cfg(feature = "any")]
     async fn any_typed_err() -> Result<(), Box<dyn std::error::Error>> {
         let client = JsonRpcClient::connect("https://archival-rpc.mainnet.near.org");
 
@@ -502,7 +2173,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[cfg(feature = "any")]
     async fn any_untyped_ok() {
         let client = JsonRpcClient::connect("https://archival-rpc.mainnet.near.org");
 
@@ -532,7 +2202,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[cfg(feature = "any")]
     async fn any_untyped_err() {
         let client = JsonRpcClient::connect("https://archival-rpc.mainnet.near.org");
 
@@ -0,0 +1,159 @@
+//! Logs emitted while executing transactions.
+//!
+//! The logs of a transaction are spread across the outcomes of the receipts it spawned, one per
+//! contract call in a chain of cross-contract calls. [`OutcomeLogs::logs`] flattens them in
+//! execution order, each annotated with the receipt that emitted it and the account it was
+//! executed on.
+//!
+//! [`OutcomeLogs`] is implemented by [`FinalExecutionOutcomeView`],
+//! [`FinalExecutionOutcomeWithReceiptView`] and [`FinalExecutionOutcomeViewEnum`].
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::logs::OutcomeLogs;
+//! use near_jsonrpc_client::{methods, JsonRpcClient};
+//! use near_primitives::views::TxExecutionStatus;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! let client = JsonRpcClient::connect("https://archival-rpc.mainnet.near.org");
+//!
+//! let response = client
+//!     .call(methods::tx::RpcTransactionStatusRequest {
+//!         transaction_info: methods::tx::TransactionInfo::TransactionId {
+//!             tx_hash: "9FtHUFBQsZ2MG77K3x3MJ9wjX3UT8zE1TczCrhZEcG8U".parse()?,
+//!             sender_account_id: "miraclx.near".parse()?,
+//!         },
+//!         wait_until: TxExecutionStatus::Executed,
+//!     })
+//!     .await?;
+//!
+//! if let Some(outcome) = response.final_execution_outcome {
+//!     for log in outcome.logs() {
+//!         println!("{}", log);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+
+use near_primitives::hash::CryptoHash;
+use near_primitives::types::AccountId;
+use near_primitives::views::{
+    FinalExecutionOutcomeView, FinalExecutionOutcomeViewEnum, FinalExecutionOutcomeWithReceiptView,
+};
+
+/// A log, along with the receipt that emitted it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeLog {
+    /// The receipt whose execution emitted the log.
+    pub receipt_id: CryptoHash,
+    /// The account the receipt was executed on.
+    pub executor_id: AccountId,
+    /// The log.
+    pub log: String,
+}
+
+impl fmt::Display for OutcomeLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.executor_id, self.log)
+    }
+}
+
+/// Outcomes of transactions, whose logs can be flattened.
+pub trait OutcomeLogs {
+    /// The logs emitted by the receipts of the transaction, in execution order.
+    ///
+    /// The receipts are ordered as the node lists them, each after the receipt that spawned it.
+    fn logs(&self) -> Vec<OutcomeLog>;
+}
+
+impl OutcomeLogs for FinalExecutionOutcomeView {
+    fn logs(&self) -> Vec<OutcomeLog> {
+        self.receipts_outcome
+            .iter()
+            .flat_map(|receipt| {
+                receipt.outcome.logs.iter().map(|log| OutcomeLog {
+                    receipt_id: receipt.id,
+                    executor_id: receipt.outcome.executor_id.clone(),
+                    log: log.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl OutcomeLogs for FinalExecutionOutcomeWithReceiptView {
+    fn logs(&self) -> Vec<OutcomeLog> {
+        self.final_outcome.logs()
+    }
+}
+
+impl OutcomeLogs for FinalExecutionOutcomeViewEnum {
+    fn logs(&self) -> Vec<OutcomeLog> {
+        match self {
+            Self::FinalExecutionOutcome(outcome) => outcome.logs(),
+            Self::FinalExecutionOutcomeWithReceipt(outcome) => outcome.logs(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn logs_are_flattened_across_receipts() {
+        let outcome = |id: CryptoHash, executor_id: &str, logs: &[&str]| {
+            json!({
+                "id": id,
+                "outcome": {
+                    "logs": logs,
+                    "receipt_ids": [],
+                    "gas_burnt": 0,
+                    "tokens_burnt": "0",
+                    "executor_id": executor_id,
+                    "status": { "SuccessValue": "" },
+                },
+                "block_hash": CryptoHash::default(),
+                "proof": [],
+            })
+        };
+        let first = CryptoHash::hash_bytes(b"first");
+        let second = CryptoHash::hash_bytes(b"second");
+        let outcome: FinalExecutionOutcomeView = serde_json::from_value(json!({
+            "status": { "SuccessValue": "" },
+            "transaction": {
+                "signer_id": "alice.near",
+                "public_key": "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+                "nonce": 1,
+                "receiver_id": "dex.near",
+                "actions": [],
+                "signature": "ed25519:3s1dvZdQtcAjBksMHFrysqvF63wnyMHPA4owNQmCJZ2EBakZEKdtMsLqrHdKWQjJbSRN6kRknN2WdwSBLWGCokXj",
+                "hash": CryptoHash::default(),
+            },
+            "transaction_outcome": outcome(CryptoHash::default(), "alice.near", &[]),
+            "receipts_outcome": [
+                outcome(first, "dex.near", &["swapping"]),
+                outcome(second, "token.near", &["transfer", "done"]),
+            ],
+        }))
+        .unwrap();
+
+        let logs = outcome.logs();
+        assert_eq!(
+            logs.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            [
+                "[dex.near] swapping",
+                "[token.near] transfer",
+                "[token.near] done"
+            ]
+        );
+        assert_eq!(logs[0].receipt_id, first);
+        assert_eq!(logs[2].receipt_id, second);
+    }
+}
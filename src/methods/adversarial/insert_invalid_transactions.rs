@@ -0,0 +1,25 @@
+use super::*;
+
+#[derive(Debug)]
+pub struct RpcAdversarialInsertInvalidTransactionsRequest {
+    pub enabled: bool,
+}
+
+impl RpcMethod for RpcAdversarialInsertInvalidTransactionsRequest {
+    type Response = ();
+    type Error = ();
+
+    fn method_name(&self) -> &str {
+        "adv_insert_invalid_transactions"
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        Ok(json!(self.enabled))
+    }
+
+    fn is_idempotent(&self) -> bool {
+        false
+    }
+}
+
+impl private::Sealed for RpcAdversarialInsertInvalidTransactionsRequest {}
@@ -12,9 +12,15 @@ pub use disable_header_sync as adv_disable_header_sync;
 pub mod get_saved_blocks;
 pub use get_saved_blocks as adv_get_saved_blocks;
 
+pub mod insert_invalid_transactions;
+pub use insert_invalid_transactions as adv_insert_invalid_transactions;
+
 pub mod produce_blocks;
 pub use produce_blocks as adv_produce_blocks;
 
+pub mod produce_chunks;
+pub use produce_chunks as adv_produce_chunks;
+
 pub mod set_weight;
 pub use set_weight as adv_set_weight;
 
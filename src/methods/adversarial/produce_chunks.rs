@@ -0,0 +1,34 @@
+use super::*;
+
+/// How the node should produce chunks.
+#[derive(Eq, Copy, Clone, Debug, PartialEq, serde::Serialize)]
+pub enum AdvProduceChunksMode {
+    /// Produce valid chunks, as usual.
+    Valid,
+    /// Stop producing chunks.
+    StopProduce,
+}
+
+#[derive(Debug)]
+pub struct RpcAdversarialProduceChunksRequest {
+    pub mode: AdvProduceChunksMode,
+}
+
+impl RpcMethod for RpcAdversarialProduceChunksRequest {
+    type Response = ();
+    type Error = ();
+
+    fn method_name(&self) -> &str {
+        "adv_produce_chunks"
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        Ok(json!(self.mode))
+    }
+
+    fn is_idempotent(&self) -> bool {
+        false
+    }
+}
+
+impl private::Sealed for RpcAdversarialProduceChunksRequest {}
@@ -16,6 +16,10 @@ impl RpcMethod for RpcAdversarialSetWeightRequest {
     fn params(&self) -> Result<serde_json::Value, io::Error> {
         Ok(json!(self.height))
     }
+
+    fn is_idempotent(&self) -> bool {
+        false
+    }
 }
 
 impl private::Sealed for RpcAdversarialSetWeightRequest {}
@@ -1,12 +1,10 @@
 //! For all intents and purposes, the predefined structures in `methods` should suffice, if you find that they
-//! don't or you crave extra flexibility, well, you can opt in to use the generic constructor `methods::any()` with the `any` feature flag.
+//! don't or you crave extra flexibility, well, you can use the generic constructor `methods::any()`.
 //!
-//! In this example, we retrieve only the parts from the genesis config response that we care about.
+//! For one-off calls, [`JsonRpcClient::call_raw`](crate::JsonRpcClient::call_raw) is a shorthand that
+//! takes the method name and parameters directly.
 //!
-//! ```toml
-//! # in Cargo.toml
-//! near-jsonrpc-client = { ..., features = ["any"] }
-//! ```
+//! In this example, we retrieve only the parts from the genesis config response that we care about.
 //!
 //! ```
 //! use serde::Deserialize;
@@ -14,7 +12,7 @@
 //!
 //! # use near_jsonrpc_client::errors::JsonRpcError;
 //! use near_jsonrpc_client::{methods, JsonRpcClient};
-//! use near_primitives::serialize::u128_dec_format;
+//! use near_primitives::serialize::dec_format;
 //! use near_primitives::types::*;
 //!
 //! #[derive(Debug, Deserialize)]
@@ -23,11 +21,11 @@
 //!     chain_id: String,
 //!     genesis_height: BlockHeight,
 //!     epoch_length: BlockHeightDelta,
-//!     #[serde(with = "u128_dec_format")]
+//!     #[serde(with = "dec_format")]
 //!     min_gas_price: Balance,
-//!     #[serde(with = "u128_dec_format")]
+//!     #[serde(with = "dec_format")]
 //!     max_gas_price: Balance,
-//!     #[serde(with = "u128_dec_format")]
+//!     #[serde(with = "dec_format")]
 //!     total_supply: Balance,
 //!     validators: Vec<AccountInfo>,
 //! }
@@ -35,11 +33,10 @@
 //! impl methods::RpcHandlerResponse for PartialGenesisConfig {}
 //!
 //! # #[tokio::main]
-//! # async fn main() -> Result<(), JsonRpcError<()>> {
+//! # async fn main() -> Result<(), JsonRpcError<serde_json::Value>> {
 //! let client = JsonRpcClient::connect("https://rpc.mainnet.near.org");
 //!
-//! # #[cfg(feature = "any")] {
-//! let genesis_config_request = methods::any::<Result<PartialGenesisConfig, ()>>(
+//! let genesis_config_request = methods::any::<Result<PartialGenesisConfig, serde_json::Value>>(
 //!     "EXPERIMENTAL_genesis_config",
 //!     json!(null),
 //! );
@@ -47,7 +44,6 @@
 //! let partial_genesis = client.call(genesis_config_request).await?;
 //!
 //! println!("{:#?}", partial_genesis);
-//! # }
 //! # Ok(())
 //! # }
 //! ```
@@ -70,6 +66,82 @@ where
     }
 }
 
+/// Create a request for an arbitrary method, with custom parsers for its result and handler error.
+///
+/// Unlike [`any`](crate::methods::any), the response types don't need to implement
+/// [`RpcHandlerResponse`] or [`RpcHandlerError`], which helps with exotic or legacy response
+/// shapes. Send it with [`JsonRpcClient::call_with_parser`](crate::JsonRpcClient::call_with_parser).
+///
+/// ## Example
+///
+/// ```
+/// use near_jsonrpc_client::{methods, JsonRpcClient};
+/// use serde_json::json;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+///
+/// let request = methods::any_with_parser(
+///     "status",
+///     json!(null),
+///     |result| Ok(result["chain_id"].as_str().unwrap_or_default().to_string()),
+///     |error| Ok(error.to_string()),
+/// );
+///
+/// let chain_id: String = client.call_with_parser(&request).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn request_with_parser<T, E>(
+    method_name: &str,
+    params: serde_json::Value,
+    parse_result: impl Fn(serde_json::Value) -> Result<T, serde_json::Error> + Send + Sync + 'static,
+    parse_error: impl Fn(serde_json::Value) -> Result<E, serde_json::Error> + Send + Sync + 'static,
+) -> RpcAnyWithParserRequest<T, E> {
+    RpcAnyWithParserRequest {
+        method: method_name.to_string(),
+        params,
+        parse_result: Box::new(parse_result),
+        parse_error: Box::new(parse_error),
+    }
+}
+
+type Parser<T> = Box<dyn Fn(serde_json::Value) -> Result<T, serde_json::Error> + Send + Sync>;
+
+/// A request for an arbitrary method, with custom parsers for its result and handler error.
+///
+/// See [`any_with_parser`](crate::methods::any_with_parser).
+pub struct RpcAnyWithParserRequest<T, E> {
+    pub method: String,
+    pub params: serde_json::Value,
+    parse_result: Parser<T>,
+    parse_error: Parser<E>,
+}
+
+impl<T, E> RpcAnyWithParserRequest<T, E> {
+    pub(crate) fn untyped(&self) -> RpcAnyRequest<serde_json::Value, serde_json::Value> {
+        request::<Result<serde_json::Value, serde_json::Value>>(&self.method, self.params.clone())
+    }
+
+    pub(crate) fn parse_result(&self, result: serde_json::Value) -> Result<T, serde_json::Error> {
+        (self.parse_result)(result)
+    }
+
+    pub(crate) fn parse_error(&self, error: serde_json::Value) -> Result<E, serde_json::Error> {
+        (self.parse_error)(error)
+    }
+}
+
+impl<T, E> std::fmt::Debug for RpcAnyWithParserRequest<T, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcAnyWithParserRequest")
+            .field("method", &self.method)
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Debug)]
 pub struct RpcAnyRequest<T, E> {
     pub method: String,
@@ -95,6 +167,11 @@ where
     fn params(&self) -> Result<serde_json::Value, io::Error> {
         Ok(self.params.clone())
     }
+
+    // arbitrary methods could mutate state, so they're never resent
+    fn is_idempotent(&self) -> bool {
+        false
+    }
 }
 
 pub trait AnyRequestResult {
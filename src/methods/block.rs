@@ -75,6 +75,16 @@ impl RpcHandlerError for RpcBlockError {
     fn parse(value: serde_json::Value) -> Result<Self, serde_json::Error> {
         common::parse_unknown_block!(value => Self)
     }
+
+    fn is_unknown_block(&self) -> bool {
+        matches!(self, Self::UnknownBlock { .. })
+    }
+
+    #[cfg(feature = "legacy-compat")]
+    fn parse_legacy_error(value: serde_json::Value) -> Option<Result<Self, serde_json::Error>> {
+        legacy::unknown_block_error(&value)
+            .map(|error_message| Ok(Self::UnknownBlock { error_message }))
+    }
 }
 
 impl RpcMethod for RpcBlockRequest {
@@ -88,6 +98,10 @@ impl RpcMethod for RpcBlockRequest {
     fn params(&self) -> Result<serde_json::Value, io::Error> {
         Ok(json!(self))
     }
+
+    fn block_height(&self) -> Option<near_primitives::types::BlockHeight> {
+        common::block_height(&self.block_reference)
+    }
 }
 
 impl private::Sealed for RpcBlockRequest {}
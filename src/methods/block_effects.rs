@@ -0,0 +1,60 @@
+//! Returns the effects of a block: the kinds of state changes it made, and to which accounts.
+//!
+//! This is the stabilized successor of [`EXPERIMENTAL_changes_in_block`](crate::methods::EXPERIMENTAL_changes_in_block).
+//!
+//! The `RpcBlockEffectsRequest` takes in a [`BlockReference`](https://docs.rs/near-primitives/0.12.0/near_primitives/types/enum.BlockReference.html) enum which has multiple variants.
+//!
+//! ## Example
+//!
+//! Returns the effects of block <https://explorer.near.org/blocks/3Lq3Mtfpc3spH9oF5dXnUzvCBEqjTQwX1yCqKibwzgWR>
+//!
+//! ```no_run
+//! use near_jsonrpc_client::{methods, JsonRpcClient};
+//! use near_primitives::types::{BlockReference, BlockId};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://archival-rpc.mainnet.near.org");
+//!
+//! let request = methods::block_effects::RpcBlockEffectsRequest {
+//!     block_reference: BlockReference::BlockId(BlockId::Height(47988413))
+//! };
+//!
+//! let response = client.call(request).await?;
+//!
+//! assert!(matches!(
+//!     response,
+//!     methods::block_effects::RpcBlockEffectsResponse { .. }
+//! ));
+//! # Ok(())
+//! # }
+//! ```
+use super::*;
+
+pub use near_jsonrpc_primitives::types::changes::RpcStateChangesError as RpcBlockEffectsError;
+pub use near_jsonrpc_primitives::types::changes::RpcStateChangesInBlockByTypeResponse as RpcBlockEffectsResponse;
+
+#[derive(Debug, serde::Serialize)]
+pub struct RpcBlockEffectsRequest {
+    #[serde(flatten)]
+    pub block_reference: near_primitives::types::BlockReference,
+}
+
+impl RpcMethod for RpcBlockEffectsRequest {
+    type Response = RpcBlockEffectsResponse;
+    type Error = RpcBlockEffectsError;
+
+    fn method_name(&self) -> &str {
+        "block_effects"
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        Ok(json!(self))
+    }
+
+    fn block_height(&self) -> Option<near_primitives::types::BlockHeight> {
+        common::block_height(&self.block_reference)
+    }
+}
+
+impl private::Sealed for RpcBlockEffectsRequest {}
@@ -48,7 +48,8 @@
 //! });
 //!
 //! let request = methods::broadcast_tx_async::RpcBroadcastTxAsyncRequest {
-//!     signed_transaction: transaction.sign(&near_crypto::Signer::InMemory(signer))
+//!     signed_transaction: transaction.sign(&near_crypto::Signer::InMemory(signer)),
+//!     wait_until: None,
 //! };
 //! # Ok(())
 //! # }
@@ -56,12 +57,34 @@
 use super::*;
 
 pub use near_primitives::transaction::SignedTransaction;
+pub use near_primitives::views::TxExecutionStatus;
 
 pub type RpcBroadcastTxAsyncResponse = near_primitives::hash::CryptoHash;
 
 #[derive(Debug)]
 pub struct RpcBroadcastTxAsyncRequest {
     pub signed_transaction: SignedTransaction,
+    /// The execution status to wait for, once converted into a [`send_tx`](crate::methods::send_tx)
+    /// request.
+    ///
+    /// The `broadcast_tx_async` method itself ignores it. Defaults to `None`, the status the
+    /// method waits for.
+    pub wait_until: Option<TxExecutionStatus>,
+}
+
+impl RpcBroadcastTxAsyncRequest {
+    pub fn new(signed_transaction: SignedTransaction) -> Self {
+        Self {
+            signed_transaction,
+            wait_until: None,
+        }
+    }
+
+    /// Set the execution status to wait for, once converted into a `send_tx` request.
+    pub fn wait_until(mut self, wait_until: TxExecutionStatus) -> Self {
+        self.wait_until = Some(wait_until);
+        self
+    }
 }
 
 impl From<RpcBroadcastTxAsyncRequest>
@@ -70,7 +93,7 @@ impl From<RpcBroadcastTxAsyncRequest>
     fn from(this: RpcBroadcastTxAsyncRequest) -> Self {
         Self {
             signed_transaction: this.signed_transaction,
-            wait_until: near_primitives::views::TxExecutionStatus::None,
+            wait_until: this.wait_until.unwrap_or(TxExecutionStatus::None),
         }
     }
 }
@@ -96,6 +119,16 @@ impl RpcMethod for RpcBroadcastTxAsyncRequest {
             &self.signed_transaction
         )?]))
     }
+
+    fn write_params(&self, writer: &mut dyn io::Write) -> Result<(), io::Error> {
+        writer.write_all(b"[")?;
+        common::write_signed_transaction(&self.signed_transaction, writer)?;
+        writer.write_all(b"]")
+    }
+
+    fn is_idempotent(&self) -> bool {
+        false
+    }
 }
 
 impl private::Sealed for RpcBroadcastTxAsyncRequest {}
@@ -49,7 +49,8 @@
 //! });
 //!
 //! let request = methods::broadcast_tx_commit::RpcBroadcastTxCommitRequest {
-//!     signed_transaction: transaction.sign(&near_crypto::Signer::InMemory(signer))
+//!     signed_transaction: transaction.sign(&near_crypto::Signer::InMemory(signer)),
+//!     wait_until: None,
 //! };
 //! # Ok(())
 //! # }
@@ -58,12 +59,34 @@ use super::*;
 
 pub use near_jsonrpc_primitives::types::transactions::RpcTransactionError;
 pub use near_primitives::transaction::SignedTransaction;
+pub use near_primitives::views::TxExecutionStatus;
 
 pub type RpcBroadcastTxCommitResponse = near_primitives::views::FinalExecutionOutcomeView;
 
 #[derive(Debug)]
 pub struct RpcBroadcastTxCommitRequest {
     pub signed_transaction: SignedTransaction,
+    /// The execution status to wait for, once converted into a [`send_tx`](crate::methods::send_tx)
+    /// request.
+    ///
+    /// The `broadcast_tx_commit` method itself ignores it. Defaults to `ExecutedOptimistic`, the status the
+    /// method waits for.
+    pub wait_until: Option<TxExecutionStatus>,
+}
+
+impl RpcBroadcastTxCommitRequest {
+    pub fn new(signed_transaction: SignedTransaction) -> Self {
+        Self {
+            signed_transaction,
+            wait_until: None,
+        }
+    }
+
+    /// Set the execution status to wait for, once converted into a `send_tx` request.
+    pub fn wait_until(mut self, wait_until: TxExecutionStatus) -> Self {
+        self.wait_until = Some(wait_until);
+        self
+    }
 }
 
 impl From<RpcBroadcastTxCommitRequest>
@@ -72,7 +95,7 @@ impl From<RpcBroadcastTxCommitRequest>
     fn from(this: RpcBroadcastTxCommitRequest) -> Self {
         Self {
             signed_transaction: this.signed_transaction,
-            wait_until: near_primitives::views::TxExecutionStatus::default(),
+            wait_until: this.wait_until.unwrap_or_default(),
         }
     }
 }
@@ -90,6 +113,61 @@ impl RpcMethod for RpcBroadcastTxCommitRequest {
             &self.signed_transaction
         )?]))
     }
+
+    fn write_params(&self, writer: &mut dyn io::Write) -> Result<(), io::Error> {
+        writer.write_all(b"[")?;
+        common::write_signed_transaction(&self.signed_transaction, writer)?;
+        writer.write_all(b"]")
+    }
+
+    fn is_idempotent(&self) -> bool {
+        false
+    }
 }
 
 impl private::Sealed for RpcBroadcastTxCommitRequest {}
+
+#[cfg(test)]
+mod tests {
+    use near_crypto::{KeyType, Signature};
+    use near_jsonrpc_primitives::types::transactions::RpcSendTransactionRequest;
+    use near_primitives::transaction::{Transaction, TransactionV0};
+
+    use super::*;
+
+    #[test]
+    fn wait_until_carries_over_to_send_tx() -> Result<(), Box<dyn std::error::Error>> {
+        let signed_transaction = SignedTransaction::new(
+            Signature::empty(KeyType::ED25519),
+            Transaction::V0(TransactionV0 {
+                signer_id: "alice.testnet".parse()?,
+                public_key: near_crypto::PublicKey::empty(KeyType::ED25519),
+                nonce: 1,
+                receiver_id: "bob.testnet".parse()?,
+                block_hash: Default::default(),
+                actions: vec![],
+            }),
+        );
+
+        let request = RpcBroadcastTxCommitRequest::new(signed_transaction.clone());
+        assert_eq!(
+            RpcSendTransactionRequest::from(request).wait_until,
+            TxExecutionStatus::ExecutedOptimistic
+        );
+
+        let request = RpcBroadcastTxCommitRequest::new(signed_transaction)
+            .wait_until(TxExecutionStatus::Final);
+        assert_eq!(
+            to_json(&request)?["params"],
+            to_json(&RpcBroadcastTxCommitRequest::new(
+                request.signed_transaction.clone()
+            ))?["params"]
+        );
+        assert_eq!(
+            RpcSendTransactionRequest::from(request).wait_until,
+            TxExecutionStatus::Final
+        );
+
+        Ok(())
+    }
+}
@@ -102,6 +102,16 @@ impl RpcHandlerError for RpcChunkError {
     fn parse(value: serde_json::Value) -> Result<Self, serde_json::Error> {
         common::parse_unknown_block!(value => Self)
     }
+
+    fn is_unknown_block(&self) -> bool {
+        matches!(self, Self::UnknownBlock { .. })
+    }
+
+    #[cfg(feature = "legacy-compat")]
+    fn parse_legacy_error(value: serde_json::Value) -> Option<Result<Self, serde_json::Error>> {
+        legacy::unknown_block_error(&value)
+            .map(|error_message| Ok(Self::UnknownBlock { error_message }))
+    }
 }
 
 impl RpcMethod for RpcChunkRequest {
@@ -115,6 +125,16 @@ impl RpcMethod for RpcChunkRequest {
     fn params(&self) -> Result<serde_json::Value, io::Error> {
         Ok(json!(self))
     }
+
+    fn block_height(&self) -> Option<near_primitives::types::BlockHeight> {
+        match self.chunk_reference {
+            ChunkReference::BlockShardId {
+                block_id: near_primitives::types::BlockId::Height(height),
+                ..
+            } => Some(height),
+            _ => None,
+        }
+    }
 }
 
 impl private::Sealed for RpcChunkRequest {}
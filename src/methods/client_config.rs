@@ -0,0 +1,96 @@
+//! Queries the configuration of the node.
+//!
+//! Returns the client configuration the node is running with, e.g. the chain it's following,
+//! whether it's an archival node and which shards it tracks.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::{methods, JsonRpcClient};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("http://localhost:3030");
+//!
+//! let request = methods::client_config::RpcClientConfigRequest;
+//!
+//! let response = client.call(request).await?;
+//!
+//! println!("{} node on {}", response.version.version, response.chain_id);
+//! if let Some(gc) = response.other.get("gc") {
+//!     println!("garbage collection: {}", gc);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use super::*;
+
+pub use near_jsonrpc_primitives::types::client_config::RpcClientConfigError;
+
+use near_primitives::types::{AccountId, ShardId};
+use near_primitives::version::Version;
+
+#[derive(Debug)]
+pub struct RpcClientConfigRequest;
+
+/// The configuration of the node.
+///
+/// Commonly audited settings are typed, the rest of the configuration is kept as is in
+/// [`other`](Self::other).
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcClientConfigResponse {
+    pub version: Version,
+    pub chain_id: String,
+    #[serde(default)]
+    pub rpc_addr: Option<String>,
+    #[serde(default)]
+    pub archive: bool,
+    #[serde(default)]
+    pub tracked_accounts: Vec<AccountId>,
+    #[serde(default)]
+    pub tracked_shards: Vec<ShardId>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+impl RpcHandlerResponse for RpcClientConfigResponse {}
+
+impl RpcHandlerError for RpcClientConfigError {}
+
+impl RpcMethod for RpcClientConfigRequest {
+    type Response = RpcClientConfigResponse;
+    type Error = RpcClientConfigError;
+
+    fn method_name(&self) -> &str {
+        "client_config"
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        Ok(json!(null))
+    }
+}
+
+impl private::Sealed for RpcClientConfigRequest {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untyped_settings_are_kept() -> Result<(), serde_json::Error> {
+        let response = RpcClientConfigResponse::parse(json!({
+            "version": { "version": "2.4.0", "build": "2.4.0", "rustc_version": "1.82.0" },
+            "chain_id": "testnet",
+            "rpc_addr": "0.0.0.0:3030",
+            "archive": true,
+            "tracked_shards": [0],
+            "gc": { "gc_blocks_limit": 2 }
+        }))?;
+
+        assert_eq!(response.chain_id, "testnet");
+        assert!(response.archive);
+        assert!(response.tracked_accounts.is_empty());
+        assert_eq!(response.other["gc"]["gc_blocks_limit"], 2);
+        Ok(())
+    }
+}
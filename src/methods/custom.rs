@@ -0,0 +1,195 @@
+//! Custom methods.
+//!
+//! The [`rpc_method!`](crate::rpc_method) macro defines a typed request for a method this crate
+//! doesn't wrap yet, e.g. an endpoint specific to an RPC provider, or one that's yet to be
+//! stabilized by the node.
+//!
+//! The request is sent with its fields as named parameters, so it must implement
+//! `serde::Serialize`. The response and error types must implement [`RpcHandlerResponse`] and
+//! [`RpcHandlerError`] respectively.
+//!
+//! The macro implements [`CustomMethod`] for the request, which is then sent wrapped in a
+//! [`CustomRequest`]. [`RpcMethod`] stays sealed, only [`CustomRequest`] implements it on behalf
+//! of custom methods.
+//!
+//! ## Example
+//!
+//! ```
+//! use near_jsonrpc_client::methods::custom::CustomRequest;
+//! use near_jsonrpc_client::{methods, rpc_method, JsonRpcClient};
+//! use near_primitives::types::AccountId;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, Deserialize)]
+//! pub struct RpcAccountActivityResponse {
+//!     pub last_active_block: u64,
+//! }
+//!
+//! #[derive(Debug, Deserialize)]
+//! pub struct RpcAccountActivityError {
+//!     pub name: String,
+//! }
+//!
+//! impl methods::RpcHandlerResponse for RpcAccountActivityResponse {}
+//!
+//! impl methods::RpcHandlerError for RpcAccountActivityError {}
+//!
+//! rpc_method! {
+//!     #[rpc(
+//!         method = "provider_account_activity",
+//!         response = RpcAccountActivityResponse,
+//!         error = RpcAccountActivityError,
+//!     )]
+//!     #[derive(Debug, Serialize)]
+//!     pub struct RpcAccountActivityRequest {
+//!         pub account_id: AccountId,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.provider.example.com");
+//!
+//! let request = CustomRequest(RpcAccountActivityRequest {
+//!     account_id: "alice.near".parse()?,
+//! });
+//!
+//! assert_eq!(
+//!     methods::to_json(&request)?["params"],
+//!     serde_json::json!({ "account_id": "alice.near" })
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Methods are assumed to be safe to resend, mark those that mutate state with
+//! `idempotent = false`.
+use super::*;
+
+/// A method defined with the [`rpc_method!`](crate::rpc_method) macro.
+pub trait CustomMethod: serde::Serialize {
+    type Response: RpcHandlerResponse;
+    type Error: RpcHandlerError;
+
+    /// The name of the method.
+    const METHOD_NAME: &'static str;
+
+    /// Whether calls to the method can be safely sent more than once, see
+    /// [`RpcMethod::is_idempotent`].
+    const IDEMPOTENT: bool = true;
+}
+
+/// A call to a [`CustomMethod`].
+#[derive(Debug, Clone)]
+pub struct CustomRequest<M>(pub M);
+
+impl<M: CustomMethod> RpcMethod for CustomRequest<M> {
+    type Response = M::Response;
+    type Error = M::Error;
+
+    fn method_name(&self) -> &str {
+        M::METHOD_NAME
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        serde_json::to_value(&self.0).map_err(Into::into)
+    }
+
+    fn is_idempotent(&self) -> bool {
+        M::IDEMPOTENT
+    }
+}
+
+impl<M> private::Sealed for CustomRequest<M> {}
+
+/// Defines a typed request for a custom method.
+///
+/// See the [`custom`](crate::methods::custom) module for details.
+#[macro_export]
+macro_rules! rpc_method {
+    (
+        #[rpc(
+            method = $method:literal,
+            response = $response:ty,
+            error = $error:ty
+            $(, idempotent = $idempotent:literal)?
+            $(,)?
+        )]
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident $($body:tt)*
+    ) => {
+        $(#[$meta])*
+        $vis struct $name $($body)*
+
+        impl $crate::methods::custom::CustomMethod for $name {
+            type Response = $response;
+            type Error = $error;
+
+            const METHOD_NAME: &'static str = $method;
+
+            $(const IDEMPOTENT: bool = $idempotent;)?
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    rpc_method! {
+        #[rpc(
+            method = "provider_submit",
+            response = serde_json::Value,
+            error = serde_json::Value,
+            idempotent = false,
+        )]
+        #[derive(Debug, serde::Serialize)]
+        struct RpcSubmitRequest {
+            payload: String,
+        }
+    }
+
+    rpc_method! {
+        #[rpc(method = "provider_ping", response = serde_json::Value, error = serde_json::Value)]
+        #[derive(Debug, serde::Serialize)]
+        struct RpcPingRequest;
+    }
+
+    #[test]
+    fn custom_methods_are_serialized() -> Result<(), io::Error> {
+        let request = CustomRequest(RpcSubmitRequest {
+            payload: "data".to_string(),
+        });
+        assert_eq!(request.method_name(), "provider_submit");
+        assert_eq!(request.params()?, json!({ "payload": "data" }));
+        assert!(!request.is_idempotent());
+
+        assert_eq!(CustomRequest(RpcPingRequest).params()?, json!(null));
+        assert!(CustomRequest(RpcPingRequest).is_idempotent());
+        Ok(())
+    }
+
+    #[cfg(feature = "unstable-custom-methods")]
+    #[test]
+    fn unsealed_methods_can_be_implemented() -> Result<(), io::Error> {
+        struct RpcStatsRequest;
+
+        impl RpcMethod for RpcStatsRequest {
+            type Response = serde_json::Value;
+            type Error = serde_json::Value;
+
+            fn method_name(&self) -> &str {
+                "provider_stats"
+            }
+
+            fn params(&self) -> Result<serde_json::Value, io::Error> {
+                Ok(json!([]))
+            }
+        }
+
+        let request = to_json(&RpcStatsRequest)?;
+        assert_eq!(request["method"], "provider_stats");
+        assert_eq!(request["params"], json!([]));
+        Ok(())
+    }
+}
@@ -170,6 +170,10 @@ impl RpcMethod for RpcStateChangesInBlockByTypeRequest {
     fn params(&self) -> Result<serde_json::Value, io::Error> {
         Ok(json!(self))
     }
+
+    fn block_height(&self) -> Option<near_primitives::types::BlockHeight> {
+        common::block_height(&self.block_reference)
+    }
 }
 
 impl private::Sealed for RpcStateChangesInBlockByTypeRequest {}
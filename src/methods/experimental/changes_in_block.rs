@@ -35,8 +35,6 @@ pub use near_jsonrpc_primitives::types::changes::{
     RpcStateChangesError, RpcStateChangesInBlockByTypeResponse, RpcStateChangesInBlockRequest,
 };
 
-impl RpcHandlerResponse for RpcStateChangesInBlockByTypeResponse {}
-
 impl RpcMethod for RpcStateChangesInBlockRequest {
     type Response = RpcStateChangesInBlockByTypeResponse;
     type Error = RpcStateChangesError;
@@ -48,6 +46,10 @@ impl RpcMethod for RpcStateChangesInBlockRequest {
     fn params(&self) -> Result<serde_json::Value, io::Error> {
         Ok(json!(self))
     }
+
+    fn block_height(&self) -> Option<near_primitives::types::BlockHeight> {
+        common::block_height(&self.block_reference)
+    }
 }
 
 impl private::Sealed for RpcStateChangesInBlockRequest {}
@@ -0,0 +1,55 @@
+//! Queries the congestion level of a shard at a given block.
+//!
+//! The `RpcCongestionLevelRequest` takes in a [`ChunkReference`](https://docs.rs/near-jsonrpc-primitives/0.28.0/near_jsonrpc_primitives/types/chunks/enum.ChunkReference.html) enum which has multiple variants.
+//!
+//! The congestion level ranges from 0, for a shard that isn't congested, to 1, for a shard
+//! rejecting new transactions.
+//!
+//! ## Example
+//!
+//! Returns the congestion level of shard 0 at a given block.
+//!
+//! ```no_run
+//! use near_jsonrpc_client::{methods, JsonRpcClient};
+//! use near_jsonrpc_primitives::types::chunks::ChunkReference;
+//! use near_primitives::types::{BlockId, ShardId};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.mainnet.near.org");
+//!
+//! let request = methods::EXPERIMENTAL_congestion_level::RpcCongestionLevelRequest {
+//!     chunk_reference: ChunkReference::BlockShardId {
+//!         block_id: BlockId::Height(140_000_000),
+//!         shard_id: ShardId::new(0),
+//!     },
+//! };
+//!
+//! let response = client.call(request).await?;
+//!
+//! println!("congestion level: {}", response.congestion_level);
+//! # Ok(())
+//! # }
+//! ```
+use super::*;
+
+pub use near_jsonrpc_primitives::types::congestion::{
+    RpcCongestionLevelError, RpcCongestionLevelRequest, RpcCongestionLevelResponse,
+};
+
+impl RpcHandlerResponse for RpcCongestionLevelResponse {}
+
+impl RpcMethod for RpcCongestionLevelRequest {
+    type Response = RpcCongestionLevelResponse;
+    type Error = RpcCongestionLevelError;
+
+    fn method_name(&self) -> &str {
+        "EXPERIMENTAL_congestion_level"
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        Ok(json!(self))
+    }
+}
+
+impl private::Sealed for RpcCongestionLevelRequest {}
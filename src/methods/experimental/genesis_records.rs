@@ -0,0 +1,154 @@
+//! Queries the genesis records of the network, one page at a time.
+//!
+//! Lets tools reconstruct the genesis state without downloading the genesis file out-of-band.
+//!
+//! This is a legacy method, only served by older, archival nodes. Current nearcore releases
+//! respond with a method-not-found error.
+//!
+//! Pages must hold at least one record, requests with a zero `limit` are rejected before being
+//! sent.
+//!
+//! ## Example
+//!
+//! Fetches all genesis records, 100 at a time.
+//!
+//! ```no_run
+//! use near_jsonrpc_client::{methods, JsonRpcClient};
+//! use methods::EXPERIMENTAL_genesis_records::{GenesisRecordsPagination, RpcGenesisRecordsRequest};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("http://localhost:3030");
+//!
+//! let mut request = RpcGenesisRecordsRequest {
+//!     pagination: GenesisRecordsPagination { offset: 0, limit: 100 },
+//! };
+//!
+//! let mut records = vec![];
+//! loop {
+//!     let response = client.call(&request).await?;
+//!     let done = response.records.len() < request.pagination.limit;
+//!     records.extend(response.records);
+//!     if done {
+//!         break;
+//!     }
+//!     request.pagination = request.pagination.next();
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use super::*;
+
+pub use near_primitives::state_record::StateRecord;
+
+/// A page of genesis records.
+#[derive(Eq, Copy, Clone, Debug, PartialEq, serde::Serialize, Deserialize)]
+pub struct GenesisRecordsPagination {
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl GenesisRecordsPagination {
+    /// The page following this one.
+    pub fn next(&self) -> Self {
+        Self {
+            offset: self.offset.saturating_add(self.limit),
+            limit: self.limit,
+        }
+    }
+}
+
+impl Default for GenesisRecordsPagination {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            limit: 100,
+        }
+    }
+}
+
+#[derive(Debug, Default, serde::Serialize)]
+pub struct RpcGenesisRecordsRequest {
+    pub pagination: GenesisRecordsPagination,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RpcGenesisRecordsResponse {
+    pub pagination: GenesisRecordsPagination,
+    pub records: Vec<StateRecord>,
+}
+
+#[derive(Debug, Deserialize, Error)]
+#[error("{}", unreachable!("fatal: this error should never be constructed"))]
+pub enum RpcGenesisRecordsError {}
+
+impl RpcHandlerResponse for RpcGenesisRecordsResponse {}
+
+impl RpcHandlerError for RpcGenesisRecordsError {}
+
+impl RpcMethod for RpcGenesisRecordsRequest {
+    type Response = RpcGenesisRecordsResponse;
+    type Error = RpcGenesisRecordsError;
+
+    fn method_name(&self) -> &str {
+        "EXPERIMENTAL_genesis_records"
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        if self.pagination.limit == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "genesis records pages must hold at least one record",
+            ));
+        }
+        Ok(json!(self))
+    }
+}
+
+impl private::Sealed for RpcGenesisRecordsRequest {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pages_are_sequential() -> Result<(), io::Error> {
+        let request = RpcGenesisRecordsRequest::default();
+        assert_eq!(
+            request.params()?,
+            json!({ "pagination": { "offset": 0, "limit": 100 } })
+        );
+        assert_eq!(
+            request.pagination.next().next(),
+            GenesisRecordsPagination {
+                offset: 200,
+                limit: 100
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn pages_stop_at_the_last_offset() {
+        let last = GenesisRecordsPagination {
+            offset: usize::MAX - 1,
+            limit: 100,
+        };
+        assert_eq!(last.next().offset, usize::MAX);
+        assert_eq!(last.next().next().offset, usize::MAX);
+    }
+
+    #[test]
+    fn empty_pages_are_rejected() {
+        let request = RpcGenesisRecordsRequest {
+            pagination: GenesisRecordsPagination {
+                offset: 0,
+                limit: 0,
+            },
+        };
+        assert_eq!(
+            request.params().unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! Returns the proof of a block against a light client head.
+//!
+//! Proves that the block with the given hash is part of the chain ending at `light_client_head`,
+//! complementing the execution proofs returned by [`light_client_proof`](crate::methods::light_client_proof).
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::{methods, JsonRpcClient};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! let client = JsonRpcClient::connect("https://archival-rpc.mainnet.near.org");
+//!
+//! let request = methods::EXPERIMENTAL_light_client_block_proof::RpcLightClientBlockProofRequest {
+//!     block_hash: "6atGq4TUTZerVHU9qWoYfzXNBg3K4C4cca15TE6KfuBr".parse()?,
+//!     light_client_head: "ANm3jm5wq1Z4rJv6tXWyiDtC3wYKpXVHY4iq6bE1te7B".parse()?,
+//! };
+//!
+//! let response = client.call(request).await?;
+//!
+//! assert!(matches!(
+//!     response,
+//!     methods::EXPERIMENTAL_light_client_block_proof::RpcLightClientBlockProofResponse { .. }
+//! ));
+//! # Ok(())
+//! # }
+//! ```
+use super::*;
+
+pub use near_jsonrpc_primitives::types::light_client::{
+    RpcLightClientBlockProofRequest, RpcLightClientBlockProofResponse, RpcLightClientProofError,
+};
+
+impl RpcHandlerResponse for RpcLightClientBlockProofResponse {}
+
+impl RpcMethod for RpcLightClientBlockProofRequest {
+    type Response = RpcLightClientBlockProofResponse;
+    type Error = RpcLightClientProofError;
+
+    fn method_name(&self) -> &str {
+        "EXPERIMENTAL_light_client_block_proof"
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        Ok(json!(self))
+    }
+}
+
+impl private::Sealed for RpcLightClientBlockProofRequest {}
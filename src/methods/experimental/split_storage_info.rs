@@ -0,0 +1,49 @@
+//! Returns the split storage status of an archival node.
+//!
+//! Reports the heads of the hot and cold stores, so operators can monitor how far the cold store
+//! lags behind. Nodes that don't use split storage return empty fields.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::{methods, JsonRpcClient};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("http://localhost:3030");
+//!
+//! let request = methods::EXPERIMENTAL_split_storage_info::RpcSplitStorageInfoRequest {};
+//!
+//! let response = client.call(request).await?;
+//!
+//! println!(
+//!     "hot head: {:?}, cold head: {:?}",
+//!     response.result.head_height, response.result.cold_head_height
+//! );
+//! # Ok(())
+//! # }
+//! ```
+use super::*;
+
+pub use near_jsonrpc_primitives::types::split_storage::{
+    RpcSplitStorageInfoError, RpcSplitStorageInfoRequest, RpcSplitStorageInfoResponse,
+};
+
+impl RpcHandlerResponse for RpcSplitStorageInfoResponse {}
+
+impl RpcHandlerError for RpcSplitStorageInfoError {}
+
+impl RpcMethod for RpcSplitStorageInfoRequest {
+    type Response = RpcSplitStorageInfoResponse;
+    type Error = RpcSplitStorageInfoError;
+
+    fn method_name(&self) -> &str {
+        "EXPERIMENTAL_split_storage_info"
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        Ok(json!(self))
+    }
+}
+
+impl private::Sealed for RpcSplitStorageInfoRequest {}
@@ -85,3 +85,32 @@ impl RpcMethod for RpcTransactionStatusRequest {
 }
 
 impl private::Sealed for RpcTransactionStatusRequest {}
+
+/// Queries the status of a transaction given as borsh serialized bytes.
+///
+/// Useful with transactions signed by external builders or signers, which can be queried without
+/// deserializing them into a [`SignedTransaction`](near_primitives::transaction::SignedTransaction).
+#[derive(Debug)]
+pub struct RpcTransactionStatusBytesRequest {
+    /// The borsh serialized signed transaction.
+    pub signed_transaction: Vec<u8>,
+    pub wait_until: near_primitives::views::TxExecutionStatus,
+}
+
+impl RpcMethod for RpcTransactionStatusBytesRequest {
+    type Response = RpcTransactionResponse;
+    type Error = RpcTransactionError;
+
+    fn method_name(&self) -> &str {
+        "EXPERIMENTAL_tx_status"
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        Ok(json!({
+            "signed_tx_base64": near_primitives::serialize::to_base64(&self.signed_transaction),
+            "wait_until": self.wait_until
+        }))
+    }
+}
+
+impl private::Sealed for RpcTransactionStatusBytesRequest {}
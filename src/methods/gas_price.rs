@@ -52,8 +52,36 @@
 //!     # Ok(())
 //!     # }
 //!     ```
+//!
+//! - `BlockReference`, using the named-parameter form of the method. Nodes only look blocks up
+//!   by id, or take the latest block, so references to final blocks and sync checkpoints are
+//!   rejected before being sent.
+//!
+//!     ```
+//!     # use near_jsonrpc_client::{methods, JsonRpcClient};
+//!     use near_primitives::types::BlockReference;
+//!
+//!     # #[tokio::main]
+//!     # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = JsonRpcClient::connect("https://rpc.mainnet.near.org");
+//!
+//!     let request = methods::gas_price::RpcGasPriceByReferenceRequest {
+//!         block_reference: BlockReference::latest(),
+//!     };
+//!
+//!     let response = client.call(request).await?;
+//!
+//!     assert!(matches!(
+//!         response,
+//!         methods::gas_price::RpcGasPriceResponse { .. }
+//!     ));
+//!     # Ok(())
+//!     # }
+//!     ```
 use super::*;
 
+use near_primitives::types::{BlockReference, Finality};
+
 pub use near_jsonrpc_primitives::types::gas_price::{RpcGasPriceError, RpcGasPriceRequest};
 
 pub type RpcGasPriceResponse = near_primitives::views::GasPriceView;
@@ -64,6 +92,10 @@ impl RpcHandlerError for RpcGasPriceError {
     fn parse(value: serde_json::Value) -> Result<Self, serde_json::Error> {
         common::parse_unknown_block!(value => Self)
     }
+
+    fn is_unknown_block(&self) -> bool {
+        matches!(self, Self::UnknownBlock { .. })
+    }
 }
 
 impl RpcMethod for RpcGasPriceRequest {
@@ -80,3 +112,85 @@ impl RpcMethod for RpcGasPriceRequest {
 }
 
 impl private::Sealed for RpcGasPriceRequest {}
+
+/// Queries the gas price of a block specified by a [`BlockReference`].
+///
+/// Unlike [`RpcGasPriceRequest`], this is sent with named parameters, e.g.
+/// `{"block_id": 61512623}`, or `{"block_id": null}` for the latest block.
+///
+/// Nodes only parse the `block_id` parameter, and answer with the latest gas price otherwise. So
+/// only block ids and [`BlockReference::latest`] are supported, other finalities and sync
+/// checkpoints fail with an [`io::ErrorKind::InvalidInput`] error before the request is sent.
+#[derive(Debug, Clone)]
+pub struct RpcGasPriceByReferenceRequest {
+    pub block_reference: BlockReference,
+}
+
+impl From<RpcGasPriceRequest> for RpcGasPriceByReferenceRequest {
+    fn from(request: RpcGasPriceRequest) -> Self {
+        Self {
+            block_reference: request
+                .block_id
+                .map_or_else(BlockReference::latest, BlockReference::BlockId),
+        }
+    }
+}
+
+impl RpcMethod for RpcGasPriceByReferenceRequest {
+    type Response = RpcGasPriceResponse;
+    type Error = RpcGasPriceError;
+
+    fn method_name(&self) -> &str {
+        "gas_price"
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        match &self.block_reference {
+            BlockReference::BlockId(block_id) => Ok(json!({ "block_id": block_id })),
+            BlockReference::Finality(Finality::None) => Ok(json!({ "block_id": null })),
+            block_reference => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "gas prices can only be queried by block id, or at the latest block, not at {:?}",
+                    block_reference
+                ),
+            )),
+        }
+    }
+}
+
+impl private::Sealed for RpcGasPriceByReferenceRequest {}
+
+#[cfg(test)]
+mod tests {
+    use near_primitives::types::{BlockId, SyncCheckpoint};
+
+    use super::*;
+
+    #[test]
+    fn block_references_are_named_params() -> Result<(), io::Error> {
+        let request = RpcGasPriceByReferenceRequest::from(RpcGasPriceRequest {
+            block_id: Some(BlockId::Height(61512623)),
+        });
+        assert_eq!(request.params()?, json!({ "block_id": 61512623 }));
+
+        let request = RpcGasPriceByReferenceRequest::from(RpcGasPriceRequest { block_id: None });
+        assert_eq!(request.params()?, json!({ "block_id": null }));
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_block_references_are_rejected() {
+        for block_reference in [
+            BlockReference::Finality(Finality::Final),
+            BlockReference::Finality(Finality::DoomSlug),
+            BlockReference::SyncCheckpoint(SyncCheckpoint::Genesis),
+        ] {
+            let request = RpcGasPriceByReferenceRequest { block_reference };
+            assert_eq!(
+                request.params().unwrap_err().kind(),
+                io::ErrorKind::InvalidInput
+            );
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Compatibility with the responses of older nearcore releases.
+//!
+//! Before errors were structured, nodes reported most of them as plain strings, and before
+//! `wait_until`, transaction responses lacked their execution status. With the `legacy-compat`
+//! feature, these are recognized and converted into the typed forms of current releases.
+use super::*;
+
+use near_jsonrpc_primitives::types::query::RpcQueryError;
+use near_jsonrpc_primitives::types::transactions::RpcTransactionError;
+use near_primitives::hash::CryptoHash;
+use near_primitives::types::BlockHeight;
+use near_primitives::views::TxExecutionStatus;
+
+/// Fills in the `final_execution_status` of transaction responses predating `wait_until`.
+///
+/// Such nodes only responded once the transaction was executed, without reporting whether it was
+/// finalized, so executed transactions are reported as [`TxExecutionStatus::ExecutedOptimistic`].
+pub fn tx_response(mut response: serde_json::Value) -> serde_json::Value {
+    if let Some(fields) = response.as_object_mut() {
+        if !fields.contains_key("final_execution_status") {
+            let status = match fields.get("status") {
+                Some(status) if status == "NotStarted" || status == "Started" => {
+                    TxExecutionStatus::Included
+                }
+                Some(_) => TxExecutionStatus::ExecutedOptimistic,
+                None => return response,
+            };
+            fields.insert("final_execution_status".to_string(), json!(status));
+        }
+    }
+    response
+}
+
+/// Recognizes the string errors transaction methods used to report in the `data` field.
+pub fn tx_error(data: &serde_json::Value) -> Option<RpcTransactionError> {
+    let data = data.as_str()?;
+    if data == "Timeout" {
+        return Some(RpcTransactionError::TimeoutError);
+    }
+    let hash = data
+        .strip_prefix("Transaction ")?
+        .strip_suffix(" doesn't exist")?;
+    Some(RpcTransactionError::UnknownTransaction {
+        requested_transaction_hash: hash.parse().ok()?,
+    })
+}
+
+/// Recognizes the string errors reported in the `data` field for blocks missing from the node.
+pub fn unknown_block_error(data: &serde_json::Value) -> Option<String> {
+    data.as_str()
+        .filter(|data| data.starts_with("DB Not Found Error: BLOCK"))
+        .map(str::to_string)
+}
+
+/// Recognizes the string errors of queries, besides unknown access keys.
+pub fn query_error(
+    error: &str,
+    block_height: BlockHeight,
+    block_hash: CryptoHash,
+) -> Option<RpcQueryError> {
+    let account_id = |prefix: &str, suffix: &str| {
+        error
+            .strip_prefix(prefix)?
+            .strip_suffix(suffix)?
+            .parse::<near_primitives::types::AccountId>()
+            .ok()
+    };
+
+    if let Some(requested_account_id) = account_id("account ", " does not exist while viewing") {
+        return Some(RpcQueryError::UnknownAccount {
+            requested_account_id,
+            block_height,
+            block_hash,
+        });
+    }
+    if let Some(contract_account_id) =
+        account_id("contract code of account ", " does not exist while viewing").or_else(|| {
+            account_id(
+                "Contract code for contract ID #",
+                " has never been observed on the node",
+            )
+        })
+    {
+        return Some(RpcQueryError::NoContractCode {
+            contract_account_id,
+            block_height,
+            block_hash,
+        });
+    }
+    if let Some(contract_account_id) =
+        account_id("State of contract ", " is too large to be viewed")
+    {
+        return Some(RpcQueryError::TooLargeContractState {
+            contract_account_id,
+            block_height,
+            block_hash,
+        });
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use near_jsonrpc_primitives::types::blocks::RpcBlockError;
+    use near_jsonrpc_primitives::types::transactions::RpcTransactionResponse;
+
+    #[test]
+    fn legacy_tx_responses_are_upgraded() -> Result<(), serde_json::Error> {
+        let response = RpcTransactionResponse::parse(json!({
+            "status": { "SuccessValue": "" },
+            "transaction": {
+                "signer_id": "alice.near",
+                "public_key": "ed25519:9KnjTjL6vVoM8heHvCcTgLZ67FwFkiLsNtknFAVsVvYY",
+                "nonce": 1,
+                "receiver_id": "bob.near",
+                "actions": [],
+                "signature": "ed25519:3s1dvZdQtcAjBksMHFrysqvF63wnyMHPA4owNQmCJZ2EBakZEKdtMsLqrHdKWQjJbSRN6kRknN2WdwSBLWGCokXj",
+                "hash": "11111111111111111111111111111111"
+            },
+            "transaction_outcome": {
+                "proof": [],
+                "block_hash": "11111111111111111111111111111111",
+                "id": "11111111111111111111111111111111",
+                "outcome": {
+                    "logs": [],
+                    "receipt_ids": [],
+                    "gas_burnt": 0,
+                    "tokens_burnt": "0",
+                    "executor_id": "alice.near",
+                    "status": { "SuccessValue": "" },
+                    "metadata": { "version": 1, "gas_profile": null }
+                }
+            },
+            "receipts_outcome": []
+        }))?;
+        assert_eq!(
+            response.final_execution_status,
+            TxExecutionStatus::ExecutedOptimistic
+        );
+        assert!(response.final_execution_outcome.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn legacy_string_errors_are_typed() {
+        assert!(matches!(
+            RpcTransactionError::parse_legacy_error(json!("Timeout")),
+            Some(Ok(RpcTransactionError::TimeoutError))
+        ));
+        assert!(matches!(
+            RpcTransactionError::parse_legacy_error(json!(
+                "Transaction 11111111111111111111111111111111 doesn't exist"
+            )),
+            Some(Ok(RpcTransactionError::UnknownTransaction { .. }))
+        ));
+        assert!(matches!(
+            RpcBlockError::parse_legacy_error(json!(
+                "DB Not Found Error: BLOCK HEIGHT: 1 \n Cause: Unknown"
+            )),
+            Some(Ok(RpcBlockError::UnknownBlock { .. }))
+        ));
+        assert!(matches!(
+            query_error(
+                "account alice.near does not exist while viewing",
+                1,
+                CryptoHash::default()
+            ),
+            Some(RpcQueryError::UnknownAccount {
+                block_height: 1,
+                ..
+            })
+        ));
+        assert!(query_error("wasm execution failed", 1, CryptoHash::default()).is_none());
+    }
+}
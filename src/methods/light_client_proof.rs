@@ -34,12 +34,6 @@ pub use near_jsonrpc_primitives::types::light_client::{
 
 impl RpcHandlerResponse for RpcLightClientExecutionProofResponse {}
 
-impl RpcHandlerError for RpcLightClientProofError {
-    fn parse(value: serde_json::Value) -> Result<Self, serde_json::Error> {
-        common::parse_unknown_block!(value => Self)
-    }
-}
-
 impl RpcMethod for RpcLightClientExecutionProofRequest {
     type Response = RpcLightClientExecutionProofResponse;
     type Error = RpcLightClientProofError;
@@ -0,0 +1,50 @@
+//! Returns the maintenance windows of a validator in the current epoch.
+//!
+//! A maintenance window is a range of block heights during which the validator isn't expected to
+//! produce blocks or chunks, so it can be safely restarted.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::{methods, JsonRpcClient};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.mainnet.near.org");
+//!
+//! let request = methods::maintenance_windows::RpcMaintenanceWindowsRequest {
+//!     account_id: "aurora.pool.near".parse()?,
+//! };
+//!
+//! let response = client.call(request).await?;
+//!
+//! for (start, end) in response {
+//!     println!("safe to restart from block #{} to #{}", start, end);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use super::*;
+
+pub use near_jsonrpc_primitives::types::maintenance::{
+    RpcMaintenanceWindowsError, RpcMaintenanceWindowsRequest, RpcMaintenanceWindowsResponse,
+};
+
+impl RpcHandlerResponse for RpcMaintenanceWindowsResponse {}
+
+impl RpcHandlerError for RpcMaintenanceWindowsError {}
+
+impl RpcMethod for RpcMaintenanceWindowsRequest {
+    type Response = RpcMaintenanceWindowsResponse;
+    type Error = RpcMaintenanceWindowsError;
+
+    fn method_name(&self) -> &str {
+        "maintenance_windows"
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        Ok(json!(self))
+    }
+}
+
+impl private::Sealed for RpcMaintenanceWindowsRequest {}
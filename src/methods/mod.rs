@@ -3,14 +3,32 @@ use std::io;
 
 use serde::Deserialize;
 use serde_json::json;
+use serde_json::value::RawValue;
 use thiserror::Error;
 
 mod private {
+    // unused as a bound once unsealed, but still implemented by every method of this crate
+    #[cfg_attr(feature = "unstable-custom-methods", allow(dead_code))]
     pub trait Sealed {}
+
+    #[cfg(not(feature = "unstable-custom-methods"))]
+    pub use Sealed as Bound;
+
+    /// With the `unstable-custom-methods` feature, any type can implement [`RpcMethod`](super::RpcMethod).
+    #[cfg(feature = "unstable-custom-methods")]
+    pub trait Bound {}
+
+    #[cfg(feature = "unstable-custom-methods")]
+    impl<T: ?Sized> Bound for T {}
 }
 
 /// A trait identifying valid NEAR JSON-RPC methods.
-pub trait RpcMethod: private::Sealed
+///
+/// This trait is sealed, methods not covered by this crate can be defined with the
+/// [`rpc_method!`](crate::rpc_method) macro. Enabling the `unstable-custom-methods` feature
+/// unseals it, letting other crates implement it directly. Its methods may change in minor
+/// releases, so implementations outside this crate aren't covered by semver guarantees.
+pub trait RpcMethod: private::Bound
 where
     Self::Response: RpcHandlerResponse,
     Self::Error: RpcHandlerError,
@@ -22,11 +40,48 @@ where
 
     fn params(&self) -> Result<serde_json::Value, io::Error>;
 
+    /// Serializes the parameters of this method straight into `writer`.
+    ///
+    /// By default, this writes out [`params`](Self::params). Methods with large parameters, like
+    /// signed transactions, override it to avoid building an intermediate `serde_json::Value`.
+    fn write_params(&self, writer: &mut dyn io::Write) -> Result<(), io::Error> {
+        serde_json::to_writer(writer, &self.params()?).map_err(Into::into)
+    }
+
+    /// Whether this call can be safely sent more than once.
+    ///
+    /// Retry and failover layers only resend calls for which this returns `true`, once the
+    /// server may have already received them. Methods that mutate state, like broadcasting a
+    /// transaction, return `false`.
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
+    /// The name of an equivalent method, for nodes that don't support this one.
+    ///
+    /// Experimental methods that have been stabilized under another name fall back to it, and
+    /// vice versa. See [`JsonRpcClientConnector::method_fallback`](crate::JsonRpcClientConnector::method_fallback).
+    fn fallback_method_name(&self) -> Option<&str> {
+        common::fallback_method_name(self.method_name())
+    }
+
+    /// The height of the block the method is called for, if it's pinned to one by height.
+    fn block_height(&self) -> Option<near_primitives::types::BlockHeight> {
+        None
+    }
+
     fn parse_handler_response(
         response: serde_json::Value,
     ) -> Result<Result<Self::Response, Self::Error>, serde_json::Error> {
         Self::Response::parse(response).map(Ok)
     }
+
+    /// Parses the raw result of a method call, without going through a `serde_json::Value`.
+    fn parse_raw_handler_response(
+        response: &RawValue,
+    ) -> Result<Result<Self::Response, Self::Error>, serde_json::Error> {
+        Self::Response::parse_raw(response).map(Ok)
+    }
 }
 
 impl<T> private::Sealed for &T where T: private::Sealed {}
@@ -45,11 +100,33 @@ where
         T::params(self)
     }
 
+    fn write_params(&self, writer: &mut dyn io::Write) -> Result<(), io::Error> {
+        T::write_params(self, writer)
+    }
+
+    fn is_idempotent(&self) -> bool {
+        T::is_idempotent(self)
+    }
+
+    fn fallback_method_name(&self) -> Option<&str> {
+        T::fallback_method_name(self)
+    }
+
+    fn block_height(&self) -> Option<near_primitives::types::BlockHeight> {
+        T::block_height(self)
+    }
+
     fn parse_handler_response(
         response: serde_json::Value,
     ) -> Result<Result<Self::Response, Self::Error>, serde_json::Error> {
         T::parse_handler_response(response)
     }
+
+    fn parse_raw_handler_response(
+        response: &RawValue,
+    ) -> Result<Result<Self::Response, Self::Error>, serde_json::Error> {
+        T::parse_raw_handler_response(response)
+    }
 }
 
 /// A trait identifying valid NEAR JSON-RPC method responses.
@@ -57,6 +134,13 @@ pub trait RpcHandlerResponse: serde::de::DeserializeOwned {
     fn parse(value: serde_json::Value) -> Result<Self, serde_json::Error> {
         serde_json::from_value(value)
     }
+
+    /// Parser for the raw JSON of a response.
+    ///
+    /// Implementations overriding [`RpcHandlerResponse::parse`] should override this as well.
+    fn parse_raw(value: &RawValue) -> Result<Self, serde_json::Error> {
+        common::from_raw(value)
+    }
 }
 
 /// A trait identifying valid NEAR JSON-RPC errors.
@@ -74,15 +158,24 @@ pub trait RpcHandlerError: serde::de::DeserializeOwned {
     fn parse_legacy_error(_error: serde_json::Value) -> Option<Result<Self, serde_json::Error>> {
         None
     }
+
+    /// Returns true if the block the method was called for isn't known to the node.
+    fn is_unknown_block(&self) -> bool {
+        false
+    }
 }
 
 pub mod block;
+pub mod block_effects;
 pub mod broadcast_tx_async;
 pub mod broadcast_tx_commit;
 pub mod chunk;
+pub mod client_config;
+pub mod custom;
 pub mod gas_price;
 pub mod health;
 pub mod light_client_proof;
+pub mod maintenance_windows;
 pub mod network_info;
 pub mod next_light_client_block;
 pub mod query;
@@ -95,20 +188,29 @@ pub mod validators;
 mod experimental;
 pub use experimental::EXPERIMENTAL_changes;
 pub use experimental::EXPERIMENTAL_changes_in_block;
+pub use experimental::EXPERIMENTAL_congestion_level;
 pub use experimental::EXPERIMENTAL_genesis_config;
+pub use experimental::EXPERIMENTAL_genesis_records;
+pub use experimental::EXPERIMENTAL_light_client_block_proof;
 pub use experimental::EXPERIMENTAL_protocol_config;
 pub use experimental::EXPERIMENTAL_receipt;
+pub use experimental::EXPERIMENTAL_split_storage_info;
 pub use experimental::EXPERIMENTAL_tx_status;
 pub use experimental::EXPERIMENTAL_validators_ordered;
 // ======== experimental ========
 
 // ======== any ========
-#[cfg(feature = "any")]
 mod any;
-#[cfg(feature = "any")]
-pub use any::{request as any, RpcAnyRequest};
+pub use any::{
+    request as any, request_with_parser as any_with_parser, RpcAnyRequest, RpcAnyWithParserRequest,
+};
 // ======== any ========
 
+// ======== tolerant ========
+mod tolerant;
+pub use tolerant::{request as tolerant, RpcTolerantRequest, Tolerant};
+// ======== tolerant ========
+
 // ======== sandbox ========
 #[cfg(feature = "sandbox")]
 mod sandbox;
@@ -133,9 +235,15 @@ pub use adversarial::adv_disable_header_sync;
 #[cfg(feature = "adversarial")]
 pub use adversarial::adv_disable_doomslug;
 
+#[cfg(feature = "adversarial")]
+pub use adversarial::adv_insert_invalid_transactions;
+
 #[cfg(feature = "adversarial")]
 pub use adversarial::adv_produce_blocks;
 
+#[cfg(feature = "adversarial")]
+pub use adversarial::adv_produce_chunks;
+
 #[cfg(feature = "adversarial")]
 pub use adversarial::adv_switch_to_height;
 
@@ -156,6 +264,93 @@ pub fn to_json<M: RpcMethod>(method: &M) -> Result<serde_json::Value, io::Error>
     Ok(json!(request_payload))
 }
 
+/// Serializes an RPC Method as a JSON RPC request straight into `writer`.
+///
+/// This produces the same request as [`to_json`], without any intermediate allocations.
+pub fn to_writer<M: RpcMethod>(method: &M, writer: impl io::Write) -> Result<(), io::Error> {
+    to_writer_with_id(method, &generate_request_id(), writer)
+}
+
+/// Serializes an RPC Method as a JSON RPC request with the specified id straight into `writer`.
+pub fn to_writer_with_id<M: RpcMethod>(
+    method: &M,
+    id: &str,
+    mut writer: impl io::Write,
+) -> Result<(), io::Error> {
+    writer.write_all(br#"{"jsonrpc":"2.0","method":"#)?;
+    serde_json::to_writer(&mut writer, method.method_name())?;
+    writer.write_all(br#","params":"#)?;
+    method.write_params(&mut writer)?;
+    writer.write_all(br#","id":"#)?;
+    serde_json::to_writer(&mut writer, id)?;
+    writer.write_all(b"}")
+}
+
+/// A method sent under another name, see [`RpcMethod::fallback_method_name`].
+pub(crate) struct Renamed<'a, M> {
+    pub method: &'a M,
+    pub method_name: &'a str,
+}
+
+impl<M> private::Sealed for Renamed<'_, M> {}
+
+impl<M: RpcMethod> RpcMethod for Renamed<'_, M> {
+    type Response = M::Response;
+    type Error = M::Error;
+
+    fn method_name(&self) -> &str {
+        self.method_name
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        self.method.params()
+    }
+
+    fn write_params(&self, writer: &mut dyn io::Write) -> Result<(), io::Error> {
+        self.method.write_params(writer)
+    }
+
+    fn is_idempotent(&self) -> bool {
+        self.method.is_idempotent()
+    }
+
+    fn fallback_method_name(&self) -> Option<&str> {
+        None
+    }
+
+    fn block_height(&self) -> Option<near_primitives::types::BlockHeight> {
+        self.method.block_height()
+    }
+
+    fn parse_handler_response(
+        response: serde_json::Value,
+    ) -> Result<Result<Self::Response, Self::Error>, serde_json::Error> {
+        M::parse_handler_response(response)
+    }
+
+    fn parse_raw_handler_response(
+        response: &RawValue,
+    ) -> Result<Result<Self::Response, Self::Error>, serde_json::Error> {
+        M::parse_raw_handler_response(response)
+    }
+}
+
+/// Returns true if the method submits a transaction to the network.
+pub(crate) fn submits_transaction(method_name: &str) -> bool {
+    matches!(
+        method_name,
+        "send_tx" | "broadcast_tx_async" | "broadcast_tx_commit"
+    )
+}
+
+/// Generates a random request id, like the ones used by [`to_json`].
+pub(crate) fn generate_request_id() -> String {
+    near_primitives::utils::generate_random_string(9)
+}
+
+#[cfg(feature = "legacy-compat")]
+mod legacy;
+
 mod common {
     use super::*;
 
@@ -179,12 +374,72 @@ mod common {
     }
     pub(crate) use _parse_unknown_block as parse_unknown_block;
 
+    /// Experimental methods and the stable methods that replaced them.
+    const METHOD_ALIASES: [(&str, &str); 3] = [
+        ("EXPERIMENTAL_tx_status", "tx"),
+        ("EXPERIMENTAL_changes_in_block", "block_effects"),
+        ("EXPERIMENTAL_changes", "changes"),
+    ];
+
+    pub fn block_height(
+        block_reference: &near_primitives::types::BlockReference,
+    ) -> Option<near_primitives::types::BlockHeight> {
+        match block_reference {
+            near_primitives::types::BlockReference::BlockId(
+                near_primitives::types::BlockId::Height(height),
+            ) => Some(*height),
+            _ => None,
+        }
+    }
+
+    pub fn fallback_method_name(method_name: &str) -> Option<&'static str> {
+        METHOD_ALIASES
+            .iter()
+            .find_map(|&(experimental, stable)| match method_name {
+                name if name == experimental => Some(stable),
+                name if name == stable => Some(experimental),
+                _ => None,
+            })
+    }
+
+    /// Deserializes raw JSON, with `simd-json` when the feature is enabled.
+    #[cfg(not(feature = "simd-json"))]
+    pub fn from_raw<T: serde::de::DeserializeOwned>(
+        value: &RawValue,
+    ) -> Result<T, serde_json::Error> {
+        serde_json::from_str(value.get())
+    }
+
+    /// Deserializes raw JSON, with `simd-json` when the feature is enabled.
+    #[cfg(feature = "simd-json")]
+    pub fn from_raw<T: serde::de::DeserializeOwned>(
+        value: &RawValue,
+    ) -> Result<T, serde_json::Error> {
+        // simd-json parses in place, so it needs its own copy of the input
+        let mut json = value.get().as_bytes().to_vec();
+        simd_json::serde::from_slice(&mut json).map_err(serde::de::Error::custom)
+    }
+
     pub fn serialize_signed_transaction(
         tx: &near_primitives::transaction::SignedTransaction,
     ) -> Result<String, io::Error> {
         Ok(near_primitives::serialize::to_base64(&borsh::to_vec(&tx)?))
     }
 
+    /// Writes a signed transaction as a base64 JSON string, without buffering its borsh encoding.
+    pub fn write_signed_transaction(
+        tx: &near_primitives::transaction::SignedTransaction,
+        writer: &mut dyn io::Write,
+    ) -> Result<(), io::Error> {
+        writer.write_all(b"\"")?;
+        let mut encoder = base64::write::EncoderWriter::new(
+            &mut *writer,
+            &base64::engine::general_purpose::STANDARD,
+        );
+        borsh::to_writer(&mut encoder, tx)?;
+        encoder.finish()?.write_all(b"\"")
+    }
+
     // adv_*
     #[cfg(feature = "adversarial")]
     impl RpcHandlerError for () {}
@@ -195,16 +450,20 @@ mod common {
         fn parse(_value: serde_json::Value) -> Result<Self, serde_json::Error> {
             Ok(())
         }
+
+        fn parse_raw(_value: &RawValue) -> Result<Self, serde_json::Error> {
+            Ok(())
+        }
     }
 
-    #[cfg(feature = "any")]
+    // any, call_raw
     impl RpcHandlerResponse for serde_json::Value {
         fn parse(value: serde_json::Value) -> Result<Self, serde_json::Error> {
             Ok(value)
         }
     }
 
-    #[cfg(feature = "any")]
+    // any, call_raw
     impl RpcHandlerError for serde_json::Value {
         fn parse(handler_error: serde_json::Value) -> Result<Self, serde_json::Error> {
             Ok(handler_error)
@@ -217,6 +476,10 @@ mod common {
     // broadcast_tx_commit, tx, EXPERIMENTAL_tx_status
     impl RpcHandlerError for near_jsonrpc_primitives::types::transactions::RpcTransactionError {
         fn parse_legacy_error(value: serde_json::Value) -> Option<Result<Self, serde_json::Error>> {
+            #[cfg(feature = "legacy-compat")]
+            if let Some(err) = legacy::tx_error(&value) {
+                return Some(Ok(err));
+            }
             match serde_json::from_value::<near_jsonrpc_primitives::errors::ServerError>(value) {
                 Ok(near_jsonrpc_primitives::errors::ServerError::TxExecutionError(
                     near_primitives::errors::TxExecutionError::InvalidTxError(context),
@@ -230,15 +493,46 @@ mod common {
     // health, status
     impl RpcHandlerError for near_jsonrpc_primitives::types::status::RpcStatusError {}
 
-    // EXPERIMENTAL_changes, EXPERIMENTAL_changes_in_block
+    // EXPERIMENTAL_changes, EXPERIMENTAL_changes_in_block, block_effects
     impl RpcHandlerError for near_jsonrpc_primitives::types::changes::RpcStateChangesError {
         fn parse(value: serde_json::Value) -> Result<Self, serde_json::Error> {
             parse_unknown_block!(value => Self)
         }
+
+        fn is_unknown_block(&self) -> bool {
+            matches!(self, Self::UnknownBlock { .. })
+        }
+    }
+
+    // EXPERIMENTAL_changes_in_block, block_effects
+    impl RpcHandlerResponse
+        for near_jsonrpc_primitives::types::changes::RpcStateChangesInBlockByTypeResponse
+    {
+    }
+
+    // light_client_proof, EXPERIMENTAL_light_client_block_proof
+    impl RpcHandlerError for near_jsonrpc_primitives::types::light_client::RpcLightClientProofError {
+        fn parse(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+            parse_unknown_block!(value => Self)
+        }
+
+        fn is_unknown_block(&self) -> bool {
+            matches!(self, Self::UnknownBlock { .. })
+        }
     }
 
     // send_tx
-    impl RpcHandlerResponse for near_jsonrpc_primitives::types::transactions::RpcTransactionResponse {}
+    impl RpcHandlerResponse for near_jsonrpc_primitives::types::transactions::RpcTransactionResponse {
+        #[cfg(feature = "legacy-compat")]
+        fn parse(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+            serde_json::from_value(legacy::tx_response(value))
+        }
+
+        #[cfg(feature = "legacy-compat")]
+        fn parse_raw(value: &RawValue) -> Result<Self, serde_json::Error> {
+            from_raw(value).or_else(|_| Self::parse(serde_json::from_str(value.get())?))
+        }
+    }
 
     // validators, EXPERIMENTAL_validators_ordered
     impl RpcHandlerError for near_jsonrpc_primitives::types::validator::RpcValidatorError {}
@@ -194,9 +194,24 @@ use super::*;
 
 pub use near_jsonrpc_primitives::types::query::{RpcQueryError, RpcQueryRequest, RpcQueryResponse};
 
+/// Responses of queries pinned to a single block.
+///
+/// See [`JsonRpcClient::query_pinned`](crate::JsonRpcClient::query_pinned).
+#[derive(Debug)]
+pub struct RpcPinnedQueryResponses {
+    /// Hash of the block all queries were made against.
+    pub block_hash: near_primitives::hash::CryptoHash,
+    /// Results of the queries, in the order they were requested.
+    pub responses: Vec<crate::MethodCallResult<RpcQueryResponse, RpcQueryError>>,
+}
+
 impl RpcHandlerResponse for RpcQueryResponse {}
 
-impl RpcHandlerError for RpcQueryError {}
+impl RpcHandlerError for RpcQueryError {
+    fn is_unknown_block(&self) -> bool {
+        matches!(self, Self::UnknownBlock { .. })
+    }
+}
 
 impl private::Sealed for RpcQueryRequest {}
 
@@ -212,56 +227,76 @@ impl RpcMethod for RpcQueryRequest {
         Ok(json!(self))
     }
 
+    fn block_height(&self) -> Option<near_primitives::types::BlockHeight> {
+        common::block_height(&self.block_reference)
+    }
+
     fn parse_handler_response(
         response: serde_json::Value,
     ) -> Result<Result<Self::Response, Self::Error>, serde_json::Error> {
-        match serde_json::from_value::<QueryResponse>(response)? {
-            QueryResponse::HandlerResponse(r) => Ok(Ok(r)),
-            QueryResponse::HandlerError(LegacyQueryError {
-                error,
-                block_height,
-                block_hash,
-            }) => {
-                let mut err_parts = error.split(' ');
-                let query_error = if let (
-                    Some("access"),
-                    Some("key"),
-                    Some(pk),
-                    Some("does"),
-                    Some("not"),
-                    Some("exist"),
-                    Some("while"),
-                    Some("viewing"),
-                    None,
-                ) = (
-                    err_parts.next(),
-                    err_parts.next(),
-                    err_parts.next(),
-                    err_parts.next(),
-                    err_parts.next(),
-                    err_parts.next(),
-                    err_parts.next(),
-                    err_parts.next(),
-                    err_parts.next(),
-                ) {
-                    let public_key = pk
-                        .parse::<near_crypto::PublicKey>()
-                        .map_err(serde::de::Error::custom)?;
-                    RpcQueryError::UnknownAccessKey {
-                        public_key,
-                        block_height,
-                        block_hash,
-                    }
-                } else {
-                    RpcQueryError::ContractExecutionError {
-                        vm_error: error,
-                        block_height,
-                        block_hash,
-                    }
-                };
-
-                Ok(Err(query_error))
-            }
+        parse_query_response(serde_json::from_value(response)?)
+    }
+
+    fn parse_raw_handler_response(
+        response: &RawValue,
+    ) -> Result<Result<Self::Response, Self::Error>, serde_json::Error> {
+        parse_query_response(common::from_raw(response)?)
+    }
+}
+
+fn parse_query_response(
+    response: QueryResponse,
+) -> Result<Result<RpcQueryResponse, RpcQueryError>, serde_json::Error> {
+    match response {
+        QueryResponse::HandlerResponse(r) => Ok(Ok(r)),
+        QueryResponse::HandlerError(LegacyQueryError {
+            error,
+            block_height,
+            block_hash,
+        }) => {
+            let mut err_parts = error.split(' ');
+            let query_error = if let (
+                Some("access"),
+                Some("key"),
+                Some(pk),
+                Some("does"),
+                Some("not"),
+                Some("exist"),
+                Some("while"),
+                Some("viewing"),
+                None,
+            ) = (
+                err_parts.next(),
+                err_parts.next(),
+                err_parts.next(),
+                err_parts.next(),
+                err_parts.next(),
+                err_parts.next(),
+                err_parts.next(),
+                err_parts.next(),
+                err_parts.next(),
+            ) {
+                let public_key = pk
+                    .parse::<near_crypto::PublicKey>()
+                    .map_err(serde::de::Error::custom)?;
+                RpcQueryError::UnknownAccessKey {
+                    public_key,
+                    block_height,
+                    block_hash,
+                }
+            } else {
+                #[cfg(feature = "legacy-compat")]
+                if let Some(query_error) = legacy::query_error(&error, block_height, block_hash) {
+                    return Ok(Err(query_error));
+                }
+                RpcQueryError::ContractExecutionError {
+                    vm_error: error,
+                    block_height,
+                    block_hash,
+                }
+            };
+
+            Ok(Err(query_error))
         }
     }
 }
@@ -284,6 +319,34 @@ struct LegacyQueryError {
 mod tests {
     use {super::*, crate::*};
 
+    #[test]
+    fn raw_legacy_error_is_parsed() -> Result<(), Box<dyn std::error::Error>> {
+        let response = r#"{
+            "error": "access key ed25519:9KnjTjL6vVoM8heHvCcTgLZ67FwFkiLsNtknFAVsVvYY does not exist while viewing",
+            "block_height": 63503911,
+            "block_hash": "8nDoeLmiP2CY8nYSsW3AuNrMk5RBeqaZWjmXNgYA8kQV"
+        }"#;
+
+        let raw_error = RpcQueryRequest::parse_raw_handler_response(
+            &serde_json::value::RawValue::from_string(response.to_string())?,
+        )?
+        .unwrap_err();
+
+        assert!(
+            matches!(
+                raw_error,
+                RpcQueryError::UnknownAccessKey {
+                    block_height: 63503911,
+                    ..
+                }
+            ),
+            "this is unexpected: {:#?}",
+            raw_error
+        );
+
+        Ok(())
+    }
+
     /// This test is to make sure the method executor treats `&RpcMethod`s the same as `RpcMethod`s.
     #[tokio::test]
     async fn test_unknown_method() -> Result<(), Box<dyn std::error::Error>> {
@@ -379,4 +442,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn unknown_blocks_are_pinned_by_height() -> Result<(), Box<dyn std::error::Error>> {
+        let mut request = RpcQueryRequest {
+            block_reference: near_primitives::types::BlockReference::BlockId(
+                near_primitives::types::BlockId::Height(63503911),
+            ),
+            request: near_primitives::views::QueryRequest::ViewAccount {
+                account_id: "miraclx.testnet".parse()?,
+            },
+        };
+        assert_eq!(request.block_height(), Some(63503911));
+
+        request.block_reference = near_primitives::types::BlockReference::latest();
+        assert_eq!(request.block_height(), None);
+
+        let err = RpcQueryError::parse(json!({
+            "name": "UNKNOWN_BLOCK",
+            "info": { "block_reference": { "block_id": 63503911 } }
+        }))?;
+        assert!(err.is_unknown_block());
+
+        Ok(())
+    }
 }
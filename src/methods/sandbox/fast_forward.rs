@@ -1,6 +1,11 @@
 //! Fast forwards a sandboxed node by a specific height.
 //!
-//! Fas forwarding allows one to skip to some point in the future and observe actions.
+//! Fast forwarding allows one to skip to some point in the future and observe actions.
+//!
+//! The call only returns once the node has reached the new height, so there's no separate
+//! method to check on the progress of a fast forward. The sandbox RPC of the node consists of
+//! this method and [`sandbox_patch_state`](crate::methods::sandbox_patch_state), everything
+//! else, like the current height, is available through the regular methods, e.g. [`status`](crate::methods::status).
 //!
 //! ## Example
 //!
@@ -45,6 +50,10 @@ impl RpcMethod for RpcSandboxFastForwardRequest {
     fn params(&self) -> Result<serde_json::Value, io::Error> {
         Ok(json!(self))
     }
+
+    fn is_idempotent(&self) -> bool {
+        false
+    }
 }
 
 impl private::Sealed for RpcSandboxFastForwardRequest {}
@@ -53,6 +53,10 @@ impl RpcMethod for RpcSandboxPatchStateRequest {
     fn params(&self) -> Result<serde_json::Value, io::Error> {
         Ok(json!(self))
     }
+
+    fn is_idempotent(&self) -> bool {
+        false
+    }
 }
 
 impl private::Sealed for RpcSandboxPatchStateRequest {}
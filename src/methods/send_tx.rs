@@ -78,6 +78,55 @@ impl RpcMethod for RpcSendTransactionRequest {
                 "wait_until": self.wait_until
         }))
     }
+
+    fn write_params(&self, writer: &mut dyn io::Write) -> Result<(), io::Error> {
+        writer.write_all(br#"{"signed_tx_base64":"#)?;
+        common::write_signed_transaction(&self.signed_transaction, writer)?;
+        writer.write_all(br#","wait_until":"#)?;
+        serde_json::to_writer(&mut *writer, &self.wait_until)?;
+        writer.write_all(b"}")
+    }
+
+    fn is_idempotent(&self) -> bool {
+        false
+    }
 }
 
 impl private::Sealed for RpcSendTransactionRequest {}
+
+#[cfg(test)]
+mod tests {
+    use near_crypto::{KeyType, Signature};
+    use near_primitives::transaction::{Transaction, TransactionV0};
+    use near_primitives::views::TxExecutionStatus;
+
+    use super::*;
+
+    #[test]
+    fn streamed_request_matches_json() -> Result<(), Box<dyn std::error::Error>> {
+        let request = RpcSendTransactionRequest {
+            signed_transaction: SignedTransaction::new(
+                Signature::empty(KeyType::ED25519),
+                Transaction::V0(TransactionV0 {
+                    signer_id: "alice.testnet".parse()?,
+                    public_key: near_crypto::PublicKey::empty(KeyType::ED25519),
+                    nonce: 1,
+                    receiver_id: "bob.testnet".parse()?,
+                    block_hash: Default::default(),
+                    actions: vec![],
+                }),
+            ),
+            wait_until: TxExecutionStatus::Final,
+        };
+
+        let mut streamed = Vec::new();
+        to_writer(&request, &mut streamed)?;
+        let mut streamed = serde_json::from_slice::<serde_json::Value>(&streamed)?;
+        let mut expected = to_json(&request)?;
+        streamed["id"].take();
+        expected["id"].take();
+
+        assert_eq!(streamed, expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+//! Tolerant parsing of method responses.
+//!
+//! Nodes may return enum variants or fields newer than the types this crate was built against,
+//! which fail to deserialize into the typed response or handler error of a method. Wrapping a
+//! method with [`tolerant()`](request) keeps such responses as raw JSON instead of failing the
+//! call with a parse error.
+use super::*;
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+
+/// Wrap a method, falling back to raw JSON for responses and handler errors that fail to parse.
+///
+/// ## Example
+///
+/// ```no_run
+/// use near_jsonrpc_client::{methods, JsonRpcClient};
+/// use near_jsonrpc_client::methods::Tolerant;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+///
+/// let request = methods::tolerant(methods::status::RpcStatusRequest);
+///
+/// match client.call(request).await? {
+///     Tolerant::Typed(status) => println!("latest block: {}", status.sync_info.latest_block_height),
+///     Tolerant::Raw(status) => println!("unrecognized status: {}", status),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn request<M: RpcMethod>(method: M) -> RpcTolerantRequest<M> {
+    RpcTolerantRequest { method }
+}
+
+/// A method whose response and handler error fall back to raw JSON when they fail to parse.
+#[derive(Debug)]
+pub struct RpcTolerantRequest<M> {
+    pub method: M,
+}
+
+/// A response or handler error, parsed into its type when possible.
+#[derive(Clone, Debug)]
+pub enum Tolerant<T> {
+    /// The value parsed into its type.
+    Typed(T),
+    /// The raw JSON of a value that failed to parse.
+    Raw(serde_json::Value),
+}
+
+impl<T> Tolerant<T> {
+    /// Get the parsed value, if parsing succeeded.
+    pub fn typed(&self) -> Option<&T> {
+        match self {
+            Tolerant::Typed(value) => Some(value),
+            Tolerant::Raw(_) => None,
+        }
+    }
+
+    /// Get the raw JSON, if parsing failed.
+    pub fn raw(&self) -> Option<&serde_json::Value> {
+        match self {
+            Tolerant::Typed(_) => None,
+            Tolerant::Raw(value) => Some(value),
+        }
+    }
+
+    fn or_raw(
+        value: serde_json::Value,
+        parse: impl FnOnce(serde_json::Value) -> Result<T, serde_json::Error>,
+    ) -> Self {
+        match parse(value.clone()) {
+            Ok(value) => Tolerant::Typed(value),
+            Err(err) => {
+                log::debug!("falling back to raw JSON: {}", err);
+                Tolerant::Raw(value)
+            }
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Tolerant<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tolerant::Typed(value) => fmt::Display::fmt(value, f),
+            Tolerant::Raw(value) => fmt::Display::fmt(value, f),
+        }
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Tolerant<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_json::Value::deserialize(deserializer)
+            .map(|value| Self::or_raw(value, serde_json::from_value))
+    }
+}
+
+impl<T: RpcHandlerResponse> RpcHandlerResponse for Tolerant<T> {
+    fn parse(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        Ok(Self::or_raw(value, T::parse))
+    }
+
+    fn parse_raw(value: &RawValue) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(value.get()).map(|value| Self::or_raw(value, T::parse))
+    }
+}
+
+impl<E: RpcHandlerError> RpcHandlerError for Tolerant<E> {
+    fn parse(handler_error: serde_json::Value) -> Result<Self, serde_json::Error> {
+        Ok(Self::or_raw(handler_error, E::parse))
+    }
+
+    fn parse_legacy_error(error: serde_json::Value) -> Option<Result<Self, serde_json::Error>> {
+        E::parse_legacy_error(error.clone()).map(|parsed| {
+            Ok(match parsed {
+                Ok(error) => Tolerant::Typed(error),
+                Err(_) => Tolerant::Raw(error),
+            })
+        })
+    }
+
+    fn is_unknown_block(&self) -> bool {
+        self.typed().map_or(false, E::is_unknown_block)
+    }
+}
+
+impl<M: RpcMethod> RpcMethod for RpcTolerantRequest<M> {
+    type Response = Tolerant<M::Response>;
+    type Error = Tolerant<M::Error>;
+
+    fn method_name(&self) -> &str {
+        self.method.method_name()
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        self.method.params()
+    }
+
+    fn write_params(&self, writer: &mut dyn io::Write) -> Result<(), io::Error> {
+        self.method.write_params(writer)
+    }
+
+    fn is_idempotent(&self) -> bool {
+        self.method.is_idempotent()
+    }
+
+    fn fallback_method_name(&self) -> Option<&str> {
+        self.method.fallback_method_name()
+    }
+
+    fn block_height(&self) -> Option<near_primitives::types::BlockHeight> {
+        self.method.block_height()
+    }
+
+    fn parse_handler_response(
+        response: serde_json::Value,
+    ) -> Result<Result<Self::Response, Self::Error>, serde_json::Error> {
+        Ok(match M::parse_handler_response(response.clone()) {
+            Ok(Ok(response)) => Ok(Tolerant::Typed(response)),
+            Ok(Err(err)) => Err(Tolerant::Typed(err)),
+            Err(err) => {
+                log::debug!("falling back to raw JSON: {}", err);
+                Ok(Tolerant::Raw(response))
+            }
+        })
+    }
+
+    fn parse_raw_handler_response(
+        response: &RawValue,
+    ) -> Result<Result<Self::Response, Self::Error>, serde_json::Error> {
+        match M::parse_raw_handler_response(response) {
+            Ok(Ok(response)) => Ok(Ok(Tolerant::Typed(response))),
+            Ok(Err(err)) => Ok(Err(Tolerant::Typed(err))),
+            Err(err) => {
+                log::debug!("falling back to raw JSON: {}", err);
+                serde_json::from_str(response.get()).map(|response| Ok(Tolerant::Raw(response)))
+            }
+        }
+    }
+}
+
+impl<M> private::Sealed for RpcTolerantRequest<M> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_responses_fall_back_to_raw() -> Result<(), serde_json::Error> {
+        type Request = RpcTolerantRequest<gas_price::RpcGasPriceRequest>;
+
+        let response = Request::parse_handler_response(json!({ "gas_price": "100" }))?;
+        assert!(matches!(response, Ok(Tolerant::Typed(_))));
+
+        let response = Request::parse_handler_response(json!({ "gas_price": "unknown" }))?;
+        assert_eq!(
+            response.ok().as_ref().and_then(Tolerant::raw),
+            Some(&json!({ "gas_price": "unknown" }))
+        );
+
+        let error = <Tolerant<gas_price::RpcGasPriceError>>::parse(json!({ "name": "NEW_ERROR" }))?;
+        assert_eq!(error.raw(), Some(&json!({ "name": "NEW_ERROR" })));
+
+        Ok(())
+    }
+}
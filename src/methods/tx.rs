@@ -89,3 +89,71 @@ impl RpcMethod for RpcTransactionStatusRequest {
 }
 
 impl private::Sealed for RpcTransactionStatusRequest {}
+
+/// Queries the status of a transaction given as borsh serialized bytes.
+///
+/// Useful with transactions signed by external builders or signers, which can be queried without
+/// deserializing them into a [`SignedTransaction`](near_primitives::transaction::SignedTransaction).
+#[derive(Debug)]
+pub struct RpcTransactionStatusBytesRequest {
+    /// The borsh serialized signed transaction.
+    pub signed_transaction: Vec<u8>,
+    pub wait_until: near_primitives::views::TxExecutionStatus,
+}
+
+impl RpcMethod for RpcTransactionStatusBytesRequest {
+    type Response = RpcTransactionResponse;
+    type Error = RpcTransactionError;
+
+    fn method_name(&self) -> &str {
+        "tx"
+    }
+
+    fn params(&self) -> Result<serde_json::Value, io::Error> {
+        Ok(json!({
+            "signed_tx_base64": near_primitives::serialize::to_base64(&self.signed_transaction),
+            "wait_until": self.wait_until
+        }))
+    }
+}
+
+impl private::Sealed for RpcTransactionStatusBytesRequest {}
+
+#[cfg(test)]
+mod tests {
+    use near_crypto::{KeyType, Signature};
+    use near_jsonrpc_primitives::types::transactions::SignedTransaction;
+    use near_primitives::transaction::{Transaction, TransactionV0};
+    use near_primitives::views::TxExecutionStatus;
+
+    use super::*;
+
+    #[test]
+    fn bytes_match_signed_transaction() -> Result<(), Box<dyn std::error::Error>> {
+        let signed_transaction = near_primitives::transaction::SignedTransaction::new(
+            Signature::empty(KeyType::ED25519),
+            Transaction::V0(TransactionV0 {
+                signer_id: "alice.testnet".parse()?,
+                public_key: near_crypto::PublicKey::empty(KeyType::ED25519),
+                nonce: 1,
+                receiver_id: "bob.testnet".parse()?,
+                block_hash: Default::default(),
+                actions: vec![],
+            }),
+        );
+
+        let bytes = RpcTransactionStatusBytesRequest {
+            signed_transaction: borsh::to_vec(&signed_transaction)?,
+            wait_until: TxExecutionStatus::Final,
+        };
+        let typed = RpcTransactionStatusRequest {
+            transaction_info: TransactionInfo::Transaction(SignedTransaction::SignedTransaction(
+                signed_transaction,
+            )),
+            wait_until: TxExecutionStatus::Final,
+        };
+
+        assert_eq!(bytes.params()?, typed.params()?);
+        Ok(())
+    }
+}
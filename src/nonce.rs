@@ -0,0 +1,236 @@
+//! Managing the nonces of access keys signing concurrent transactions.
+//!
+//! Viewing the access key before signing each transaction, as [`TransactionBuilder::sign`] does
+//! by default, races when several transactions are signed with the same key at once: they all
+//! get the same nonce, and all but one are rejected. A [`NonceManager`] fetches the nonce of each
+//! access key once, then hands out the following nonces locally.
+//!
+//! When a transaction is rejected with an `InvalidNonce` error anyway, for instance because the
+//! key was also used elsewhere, the manager resynchronizes with the nonce the node expects.
+//!
+//! Transactions signed with a nonce handed out locally reference the block hash of a
+//! [`BlockHashCache`], instead of fetching the latest block each time.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::nonce::NonceManager;
+//! use near_jsonrpc_client::JsonRpcClient;
+//! use near_primitives::transaction::{Action, TransferAction};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//! let nonces = NonceManager::new(client.clone());
+//!
+//! let signer = near_crypto::InMemorySigner::from_secret_key(
+//!     "fido.testnet".parse()?,
+//!     "ed25519:12dhevYshfiRqFSu8DSfxA27pTkmGRv6C5qQWTJYTcBEoB7MSTyidghi5NWXzWqrxCKgxVx97bpXPYQxYN5dieU".parse()?,
+//! );
+//!
+//! let transfers = ["alice.testnet", "bob.testnet"].map(|receiver_id| {
+//!     client
+//!         .transaction(signer.clone(), receiver_id.parse().unwrap())
+//!         .action(Action::Transfer(TransferAction { deposit: 1 }))
+//!         .nonce_manager(nonces.clone())
+//!         .send()
+//! });
+//!
+//! for response in futures_util::future::join_all(transfers).await {
+//!     println!("{:?}", response?.final_execution_outcome);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`TransactionBuilder::sign`]: crate::transaction::TransactionBuilder::sign
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use near_crypto::PublicKey;
+use near_primitives::types::{AccountId, Nonce};
+
+use crate::account::TransactionContext;
+use crate::block_hash::BlockHashCache;
+use crate::errors::{JsonRpcError, ViewError};
+use crate::methods::send_tx::RpcTransactionError;
+use crate::JsonRpcClient;
+
+type Key = (AccountId, PublicKey);
+
+/// The next nonce of an access key, if it was fetched.
+type Slot = Arc<tokio::sync::Mutex<Option<Nonce>>>;
+
+/// Hands out the nonces of access keys, fetching each once.
+///
+/// Clones share the same nonces.
+#[derive(Clone)]
+pub struct NonceManager {
+    client: JsonRpcClient,
+    nonces: Arc<Mutex<HashMap<Key, Slot>>>,
+    block_hashes: BlockHashCache,
+}
+
+impl NonceManager {
+    /// Create a manager fetching nonces with `client`.
+    pub fn new(client: JsonRpcClient) -> Self {
+        Self {
+            block_hashes: BlockHashCache::new(client.clone()),
+            client,
+            nonces: Default::default(),
+        }
+    }
+
+    /// Get the block hashes of the transactions from `block_hashes`, e.g. to share it with other
+    /// managers.
+    pub fn block_hash_cache(mut self, block_hashes: BlockHashCache) -> Self {
+        self.block_hashes = block_hashes;
+        self
+    }
+
+    /// Get the next nonce of the access key `public_key` of `account_id`, along with a recent
+    /// block hash, to sign a transaction with.
+    ///
+    /// The access key is only viewed the first time, or after [`resync`](Self::resync).
+    pub async fn transaction_context(
+        &self,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> Result<TransactionContext, ViewError> {
+        let slot = self.slot(account_id, public_key);
+        let mut next = slot.lock().await;
+        let nonce = match *next {
+            Some(nonce) => nonce,
+            None => {
+                let context = self
+                    .client
+                    .account(account_id.clone())
+                    .transaction_context(public_key.clone())
+                    .await?;
+                *next = Some(context.nonce + 1);
+                self.block_hashes.insert(context.block_hash);
+                return Ok(context);
+            }
+        };
+        *next = Some(nonce + 1);
+        drop(next);
+
+        Ok(TransactionContext {
+            nonce,
+            block_hash: self.block_hashes.get().await?,
+        })
+    }
+
+    /// Forget the nonce of the access key, so it's viewed again for the next transaction.
+    pub fn resync(&self, account_id: &AccountId, public_key: &PublicKey) {
+        self.nonces
+            .lock()
+            .unwrap()
+            .remove(&(account_id.clone(), public_key.clone()));
+    }
+
+    /// Resynchronize the nonce of the access key if `err` rejected a transaction signed with it
+    /// for an invalid nonce, or drop the cached block hash if the transaction expired, returning
+    /// true if either.
+    ///
+    /// The next nonce handed out is the one the node expects, unless it's already past it.
+    pub async fn observe(
+        &self,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+        err: &JsonRpcError<RpcTransactionError>,
+    ) -> bool {
+        if err.is_expired() {
+            self.block_hashes.invalidate();
+            return true;
+        }
+        let Some(expected) = err.expected_nonce() else {
+            return false;
+        };
+        let slot = self.slot(account_id, public_key);
+        let mut next = slot.lock().await;
+        *next = Some(next.map_or(expected, |next| next.max(expected)));
+        true
+    }
+
+    fn slot(&self, account_id: &AccountId, public_key: &PublicKey) -> Slot {
+        self.nonces
+            .lock()
+            .unwrap()
+            .entry((account_id.clone(), public_key.clone()))
+            .or_default()
+            .clone()
+    }
+}
+
+impl fmt::Debug for NonceManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NonceManager")
+            .field("client", &self.client)
+            .field("keys", &self.nonces.lock().unwrap().len())
+            .field("block_hashes", &self.block_hashes)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use near_crypto::{KeyType, SecretKey};
+    use near_primitives::errors::InvalidTxError;
+    use near_primitives::hash::CryptoHash;
+
+    use crate::errors::JsonRpcServerError;
+
+    #[tokio::test]
+    async fn invalid_nonces_resynchronize() {
+        let nonces = NonceManager::new(JsonRpcClient::connect("http://localhost:1"));
+        let account_id: AccountId = "alice.near".parse().unwrap();
+        let public_key = SecretKey::from_random(KeyType::ED25519).public_key();
+
+        let invalid_nonce = |ak_nonce| {
+            JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+                RpcTransactionError::InvalidTransaction {
+                    context: InvalidTxError::InvalidNonce {
+                        tx_nonce: 1,
+                        ak_nonce,
+                    },
+                },
+            ))
+        };
+
+        assert!(
+            nonces
+                .observe(&account_id, &public_key, &invalid_nonce(7))
+                .await
+        );
+        assert_eq!(*nonces.slot(&account_id, &public_key).lock().await, Some(8));
+
+        // a stale rejection doesn't move the nonce back
+        assert!(
+            nonces
+                .observe(&account_id, &public_key, &invalid_nonce(3))
+                .await
+        );
+        assert_eq!(*nonces.slot(&account_id, &public_key).lock().await, Some(8));
+
+        let timeout = JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+            RpcTransactionError::TimeoutError,
+        ));
+        assert!(!nonces.observe(&account_id, &public_key, &timeout).await);
+
+        nonces.block_hashes.insert(CryptoHash::default());
+        let expired = JsonRpcError::ServerError(JsonRpcServerError::HandlerError(
+            RpcTransactionError::InvalidTransaction {
+                context: InvalidTxError::Expired,
+            },
+        ));
+        assert!(nonces.observe(&account_id, &public_key, &expired).await);
+        assert!(nonces.block_hashes.get().await.is_err());
+
+        nonces.resync(&account_id, &public_key);
+        assert_eq!(*nonces.slot(&account_id, &public_key).lock().await, None);
+    }
+}
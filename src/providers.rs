@@ -0,0 +1,415 @@
+//! Presets for well-known RPC providers.
+//!
+//! A [`Provider`] describes an RPC endpoint: the network it serves, whether it's an archival
+//! node, and how it expects an API key to be sent. This module ships presets for well-known
+//! public endpoints, and a [`Registry`] to look them up by name, which can be extended with
+//! private endpoints.
+//!
+//! ## Example
+//!
+//! ```
+//! use near_jsonrpc_client::providers::{self, AuthScheme, Network, Provider, Registry};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = providers::FASTNEAR_MAINNET.connect(Some("my-api-key"))?;
+//!
+//! let mut registry = Registry::default();
+//! registry.register(
+//!     Provider::new("internal", Network::Mainnet, "https://rpc.internal.example.com")
+//!         .archival()
+//!         .auth(AuthScheme::ApiKey),
+//! );
+//!
+//! let provider = registry.find("internal", Network::Mainnet, true).unwrap();
+//! let client = provider.connect(Some("my-api-key"))?;
+//! # Ok(())
+//! # }
+//! ```
+use std::borrow::Cow;
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::errors::InvalidServerAddrError;
+use crate::header::InvalidHeaderValue;
+use crate::{auth, JsonRpcClient, JsonRpcClientConnector};
+
+/// A NEAR network.
+#[derive(Eq, Hash, Clone, Debug, PartialEq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    /// A local network, like the ones run by `neard localnet` or the sandbox.
+    Localnet,
+    /// Any other network, identified by its chain id.
+    Custom(String),
+}
+
+impl Network {
+    /// Get the network identified by a chain id, as reported by the `status` method.
+    pub fn from_chain_id(chain_id: &str) -> Self {
+        match chain_id {
+            "mainnet" => Network::Mainnet,
+            "testnet" => Network::Testnet,
+            "localnet" => Network::Localnet,
+            chain_id => Network::Custom(chain_id.to_string()),
+        }
+    }
+
+    /// The chain id of the network.
+    pub fn chain_id(&self) -> &str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+            Network::Localnet => "localnet",
+            Network::Custom(chain_id) => chain_id,
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.chain_id())
+    }
+}
+
+/// How a provider expects an API key to be sent.
+#[derive(Eq, Copy, Hash, Clone, Debug, PartialEq)]
+pub enum AuthScheme {
+    /// The provider is public, API keys are ignored.
+    None,
+    /// In the `x-api-key` header.
+    ApiKey,
+    /// In the `Authorization` header, as a bearer token.
+    Bearer,
+    /// As the last segment of the URL path.
+    UrlPath,
+}
+
+/// Potential errors returned while connecting to a [`Provider`].
+#[derive(Debug, Error)]
+pub enum ProviderError {
+    /// The address of the provider, including any API key in its path, is invalid.
+    #[error(transparent)]
+    InvalidServerAddr(#[from] InvalidServerAddrError),
+    /// The API key isn't a valid header value.
+    #[error("invalid API key: [{0}]")]
+    InvalidApiKey(#[from] InvalidHeaderValue),
+}
+
+/// An RPC endpoint.
+#[derive(Eq, Hash, Clone, Debug, PartialEq)]
+pub struct Provider {
+    name: Cow<'static, str>,
+    network: Network,
+    url: Cow<'static, str>,
+    archival: bool,
+    auth: AuthScheme,
+}
+
+const fn preset(
+    name: &'static str,
+    network: Network,
+    url: &'static str,
+    archival: bool,
+    auth: AuthScheme,
+) -> Provider {
+    Provider {
+        name: Cow::Borrowed(name),
+        network,
+        url: Cow::Borrowed(url),
+        archival,
+        auth,
+    }
+}
+
+pub const NEAR_MAINNET: Provider = preset(
+    "near",
+    Network::Mainnet,
+    crate::NEAR_MAINNET_RPC_URL,
+    false,
+    AuthScheme::None,
+);
+pub const NEAR_TESTNET: Provider = preset(
+    "near",
+    Network::Testnet,
+    crate::NEAR_TESTNET_RPC_URL,
+    false,
+    AuthScheme::None,
+);
+pub const NEAR_MAINNET_ARCHIVAL: Provider = preset(
+    "near",
+    Network::Mainnet,
+    crate::NEAR_MAINNET_ARCHIVAL_RPC_URL,
+    true,
+    AuthScheme::None,
+);
+pub const NEAR_TESTNET_ARCHIVAL: Provider = preset(
+    "near",
+    Network::Testnet,
+    crate::NEAR_TESTNET_ARCHIVAL_RPC_URL,
+    true,
+    AuthScheme::None,
+);
+pub const FASTNEAR_MAINNET: Provider = preset(
+    "fastnear",
+    Network::Mainnet,
+    "https://rpc.mainnet.fastnear.com",
+    false,
+    AuthScheme::Bearer,
+);
+pub const FASTNEAR_TESTNET: Provider = preset(
+    "fastnear",
+    Network::Testnet,
+    "https://rpc.testnet.fastnear.com",
+    false,
+    AuthScheme::Bearer,
+);
+pub const FASTNEAR_MAINNET_ARCHIVAL: Provider = preset(
+    "fastnear",
+    Network::Mainnet,
+    "https://archival-rpc.mainnet.fastnear.com",
+    true,
+    AuthScheme::Bearer,
+);
+pub const FASTNEAR_TESTNET_ARCHIVAL: Provider = preset(
+    "fastnear",
+    Network::Testnet,
+    "https://archival-rpc.testnet.fastnear.com",
+    true,
+    AuthScheme::Bearer,
+);
+pub const LAVA_MAINNET: Provider = preset(
+    "lava",
+    Network::Mainnet,
+    "https://near.lava.build",
+    false,
+    AuthScheme::None,
+);
+pub const LAVA_TESTNET: Provider = preset(
+    "lava",
+    Network::Testnet,
+    "https://neart.lava.build",
+    false,
+    AuthScheme::None,
+);
+
+/// All presets shipped with this crate.
+pub const PRESETS: [Provider; 10] = [
+    NEAR_MAINNET,
+    NEAR_TESTNET,
+    NEAR_MAINNET_ARCHIVAL,
+    NEAR_TESTNET_ARCHIVAL,
+    FASTNEAR_MAINNET,
+    FASTNEAR_TESTNET,
+    FASTNEAR_MAINNET_ARCHIVAL,
+    FASTNEAR_TESTNET_ARCHIVAL,
+    LAVA_MAINNET,
+    LAVA_TESTNET,
+];
+
+impl Provider {
+    /// Create a non-archival provider without authentication.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        network: Network,
+        url: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            network,
+            url: url.into(),
+            archival: false,
+            auth: AuthScheme::None,
+        }
+    }
+
+    /// A QuickNode endpoint, identified by the name of its subdomain.
+    ///
+    /// The token of the endpoint is passed as the API key when connecting.
+    pub fn quicknode(endpoint: &str, network: Network) -> Self {
+        let url = format!("https://{}.near-{}.quiknode.pro", endpoint, network);
+        Self::new("quicknode", network, url).auth(AuthScheme::UrlPath)
+    }
+
+    /// Mark the provider as serving an archival node.
+    pub fn archival(mut self) -> Self {
+        self.archival = true;
+        self
+    }
+
+    /// Set how the provider expects an API key to be sent.
+    pub fn auth(mut self, auth: AuthScheme) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn network(&self) -> &Network {
+        &self.network
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn is_archival(&self) -> bool {
+        self.archival
+    }
+
+    pub fn auth_scheme(&self) -> AuthScheme {
+        self.auth
+    }
+
+    /// Connect to the provider using the default connector.
+    ///
+    /// The API key, if any, is sent the way the provider expects it.
+    pub fn connect(&self, api_key: Option<&str>) -> Result<JsonRpcClient, ProviderError> {
+        self.connect_with(&crate::DEFAULT_CONNECTOR, api_key)
+    }
+
+    /// Connect to the provider using the specified connector.
+    pub fn connect_with(
+        &self,
+        connector: &JsonRpcClientConnector,
+        api_key: Option<&str>,
+    ) -> Result<JsonRpcClient, ProviderError> {
+        let api_key = match self.auth {
+            AuthScheme::None => None,
+            _ => api_key,
+        };
+        let client = match (self.auth, api_key) {
+            (AuthScheme::UrlPath, Some(api_key)) => {
+                let url = self.url.trim_end_matches('/');
+                connector.try_connect_redacted(
+                    &format!("{}/{}/", url, api_key),
+                    format!("{}/[redacted]/", url),
+                )?
+            }
+            _ => connector.try_connect(&*self.url)?,
+        };
+        Ok(match (self.auth, api_key) {
+            (AuthScheme::ApiKey, Some(api_key)) => client.header(auth::ApiKey::new(api_key)?),
+            (AuthScheme::Bearer, Some(api_key)) => {
+                client.header(auth::Authorization::bearer(api_key)?)
+            }
+            _ => client,
+        })
+    }
+}
+
+/// A set of providers, looked up by name.
+///
+/// The default registry holds all [`PRESETS`].
+#[derive(Clone, Debug)]
+pub struct Registry {
+    providers: Vec<Provider>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self {
+            providers: PRESETS.to_vec(),
+        }
+    }
+}
+
+impl Registry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self { providers: vec![] }
+    }
+
+    /// Add a provider to the registry.
+    ///
+    /// Replaces any provider with the same name, network and archival status.
+    pub fn register(&mut self, provider: Provider) -> &mut Self {
+        self.providers.retain(|existing| {
+            (&existing.name, &existing.network, existing.archival)
+                != (&provider.name, &provider.network, provider.archival)
+        });
+        self.providers.push(provider);
+        self
+    }
+
+    /// Find a provider by name, network and archival status.
+    pub fn find(&self, name: &str, network: Network, archival: bool) -> Option<&Provider> {
+        self.providers.iter().find(|provider| {
+            provider.name == name && provider.network == network && provider.archival == archival
+        })
+    }
+
+    /// Iterate over the providers serving the specified network.
+    pub fn network(&self, network: Network) -> impl Iterator<Item = &Provider> + '_ {
+        self.providers
+            .iter()
+            .filter(move |provider| provider.network == network)
+    }
+
+    /// Iterate over all providers.
+    pub fn iter(&self) -> impl Iterator<Item = &Provider> {
+        self.providers.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_keys_follow_provider_conventions() -> Result<(), Box<dyn std::error::Error>> {
+        let client = FASTNEAR_MAINNET.connect(Some("key"))?;
+        assert_eq!(client.headers()["authorization"], "Bearer key");
+
+        let client =
+            Provider::quicknode("weathered-frost", Network::Testnet).connect(Some("s3cr3t"))?;
+        assert_eq!(
+            client.server_url().map(url::Url::as_str),
+            Some("https://weathered-frost.near-testnet.quiknode.pro/s3cr3t/")
+        );
+        assert_eq!(
+            client.server_addr(),
+            "https://weathered-frost.near-testnet.quiknode.pro/[redacted]/"
+        );
+        assert!(!format!("{:?}", client).contains("s3cr3t"));
+        assert!(client.headers().is_empty());
+
+        let client = NEAR_TESTNET.connect(Some("key"))?;
+        assert!(client.headers().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn registered_providers_replace_presets() {
+        let mut registry = Registry::default();
+        registry.register(
+            Provider::new("near", Network::Mainnet, "https://rpc.internal.example.com").archival(),
+        );
+
+        let provider = registry.find("near", Network::Mainnet, true).unwrap();
+        assert_eq!(provider.url(), "https://rpc.internal.example.com");
+        assert_eq!(
+            registry.find("near", Network::Mainnet, false),
+            Some(&NEAR_MAINNET)
+        );
+        assert_eq!(registry.network(Network::Mainnet).count(), 5);
+    }
+
+    #[test]
+    fn networks_are_identified_by_chain_id() {
+        assert_eq!(Network::from_chain_id("mainnet"), Network::Mainnet);
+        assert_eq!(Network::from_chain_id("localnet"), Network::Localnet);
+        assert_eq!(
+            Network::from_chain_id("statelessnet"),
+            Network::Custom("statelessnet".to_string())
+        );
+        assert_eq!(
+            Network::Custom("statelessnet".to_string()).to_string(),
+            "statelessnet"
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! Proxy configuration.
+//!
+//! Requests can be routed through HTTP(S) or SOCKS5 proxies, with optional credentials,
+//! by adding a [`Proxy`] to a [`JsonRpcClientConnectorBuilder`](crate::JsonRpcClientConnectorBuilder).
+//!
+//! SOCKS5 proxies (`socks5://` and `socks5h://` URLs) require the `socks` feature. Use `socks5h`
+//! to have hostnames resolved by the proxy, e.g. when connecting through Tor.
+//!
+//! ## Example
+//!
+//! ```
+//! use near_jsonrpc_client::{proxy::Proxy, JsonRpcClient};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::builder()
+//!     .proxy(Proxy::all("http://proxy.corp.example.com:3128").basic_auth("user", "password"))
+//!     .build()?
+//!     .connect("https://rpc.testnet.near.org");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Proxies are only supported by the `reqwest` transport. Proxy URLs are validated when the
+//! connector is built, so any malformed URL is reported by [`build`](crate::JsonRpcClientConnectorBuilder::build).
+
+/// A proxy that requests are routed through.
+#[derive(Clone)]
+#[cfg_attr(not(feature = "reqwest"), allow(dead_code))]
+pub struct Proxy {
+    pub(crate) url: String,
+    pub(crate) scope: Scope,
+    pub(crate) auth: Option<(String, String)>,
+}
+
+#[derive(Eq, Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Scope {
+    All,
+    Http,
+    Https,
+}
+
+impl Proxy {
+    /// Route all requests through the proxy at `url`.
+    pub fn all(url: impl Into<String>) -> Self {
+        Self::new(url.into(), Scope::All)
+    }
+
+    /// Route plain `http` requests through the proxy at `url`.
+    pub fn http(url: impl Into<String>) -> Self {
+        Self::new(url.into(), Scope::Http)
+    }
+
+    /// Route `https` requests through the proxy at `url`.
+    pub fn https(url: impl Into<String>) -> Self {
+        Self::new(url.into(), Scope::Https)
+    }
+
+    fn new(url: String, scope: Scope) -> Self {
+        Self {
+            url,
+            scope,
+            auth: None,
+        }
+    }
+
+    /// Authenticate with the proxy using a username and password.
+    pub fn basic_auth(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.auth = Some((username.into(), password.into()));
+        self
+    }
+}
+
+impl std::fmt::Debug for Proxy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Proxy")
+            .field("url", &self.url)
+            .field("scope", &self.scope)
+            .field("auth", &self.auth.as_ref().map(|(username, _)| username))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_proxy_url_fails_to_build() {
+        let result = crate::JsonRpcClient::builder()
+            .proxy(Proxy::all("not a url"))
+            .build();
+
+        assert!(result.is_err());
+    }
+}
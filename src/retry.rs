@@ -0,0 +1,245 @@
+//! Retrying failed method calls.
+//!
+//! Retries are disabled by default. They can be enabled on a connector with a [`RetryPolicy`],
+//! and capped client-wide with a [`RetryBudget`] so an already degraded RPC endpoint isn't
+//! overwhelmed by a storm of retries.
+//!
+//! ## Example
+//!
+//! ```
+//! use near_jsonrpc_client::{retry::{RetryBudget, RetryPolicy}, JsonRpcClient};
+//!
+//! let budget = RetryBudget::new(0.2); // at most ~20% of requests may be retries
+//!
+//! let client = JsonRpcClient::new_client()
+//!     .retry_policy(RetryPolicy::new(3))
+//!     .retry_budget(budget.clone())
+//!     .connect("https://rpc.testnet.near.org");
+//!
+//! println!("{:?}", budget.usage());
+//! ```
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::errors::{
+    JsonRpcError, JsonRpcServerError, JsonRpcServerResponseStatusError, JsonRpcTransportRecvError,
+    JsonRpcTransportSendError, RpcTransportError,
+};
+
+/// Policy describing how many times, and how often a failed method call is retried.
+#[derive(Eq, Copy, Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a policy that retries a failed call up to `max_retries` times.
+    ///
+    /// Retries are spaced out with an exponential backoff, starting at 100ms and capped at 5s.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+
+    /// A policy that never retries.
+    pub fn never() -> Self {
+        Self::new(0)
+    }
+
+    /// Sets the delay before the first retry, it doubles with every subsequent retry.
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Sets the maximum delay between retries.
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Returns the maximum number of retries for a single call.
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Returns the delay before the specified retry (starting from 1).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::never()
+    }
+}
+
+const MILLI: u64 = 1000;
+
+/// Client-wide limit on the proportion of method calls that may be retries.
+///
+/// Every call deposits `ratio` of a token into the budget and every retry withdraws a whole token.
+/// When the budget runs dry, failed calls are returned as-is instead of being retried.
+///
+/// A budget is shared between all clones, so the same budget can be applied to multiple connectors.
+#[derive(Clone, Debug)]
+pub struct RetryBudget {
+    inner: Arc<RetryBudgetInner>,
+}
+
+#[derive(Debug)]
+struct RetryBudgetInner {
+    ratio: u64,
+    capacity: u64,
+    balance: AtomicU64,
+    requests: AtomicU64,
+    retries: AtomicU64,
+    rejected: AtomicU64,
+}
+
+/// A snapshot of the consumption of a [`RetryBudget`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RetryBudgetUsage {
+    /// Number of calls (excluding retries) that deposited into the budget.
+    pub requests: u64,
+    /// Number of retries that were allowed by the budget.
+    pub retries: u64,
+    /// Number of retries that were denied because the budget was exhausted.
+    pub rejected: u64,
+    /// Number of retries currently available.
+    pub available: f64,
+}
+
+impl RetryBudget {
+    /// Creates a budget allowing retries for up to `ratio` of all calls (e.g `0.2` for 20%).
+    ///
+    /// The budget starts with a reserve of 10 retries, which is also its minimum capacity.
+    pub fn new(ratio: f64) -> Self {
+        Self::with_reserve(ratio, 10)
+    }
+
+    /// Creates a budget allowing retries for up to `ratio` of all calls, starting with `reserve` retries.
+    pub fn with_reserve(ratio: f64, reserve: u32) -> Self {
+        let ratio = (ratio.max(0.0) * MILLI as f64) as u64;
+        let reserve = reserve as u64 * MILLI;
+        Self {
+            inner: Arc::new(RetryBudgetInner {
+                ratio,
+                capacity: reserve.max(ratio * 100),
+                balance: AtomicU64::new(reserve),
+                requests: AtomicU64::new(0),
+                retries: AtomicU64::new(0),
+                rejected: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Returns the current consumption of this budget.
+    pub fn usage(&self) -> RetryBudgetUsage {
+        RetryBudgetUsage {
+            requests: self.inner.requests.load(Ordering::Relaxed),
+            retries: self.inner.retries.load(Ordering::Relaxed),
+            rejected: self.inner.rejected.load(Ordering::Relaxed),
+            available: self.inner.balance.load(Ordering::Relaxed) as f64 / MILLI as f64,
+        }
+    }
+
+    pub(crate) fn deposit(&self) {
+        self.inner.requests.fetch_add(1, Ordering::Relaxed);
+        let _ = self
+            .inner
+            .balance
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |balance| {
+                Some((balance + self.inner.ratio).min(self.inner.capacity))
+            });
+    }
+
+    pub(crate) fn try_withdraw(&self) -> bool {
+        let withdrawn = self
+            .inner
+            .balance
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |balance| {
+                balance.checked_sub(MILLI)
+            })
+            .is_ok();
+        if withdrawn {
+            self.inner.retries.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.inner.rejected.fetch_add(1, Ordering::Relaxed);
+        }
+        withdrawn
+    }
+}
+
+/// Whether a failed call can be retried.
+///
+/// Calls that failed before reaching the server can always be retried, but other transient
+/// failures are only retried for idempotent methods since the server may have processed them.
+pub(crate) fn is_retryable<E>(err: &JsonRpcError<E>, idempotent: bool) -> bool {
+    match err {
+        JsonRpcError::TransportError(RpcTransportError::SendError(
+            JsonRpcTransportSendError::PayloadSendError(err),
+        )) => err.is_connect() || idempotent,
+        JsonRpcError::TransportError(RpcTransportError::RecvError(
+            JsonRpcTransportRecvError::PayloadRecvError(_),
+        )) => idempotent,
+        JsonRpcError::ServerError(JsonRpcServerError::ResponseStatusError(err)) => match err {
+            JsonRpcServerResponseStatusError::TooManyRequests { .. }
+            | JsonRpcServerResponseStatusError::ServiceUnavailable => true,
+            JsonRpcServerResponseStatusError::TimeoutError
+            | JsonRpcServerResponseStatusError::BadGateway { .. }
+            | JsonRpcServerResponseStatusError::GatewayTimeout { .. } => idempotent,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_limits_retries() {
+        let budget = RetryBudget::with_reserve(0.5, 1);
+
+        assert!(budget.try_withdraw());
+        assert!(!budget.try_withdraw());
+
+        budget.deposit();
+        budget.deposit();
+
+        assert!(budget.try_withdraw());
+        assert!(!budget.try_withdraw());
+
+        assert_eq!(
+            budget.usage(),
+            RetryBudgetUsage {
+                requests: 2,
+                retries: 2,
+                rejected: 2,
+                available: 0.0,
+            }
+        );
+    }
+
+    #[test]
+    fn backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy::new(5)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(350));
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+    }
+}
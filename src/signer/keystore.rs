@@ -0,0 +1,264 @@
+//! Loading keys from credential files, as saved by the NEAR CLIs.
+//!
+//! Credentials are looked up in `~/.near-credentials/<network>/`, either in
+//! `<account>.json` files, as saved by `near-cli`, or in `<account>/<public key>.json` files, as
+//! saved by `near-cli-rs`. Files are JSON objects holding the key in their `private_key`, or the
+//! legacy `secret_key` field, and files only holding the secret key as text are also accepted.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::signer::keystore;
+//! use near_jsonrpc_client::JsonRpcClient;
+//! use near_primitives::transaction::{Action, TransferAction};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let signer = keystore::load_credentials("testnet", &"fido.testnet".parse()?)?;
+//!
+//! client
+//!     .transaction(signer, "rpc_docs.testnet".parse()?)
+//!     .action(Action::Transfer(TransferAction { deposit: 1 }))
+//!     .send()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use near_crypto::{InMemorySigner, PublicKey, SecretKey};
+use near_primitives::types::AccountId;
+use serde::Deserialize;
+
+/// Potential errors returned while loading credentials.
+#[derive(Debug, thiserror::Error)]
+pub enum KeyStoreError {
+    /// The home directory, holding `.near-credentials`, couldn't be determined.
+    #[error("the home directory couldn't be determined")]
+    MissingHomeDirectory,
+    /// No credentials were found for the account.
+    #[error("no credentials found for {account_id} in {}", dir.display())]
+    NotFound { account_id: AccountId, dir: PathBuf },
+    /// The credential file couldn't be read.
+    #[error("error while reading {}: [{source}]", path.display())]
+    ReadError { path: PathBuf, source: io::Error },
+    /// The credential file is malformed.
+    #[error("malformed credential file {}: {reason}", path.display())]
+    InvalidKeyFile { path: PathBuf, reason: String },
+}
+
+#[derive(Deserialize)]
+struct KeyFile {
+    account_id: Option<AccountId>,
+    public_key: Option<PublicKey>,
+    #[serde(alias = "secret_key")]
+    private_key: SecretKey,
+}
+
+/// The directory the NEAR CLIs save credentials in, `~/.near-credentials`.
+pub fn credentials_dir() -> Result<PathBuf, KeyStoreError> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(|home| PathBuf::from(home).join(".near-credentials"))
+        .ok_or(KeyStoreError::MissingHomeDirectory)
+}
+
+/// Load the key of `account_id` on `network`, like `testnet`, from `~/.near-credentials`.
+pub fn load_credentials(
+    network: &str,
+    account_id: &AccountId,
+) -> Result<InMemorySigner, KeyStoreError> {
+    load_credentials_from(credentials_dir()?, network, account_id)
+}
+
+/// Load the key of `account_id` on `network` from the credentials directory `dir`.
+pub fn load_credentials_from(
+    dir: impl AsRef<Path>,
+    network: &str,
+    account_id: &AccountId,
+) -> Result<InMemorySigner, KeyStoreError> {
+    let dir = dir.as_ref().join(network);
+
+    let path = dir.join(format!("{}.json", account_id));
+    if path.is_file() {
+        return load_key_file_for(&path, Some(account_id));
+    }
+
+    let account_dir = dir.join(account_id.as_str());
+    let mut key_files = match fs::read_dir(&account_dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+            .collect::<Vec<_>>(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => vec![],
+        Err(source) => {
+            return Err(KeyStoreError::ReadError {
+                path: account_dir,
+                source,
+            })
+        }
+    };
+    key_files.sort();
+    match key_files.first() {
+        Some(path) => load_key_file_for(path, Some(account_id)),
+        None => Err(KeyStoreError::NotFound {
+            account_id: account_id.clone(),
+            dir,
+        }),
+    }
+}
+
+/// Load the key in the credential file at `path`.
+///
+/// The account is the one in the file, or else the name of the file, as in `<account>.json`.
+pub fn load_key_file(path: impl AsRef<Path>) -> Result<InMemorySigner, KeyStoreError> {
+    load_key_file_for(path.as_ref(), None)
+}
+
+fn load_key_file_for(
+    path: &Path,
+    account_id: Option<&AccountId>,
+) -> Result<InMemorySigner, KeyStoreError> {
+    let contents = fs::read_to_string(path).map_err(|source| KeyStoreError::ReadError {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let invalid = |reason: String| KeyStoreError::InvalidKeyFile {
+        path: path.to_path_buf(),
+        reason,
+    };
+
+    let key_file = match contents.trim() {
+        json if json.starts_with('{') => {
+            serde_json::from_str(json).map_err(|err| invalid(err.to_string()))?
+        }
+        secret_key => KeyFile {
+            account_id: None,
+            public_key: None,
+            private_key: secret_key
+                .parse()
+                .map_err(|err| invalid(format!("{}", err)))?,
+        },
+    };
+
+    if let Some(public_key) = &key_file.public_key {
+        if public_key != &key_file.private_key.public_key() {
+            return Err(invalid(
+                "the public key doesn't match the private key".to_string(),
+            ));
+        }
+    }
+
+    let account_id = match (key_file.account_id, account_id) {
+        (Some(found), Some(expected)) if &found != expected => {
+            return Err(invalid(format!(
+                "the key is for {}, not {}",
+                found, expected
+            )))
+        }
+        (Some(account_id), _) => account_id,
+        (None, Some(account_id)) => account_id.clone(),
+        (None, None) => path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse().ok())
+            .ok_or_else(|| invalid("the account is unknown".to_string()))?,
+    };
+
+    Ok(InMemorySigner::from_secret_key(
+        account_id,
+        key_file.private_key,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use near_crypto::KeyType;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!(
+            "near-jsonrpc-client-keystore-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn credentials_are_loaded() {
+        let dir = temp_dir("credentials");
+        let secret_key = SecretKey::from_random(KeyType::ED25519);
+        fs::create_dir_all(dir.join("testnet")).unwrap();
+        fs::write(
+            dir.join("testnet/alice.testnet.json"),
+            serde_json::json!({
+                "account_id": "alice.testnet",
+                "public_key": secret_key.public_key(),
+                "private_key": secret_key,
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let signer =
+            load_credentials_from(&dir, "testnet", &"alice.testnet".parse().unwrap()).unwrap();
+        assert_eq!(signer.account_id.as_str(), "alice.testnet");
+        assert_eq!(signer.secret_key, secret_key);
+
+        let err =
+            load_credentials_from(&dir, "mainnet", &"alice.testnet".parse().unwrap()).unwrap_err();
+        assert!(matches!(err, KeyStoreError::NotFound { .. }));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn legacy_key_files_are_loaded() {
+        let dir = temp_dir("legacy");
+        let secret_key = SecretKey::from_random(KeyType::ED25519);
+
+        fs::create_dir_all(dir.join("testnet/bob.testnet")).unwrap();
+        fs::write(
+            dir.join(format!(
+                "testnet/bob.testnet/{}.json",
+                secret_key.public_key()
+            )),
+            serde_json::json!({ "secret_key": secret_key }).to_string(),
+        )
+        .unwrap();
+        let signer =
+            load_credentials_from(&dir, "testnet", &"bob.testnet".parse().unwrap()).unwrap();
+        assert_eq!(signer.account_id.as_str(), "bob.testnet");
+        assert_eq!(signer.public_key, secret_key.public_key());
+
+        let path = dir.join("carol.testnet.json");
+        fs::write(&path, format!("{}\n", secret_key)).unwrap();
+        let signer = load_key_file(&path).unwrap();
+        assert_eq!(signer.account_id.as_str(), "carol.testnet");
+        assert_eq!(signer.secret_key, secret_key);
+
+        fs::write(
+            &path,
+            serde_json::json!({
+                "public_key": SecretKey::from_random(KeyType::ED25519).public_key(),
+                "private_key": secret_key,
+            })
+            .to_string(),
+        )
+        .unwrap();
+        assert!(matches!(
+            load_key_file(&path),
+            Err(KeyStoreError::InvalidKeyFile { .. })
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
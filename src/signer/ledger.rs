@@ -0,0 +1,345 @@
+//! Signing transactions on a Ledger hardware wallet, with the NEAR Ledger app.
+//!
+//! The [`LedgerSigner`] speaks the protocol of the NEAR app, over a [`LedgerTransport`] sending
+//! APDU commands to the device. The transport is left to the application, usually wrapping the
+//! HID transport of the `ledger-transport-hid` crate.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::errors::SignerError;
+//! use near_jsonrpc_client::signer::BoxFuture;
+//! use near_jsonrpc_client::signer::ledger::{HdPath, LedgerSigner, LedgerTransport};
+//! use near_jsonrpc_client::JsonRpcClient;
+//! use near_primitives::transaction::{Action, TransferAction};
+//!
+//! struct Hid;
+//!
+//! impl LedgerTransport for Hid {
+//!     fn exchange<'a>(&'a self, command: &'a [u8]) -> BoxFuture<'a, Result<Vec<u8>, SignerError>> {
+//!         Box::pin(async move {
+//!             // write the command to the device, and read its response
+//!             # unimplemented!()
+//!         })
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let signer = LedgerSigner::connect(Hid, "fido.testnet".parse()?, HdPath::default()).await?;
+//!
+//! client
+//!     .transaction(signer, "rpc_docs.testnet".parse()?)
+//!     .action(Action::Transfer(TransferAction { deposit: 1 }))
+//!     .send() // confirm the transaction on the device
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::{fmt, str::FromStr};
+
+use near_crypto::{ED25519PublicKey, KeyType, PublicKey, Signature};
+use near_primitives::types::AccountId;
+
+use super::{BoxFuture, TransactionSigner};
+use crate::errors::SignerError;
+
+const CLA: u8 = 0x80;
+const INS_SIGN_TRANSACTION: u8 = 0x02;
+const INS_GET_PUBLIC_KEY: u8 = 0x04;
+const P1_SIGN_NORMAL: u8 = 0x00;
+const P1_SIGN_NORMAL_LAST_CHUNK: u8 = 0x80;
+const P1_GET_PUBLIC_KEY_SILENT: u8 = 0x01;
+/// The network id the app expects, unused by the app besides being checked.
+const NETWORK_ID: u8 = b'W';
+const CHUNK_SIZE: usize = 250;
+const STATUS_OK: u16 = 0x9000;
+const HARDENED: u32 = 0x8000_0000;
+
+/// Sends APDU commands to a Ledger device.
+pub trait LedgerTransport: Send + Sync {
+    /// Send the `command` to the device, returning its response, status word included.
+    fn exchange<'a>(&'a self, command: &'a [u8]) -> BoxFuture<'a, Result<Vec<u8>, SignerError>>;
+}
+
+/// Potential errors returned by the NEAR app of a Ledger device.
+#[derive(Debug, thiserror::Error)]
+pub enum LedgerError {
+    /// The device responded with an error status, e.g. `0x6985` when the user rejected the
+    /// transaction, or `0x6e00` when the NEAR app isn't open.
+    #[error("the device responded with status 0x{0:04x}")]
+    Status(u16),
+    /// The device responded with a malformed response.
+    #[error("the device responded with a malformed response: {0}")]
+    InvalidResponse(String),
+}
+
+impl LedgerError {
+    /// Returns true if the user rejected the transaction on the device.
+    pub fn is_rejected(&self) -> bool {
+        matches!(self, Self::Status(0x6985))
+    }
+}
+
+/// A BIP-32 derivation path, like `44'/397'/0'/0'/1'`.
+#[derive(Eq, Clone, Debug, PartialEq)]
+pub struct HdPath(Vec<u32>);
+
+impl HdPath {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0
+            .iter()
+            .flat_map(|index| index.to_be_bytes())
+            .collect()
+    }
+}
+
+/// The default path of the NEAR app, `44'/397'/0'/0'/1'`.
+impl Default for HdPath {
+    fn default() -> Self {
+        Self(vec![
+            44 | HARDENED,
+            397 | HARDENED,
+            HARDENED,
+            HARDENED,
+            1 | HARDENED,
+        ])
+    }
+}
+
+impl FromStr for HdPath {
+    type Err = String;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let path = path.strip_prefix("m/").unwrap_or(path);
+        path.split('/')
+            .map(|index| {
+                let (index, hardened) = match index.strip_suffix('\'') {
+                    Some(index) => (index, HARDENED),
+                    None => (index, 0),
+                };
+                match index.parse::<u32>() {
+                    Ok(index) if index < HARDENED => Ok(index | hardened),
+                    _ => Err(format!("invalid derivation path: {}", path)),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl fmt::Display for HdPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (position, index) in self.0.iter().enumerate() {
+            if position > 0 {
+                f.write_str("/")?;
+            }
+            match index & HARDENED {
+                0 => write!(f, "{}", index)?,
+                _ => write!(f, "{}'", index & !HARDENED)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Signs transactions with the NEAR app of a Ledger device.
+pub struct LedgerSigner {
+    transport: Box<dyn LedgerTransport>,
+    hd_path: HdPath,
+    account_id: AccountId,
+    public_key: PublicKey,
+}
+
+impl LedgerSigner {
+    /// Get the public key at `hd_path` from the device, to sign transactions for `account_id`.
+    pub async fn connect(
+        transport: impl LedgerTransport + 'static,
+        account_id: AccountId,
+        hd_path: HdPath,
+    ) -> Result<Self, SignerError> {
+        let response = exchange(
+            &transport,
+            INS_GET_PUBLIC_KEY,
+            P1_GET_PUBLIC_KEY_SILENT,
+            &hd_path.to_bytes(),
+        )
+        .await?;
+        let public_key = ED25519PublicKey::try_from(response.as_slice())
+            .map_err(|err| LedgerError::InvalidResponse(err.to_string()))
+            .map_err(SignerError::new)?;
+        Ok(Self {
+            transport: Box::new(transport),
+            hd_path,
+            account_id,
+            public_key: public_key.into(),
+        })
+    }
+
+    /// The derivation path of the key on the device.
+    pub fn hd_path(&self) -> &HdPath {
+        &self.hd_path
+    }
+}
+
+impl TransactionSigner for LedgerSigner {
+    fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    fn sign<'a>(&'a self, message: &'a [u8]) -> BoxFuture<'a, Result<Signature, SignerError>> {
+        Box::pin(async move {
+            let mut payload = self.hd_path.to_bytes();
+            payload.extend_from_slice(message);
+
+            let mut chunks = payload.chunks(CHUNK_SIZE).peekable();
+            while let Some(chunk) = chunks.next() {
+                let last = chunks.peek().is_none();
+                let p1 = if last {
+                    P1_SIGN_NORMAL_LAST_CHUNK
+                } else {
+                    P1_SIGN_NORMAL
+                };
+                let response = exchange(&*self.transport, INS_SIGN_TRANSACTION, p1, chunk).await?;
+                if last {
+                    return Signature::from_parts(KeyType::ED25519, &response)
+                        .map_err(|err| LedgerError::InvalidResponse(err.to_string()))
+                        .map_err(SignerError::new);
+                }
+            }
+            unreachable!("the payload starts with the derivation path")
+        })
+    }
+}
+
+impl fmt::Debug for LedgerSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LedgerSigner")
+            .field("hd_path", &self.hd_path.to_string())
+            .field("account_id", &self.account_id)
+            .field("public_key", &self.public_key)
+            .finish()
+    }
+}
+
+/// Send a command to the NEAR app, returning the data of its response.
+async fn exchange(
+    transport: &dyn LedgerTransport,
+    ins: u8,
+    p1: u8,
+    data: &[u8],
+) -> Result<Vec<u8>, SignerError> {
+    let mut command = vec![CLA, ins, p1, NETWORK_ID, data.len() as u8];
+    command.extend_from_slice(data);
+
+    let mut response = transport.exchange(&command).await?;
+    if response.len() < 2 {
+        return Err(SignerError::new(LedgerError::InvalidResponse(
+            "missing status word".to_string(),
+        )));
+    }
+    let status = response.split_off(response.len() - 2);
+    match u16::from_be_bytes([status[0], status[1]]) {
+        STATUS_OK => Ok(response),
+        status => Err(SignerError::new(LedgerError::Status(status))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use near_crypto::SecretKey;
+    use near_primitives::hash::hash;
+
+    /// A device holding `secret_key` at the default path.
+    struct Device {
+        secret_key: SecretKey,
+        commands: Mutex<Vec<Vec<u8>>>,
+    }
+
+    impl LedgerTransport for Device {
+        fn exchange<'a>(
+            &'a self,
+            command: &'a [u8],
+        ) -> BoxFuture<'a, Result<Vec<u8>, SignerError>> {
+            let mut commands = self.commands.lock().unwrap();
+            commands.push(command.to_vec());
+            let mut response = match (command[1], command[2]) {
+                (INS_GET_PUBLIC_KEY, _) => self.secret_key.public_key().key_data().to_vec(),
+                (INS_SIGN_TRANSACTION, P1_SIGN_NORMAL_LAST_CHUNK) => {
+                    let payload = commands
+                        .iter()
+                        .filter(|command| command[1] == INS_SIGN_TRANSACTION)
+                        .flat_map(|command| command[5..].to_vec())
+                        .collect::<Vec<_>>();
+                    match self.secret_key.sign(hash(&payload[20..]).as_ref()) {
+                        Signature::ED25519(signature) => signature.to_bytes().to_vec(),
+                        Signature::SECP256K1(_) => unreachable!(),
+                    }
+                }
+                _ => vec![],
+            };
+            response.extend_from_slice(&STATUS_OK.to_be_bytes());
+            Box::pin(async move { Ok(response) })
+        }
+    }
+
+    #[test]
+    fn hd_paths_are_parsed() {
+        assert_eq!("44'/397'/0'/0'/1'".parse(), Ok(HdPath::default()));
+        assert_eq!(HdPath::default().to_string(), "44'/397'/0'/0'/1'");
+        assert_eq!(
+            "m/44'/397'/0'/0'/2".parse::<HdPath>().unwrap().to_bytes()[16..],
+            [0, 0, 0, 2]
+        );
+        assert!("44'/397'/x".parse::<HdPath>().is_err());
+    }
+
+    #[tokio::test]
+    async fn transactions_are_signed_in_chunks() {
+        let secret_key = SecretKey::from_random(KeyType::ED25519);
+        let device = Device {
+            secret_key: secret_key.clone(),
+            commands: Mutex::new(vec![]),
+        };
+        let signer =
+            LedgerSigner::connect(device, "alice.near".parse().unwrap(), HdPath::default())
+                .await
+                .unwrap();
+        assert_eq!(signer.public_key(), &secret_key.public_key());
+
+        let message = vec![7; 600];
+        let signature = signer.sign(&message).await.unwrap();
+        assert!(signature.verify(hash(&message).as_ref(), signer.public_key()));
+    }
+
+    #[tokio::test]
+    async fn rejections_are_reported() {
+        struct Rejecting;
+
+        impl LedgerTransport for Rejecting {
+            fn exchange<'a>(
+                &'a self,
+                _command: &'a [u8],
+            ) -> BoxFuture<'a, Result<Vec<u8>, SignerError>> {
+                Box::pin(async { Ok(vec![0x69, 0x85]) })
+            }
+        }
+
+        let err =
+            LedgerSigner::connect(Rejecting, "alice.near".parse().unwrap(), HdPath::default())
+                .await
+                .unwrap_err();
+        let err = err.get_ref().downcast_ref::<LedgerError>().unwrap();
+        assert!(err.is_rejected());
+    }
+}
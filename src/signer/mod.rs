@@ -0,0 +1,118 @@
+//! Signers of transactions.
+//!
+//! The high-level helpers of this crate, like the [`TransactionBuilder`], sign transactions with a
+//! [`TransactionSigner`]. Keys held in memory are signed with by a [`near_crypto::InMemorySigner`],
+//! while keys held elsewhere, like in a hardware wallet, a key management service or an MPC
+//! network, can be signed with by implementing the trait, or with a [`remote::RemoteSigner`].
+//!
+//! Not to be confused with the [`signing`](crate::signing) of requests to private RPC gateways.
+//!
+//! ## Example
+//!
+//! ```
+//! use near_crypto::{PublicKey, Signature};
+//! use near_jsonrpc_client::errors::SignerError;
+//! use near_jsonrpc_client::signer::{BoxFuture, TransactionSigner};
+//! use near_primitives::types::AccountId;
+//!
+//! struct Vault {
+//!     account_id: AccountId,
+//!     public_key: PublicKey,
+//! }
+//!
+//! impl TransactionSigner for Vault {
+//!     fn account_id(&self) -> &AccountId {
+//!         &self.account_id
+//!     }
+//!
+//!     fn public_key(&self) -> &PublicKey {
+//!         &self.public_key
+//!     }
+//!
+//!     fn sign<'a>(&'a self, message: &'a [u8]) -> BoxFuture<'a, Result<Signature, SignerError>> {
+//!         Box::pin(async move {
+//!             // send the message to the vault, and return its signature
+//!             Err(SignerError::new("the vault is sealed"))
+//!         })
+//!     }
+//! }
+//! ```
+//!
+//! [`TransactionBuilder`]: crate::transaction::TransactionBuilder
+use std::sync::Arc;
+
+use near_crypto::{InMemorySigner, PublicKey, Signature};
+use near_primitives::hash::hash;
+use near_primitives::types::AccountId;
+
+use crate::errors::SignerError;
+
+pub use futures_util::future::BoxFuture;
+
+pub mod keystore;
+#[cfg(feature = "ledger")]
+pub mod ledger;
+pub mod remote;
+
+/// Signs transactions on behalf of an account, with one of its access keys.
+pub trait TransactionSigner: Send + Sync {
+    /// The account the transactions are signed for.
+    fn account_id(&self) -> &AccountId;
+
+    /// The public key of the access key signing the transactions.
+    fn public_key(&self) -> &PublicKey;
+
+    /// Sign the `message`, the borsh serialization of a transaction.
+    ///
+    /// Transactions are signed by signing the SHA-256 hash of the message.
+    fn sign<'a>(&'a self, message: &'a [u8]) -> BoxFuture<'a, Result<Signature, SignerError>>;
+}
+
+impl TransactionSigner for InMemorySigner {
+    fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    fn sign<'a>(&'a self, message: &'a [u8]) -> BoxFuture<'a, Result<Signature, SignerError>> {
+        let signature = self.secret_key.sign(hash(message).as_ref());
+        Box::pin(async move { Ok(signature) })
+    }
+}
+
+impl<S: TransactionSigner + ?Sized> TransactionSigner for Arc<S> {
+    fn account_id(&self) -> &AccountId {
+        (**self).account_id()
+    }
+
+    fn public_key(&self) -> &PublicKey {
+        (**self).public_key()
+    }
+
+    fn sign<'a>(&'a self, message: &'a [u8]) -> BoxFuture<'a, Result<Signature, SignerError>> {
+        (**self).sign(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use near_crypto::{KeyType, SecretKey};
+
+    #[tokio::test]
+    async fn messages_are_signed_by_their_hash() {
+        let signer: Arc<dyn TransactionSigner> = Arc::new(InMemorySigner::from_secret_key(
+            "alice.near".parse().unwrap(),
+            SecretKey::from_random(KeyType::ED25519),
+        ));
+        assert_eq!(signer.account_id().as_str(), "alice.near");
+
+        let signature = signer.sign(b"transaction").await.unwrap();
+        assert!(signature.verify(hash(b"transaction").as_ref(), signer.public_key()));
+        assert!(!signature.verify(b"transaction", signer.public_key()));
+    }
+}
@@ -0,0 +1,211 @@
+//! Signing transactions with keys held elsewhere, like in a key management service, a signing
+//! microservice or an MPC network.
+//!
+//! A [`RemoteSigner`] delegates the signing of transactions to an async callback, or to a task
+//! receiving [`SignRequest`]s over a channel, giving up after a timeout.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use near_jsonrpc_client::errors::SignerError;
+//! use near_jsonrpc_client::signer::remote::RemoteSigner;
+//! use near_jsonrpc_client::JsonRpcClient;
+//! use near_primitives::transaction::{Action, TransferAction};
+//!
+//! # async fn kms_sign(message: Vec<u8>) -> Result<near_crypto::Signature, SignerError> { unimplemented!() }
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let signer = RemoteSigner::new(
+//!     "fido.testnet".parse()?,
+//!     "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp".parse()?,
+//!     |message| kms_sign(message),
+//! )
+//! .timeout(Duration::from_secs(10));
+//!
+//! client
+//!     .transaction(signer, "rpc_docs.testnet".parse()?)
+//!     .action(Action::Transfer(TransferAction { deposit: 1 }))
+//!     .send()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+use near_crypto::{PublicKey, Signature};
+use near_primitives::types::AccountId;
+use tokio::sync::{mpsc, oneshot};
+
+use super::{BoxFuture, TransactionSigner};
+use crate::errors::SignerError;
+
+type Callback = dyn Fn(Vec<u8>) -> BoxFuture<'static, Result<Signature, SignerError>> + Send + Sync;
+
+/// Potential errors returned while waiting on a remote signer.
+#[derive(Debug, thiserror::Error)]
+pub enum RemoteSignerError {
+    /// The signature wasn't returned before the timeout.
+    #[error("the signature wasn't returned within {0:?}")]
+    Timeout(Duration),
+    /// The task receiving the requests stopped, or dropped the request without responding.
+    #[error("the signing task is no longer running")]
+    Disconnected,
+}
+
+/// A request to sign a transaction, received by the task signing on behalf of a
+/// [`RemoteSigner`] created with [`RemoteSigner::from_channel`].
+#[derive(Debug)]
+pub struct SignRequest {
+    /// The borsh serialization of the transaction, see [`TransactionSigner::sign`].
+    pub message: Vec<u8>,
+    /// Where to send the signature.
+    pub respond_to: oneshot::Sender<Result<Signature, SignerError>>,
+}
+
+/// Signs transactions by delegating to an async callback.
+pub struct RemoteSigner {
+    account_id: AccountId,
+    public_key: PublicKey,
+    callback: Box<Callback>,
+    timeout: Option<Duration>,
+}
+
+impl RemoteSigner {
+    /// Sign transactions for `account_id` with the access key `public_key`, by calling `callback`
+    /// with each message to sign.
+    pub fn new<F, Fut>(account_id: AccountId, public_key: PublicKey, callback: F) -> Self
+    where
+        F: Fn(Vec<u8>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Signature, SignerError>> + Send + 'static,
+    {
+        Self {
+            account_id,
+            public_key,
+            callback: Box::new(move |message| Box::pin(callback(message))),
+            timeout: None,
+        }
+    }
+
+    /// Sign transactions for `account_id` with the access key `public_key`, by sending each
+    /// message to sign to the task receiving from `sender`.
+    pub fn from_channel(
+        account_id: AccountId,
+        public_key: PublicKey,
+        sender: mpsc::Sender<SignRequest>,
+    ) -> Self {
+        Self::new(account_id, public_key, move |message| {
+            let sender = sender.clone();
+            async move {
+                let (respond_to, response) = oneshot::channel();
+                sender
+                    .send(SignRequest {
+                        message,
+                        respond_to,
+                    })
+                    .await
+                    .map_err(|_| SignerError::new(RemoteSignerError::Disconnected))?;
+                response
+                    .await
+                    .map_err(|_| SignerError::new(RemoteSignerError::Disconnected))?
+            }
+        })
+    }
+
+    /// Give up on signing a transaction after `timeout`. Waits indefinitely by default.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+impl TransactionSigner for RemoteSigner {
+    fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    fn sign<'a>(&'a self, message: &'a [u8]) -> BoxFuture<'a, Result<Signature, SignerError>> {
+        let signature = (self.callback)(message.to_vec());
+        Box::pin(async move {
+            match self.timeout {
+                Some(timeout) => tokio::time::timeout(timeout, signature)
+                    .await
+                    .map_err(|_| SignerError::new(RemoteSignerError::Timeout(timeout)))?,
+                None => signature.await,
+            }
+        })
+    }
+}
+
+impl fmt::Debug for RemoteSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteSigner")
+            .field("account_id", &self.account_id)
+            .field("public_key", &self.public_key)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use near_crypto::{KeyType, SecretKey};
+    use near_primitives::hash::hash;
+
+    #[tokio::test]
+    async fn requests_are_signed_over_the_channel() {
+        let secret_key = SecretKey::from_random(KeyType::ED25519);
+        let (sender, mut receiver) = mpsc::channel::<SignRequest>(1);
+        let signer = RemoteSigner::from_channel(
+            "alice.near".parse().unwrap(),
+            secret_key.public_key(),
+            sender,
+        );
+
+        let key = secret_key.clone();
+        let task = tokio::spawn(async move {
+            while let Some(request) = receiver.recv().await {
+                let signature = key.sign(hash(&request.message).as_ref());
+                let _ = request.respond_to.send(Ok(signature));
+            }
+        });
+
+        let signature = signer.sign(b"transaction").await.unwrap();
+        assert!(signature.verify(hash(b"transaction").as_ref(), &secret_key.public_key()));
+
+        task.abort();
+        let _ = task.await;
+        let err = signer.sign(b"transaction").await.unwrap_err();
+        assert!(matches!(
+            err.get_ref().downcast_ref(),
+            Some(RemoteSignerError::Disconnected)
+        ));
+    }
+
+    #[tokio::test]
+    async fn slow_signers_time_out() {
+        let signer = RemoteSigner::new(
+            "alice.near".parse().unwrap(),
+            SecretKey::from_random(KeyType::ED25519).public_key(),
+            |_| futures_util::future::pending(),
+        )
+        .timeout(Duration::from_millis(10));
+
+        let err = signer.sign(b"transaction").await.unwrap_err();
+        assert!(matches!(
+            err.get_ref().downcast_ref(),
+            Some(RemoteSignerError::Timeout(_))
+        ));
+    }
+}
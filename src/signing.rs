@@ -0,0 +1,122 @@
+//! Request signing for private RPC gateways.
+//!
+//! Self-hosted gateways commonly authenticate clients by requiring every request body to be
+//! signed with a shared secret. With an [`HmacSigner`] configured on a connector, every request
+//! carries the hex encoded HMAC of its body in a header (`x-signature` by default).
+//!
+//! ## Example
+//!
+//! ```
+//! use near_jsonrpc_client::{header::HeaderName, signing::{HmacAlgorithm, HmacSigner}, JsonRpcClient};
+//!
+//! let signer = HmacSigner::new("shared-secret")
+//!     .header(HeaderName::from_static("x-gateway-signature"))
+//!     .algorithm(HmacAlgorithm::Sha512);
+//!
+//! let client = JsonRpcClient::new_client()
+//!     .sign_requests(signer)
+//!     .connect("https://archival.rpc.internal.example.com");
+//! ```
+use std::fmt;
+use std::sync::Arc;
+
+use hmac::{Hmac, Mac};
+use sha2::{Sha256, Sha512};
+
+use crate::header::{HeaderName, HeaderValue};
+
+/// Hash function used to compute request signatures.
+#[derive(Eq, Copy, Clone, Debug, PartialEq, Default)]
+pub enum HmacAlgorithm {
+    /// HMAC-SHA256.
+    #[default]
+    Sha256,
+    /// HMAC-SHA512.
+    Sha512,
+}
+
+/// Signs request bodies with an HMAC secret.
+#[derive(Clone)]
+pub struct HmacSigner {
+    secret: Arc<[u8]>,
+    header: HeaderName,
+    algorithm: HmacAlgorithm,
+}
+
+impl HmacSigner {
+    /// Create a signer with the specified secret.
+    ///
+    /// Signatures are computed with HMAC-SHA256 and sent in the `x-signature` header by default.
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            secret: secret.as_ref().into(),
+            header: HeaderName::from_static("x-signature"),
+            algorithm: HmacAlgorithm::default(),
+        }
+    }
+
+    /// Set the header the signature is sent in.
+    pub fn header(mut self, header: HeaderName) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Set the hash function used to compute signatures.
+    pub fn algorithm(mut self, algorithm: HmacAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Returns the hex encoded signature of `body`.
+    pub fn sign(&self, body: &[u8]) -> String {
+        match self.algorithm {
+            HmacAlgorithm::Sha256 => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret)
+                    .expect("HMAC can take a key of any size");
+                mac.update(body);
+                hex::encode(mac.finalize().into_bytes())
+            }
+            HmacAlgorithm::Sha512 => {
+                let mut mac = Hmac::<Sha512>::new_from_slice(&self.secret)
+                    .expect("HMAC can take a key of any size");
+                mac.update(body);
+                hex::encode(mac.finalize().into_bytes())
+            }
+        }
+    }
+
+    pub(crate) fn signature_header(&self, body: &[u8]) -> (HeaderName, HeaderValue) {
+        let signature =
+            HeaderValue::from_str(&self.sign(body)).expect("hex is always a valid header value");
+        (self.header.clone(), signature)
+    }
+}
+
+impl fmt::Debug for HmacSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacSigner")
+            .field("header", &self.header)
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs_with_rfc_4231_vectors() {
+        let signer = HmacSigner::new(b"Jefe");
+        let body = b"what do ya want for nothing?";
+
+        assert_eq!(
+            signer.sign(body),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            signer.algorithm(HmacAlgorithm::Sha512).sign(body),
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! Staking with staking pools.
+//!
+//! A [`StakingPool`] is derived from a [`JsonRpcClient`] with [`JsonRpcClient::staking_pool`]. It
+//! wraps the methods of the standard [staking pool contract](https://github.com/near/core-contracts/tree/master/staking-pool)
+//! delegators use: views of the balances of an account, and the actions depositing, staking,
+//! unstaking and withdrawing tokens, to be included in transactions sent to the pool.
+//!
+//! Unstaked tokens can only be withdrawn after a few epochs, once
+//! [`is_account_unstaked_balance_available`](StakingPool::is_account_unstaked_balance_available).
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::JsonRpcClient;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let signer = near_crypto::InMemorySigner::from_secret_key(
+//!     "fido.testnet".parse()?,
+//!     "ed25519:12dhevYshfiRqFSu8DSfxA27pTkmGRv6C5qQWTJYTcBEoB7MSTyidghi5NWXzWqrxCKgxVx97bpXPYQxYN5dieU".parse()?,
+//! );
+//!
+//! let pool = client.staking_pool("legends.pool.f863973.m0".parse()?);
+//!
+//! client
+//!     .transaction(signer, pool.id().clone())
+//!     .action(pool.deposit_and_stake(1_000_000_000_000_000_000_000_000)) // 1 NEAR
+//!     .send()
+//!     .await?;
+//!
+//! let staked = pool
+//!     .get_account_staked_balance(&"fido.testnet".parse()?)
+//!     .await?;
+//! println!("staked: {} yoctoNEAR", staked);
+//! # Ok(())
+//! # }
+//! ```
+use near_primitives::serialize::dec_format;
+use near_primitives::transaction::Action;
+use near_primitives::types::{AccountId, Balance, BlockReference, Gas};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::contract::ContractRef;
+use crate::errors::ViewError;
+use crate::JsonRpcClient;
+
+/// The gas attached to the calls to the pool, 50 TeraGas.
+const STAKING_GAS: Gas = 50_000_000_000_000;
+
+/// The balances of an account in a staking pool.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct PoolAccount {
+    /// The account.
+    pub account_id: AccountId,
+    /// The tokens of the account that aren't staked.
+    #[serde(with = "dec_format")]
+    pub unstaked_balance: Balance,
+    /// The tokens staked by the account.
+    #[serde(with = "dec_format")]
+    pub staked_balance: Balance,
+    /// Whether the unstaked tokens can be withdrawn.
+    pub can_withdraw: bool,
+}
+
+/// A balance, serialized as a decimal string.
+#[derive(Deserialize)]
+struct DecBalance(#[serde(with = "dec_format")] Balance);
+
+/// A handle on a staking pool.
+#[derive(Clone, Debug)]
+pub struct StakingPool {
+    contract: ContractRef,
+}
+
+impl StakingPool {
+    /// The account the pool is deployed to.
+    pub fn id(&self) -> &AccountId {
+        self.contract.id()
+    }
+
+    /// The contract of the pool, for the methods not wrapped here.
+    pub fn contract(&self) -> &ContractRef {
+        &self.contract
+    }
+
+    /// View the pool at the block `block_reference`.
+    pub fn at(mut self, block_reference: BlockReference) -> Self {
+        self.contract = self.contract.at(block_reference);
+        self
+    }
+
+    /// Get the tokens staked by `account_id`.
+    pub async fn get_account_staked_balance(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Balance, ViewError> {
+        self.view_balance("get_account_staked_balance", account_id)
+            .await
+    }
+
+    /// Get the tokens of `account_id` that aren't staked, either deposited or unstaked.
+    pub async fn get_account_unstaked_balance(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Balance, ViewError> {
+        self.view_balance("get_account_unstaked_balance", account_id)
+            .await
+    }
+
+    /// Get all the tokens of `account_id` in the pool, staked or not.
+    pub async fn get_account_total_balance(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Balance, ViewError> {
+        self.view_balance("get_account_total_balance", account_id)
+            .await
+    }
+
+    /// Returns true if the unstaked tokens of `account_id` can be withdrawn.
+    pub async fn is_account_unstaked_balance_available(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<bool, ViewError> {
+        Ok(self
+            .contract
+            .view(
+                "is_account_unstaked_balance_available",
+                json!({ "account_id": account_id }),
+            )
+            .await?
+            .result)
+    }
+
+    /// Get the balances of `account_id` at once.
+    pub async fn get_account(&self, account_id: &AccountId) -> Result<PoolAccount, ViewError> {
+        Ok(self
+            .contract
+            .view("get_account", json!({ "account_id": account_id }))
+            .await?
+            .result)
+    }
+
+    /// An action depositing `amount` to the pool, and staking it.
+    pub fn deposit_and_stake(&self, amount: Balance) -> Action {
+        self.contract
+            .call("deposit_and_stake", json!({}), STAKING_GAS, amount)
+    }
+
+    /// An action depositing `amount` to the pool, without staking it.
+    pub fn deposit(&self, amount: Balance) -> Action {
+        self.contract
+            .call("deposit", json!({}), STAKING_GAS, amount)
+    }
+
+    /// An action staking `amount` of the tokens deposited.
+    pub fn stake(&self, amount: Balance) -> Action {
+        self.call_with_amount("stake", amount)
+    }
+
+    /// An action unstaking `amount` of the tokens staked.
+    pub fn unstake(&self, amount: Balance) -> Action {
+        self.call_with_amount("unstake", amount)
+    }
+
+    /// An action unstaking all the tokens staked.
+    pub fn unstake_all(&self) -> Action {
+        self.contract.call("unstake_all", json!({}), STAKING_GAS, 0)
+    }
+
+    /// An action withdrawing `amount` of the unstaked tokens.
+    pub fn withdraw(&self, amount: Balance) -> Action {
+        self.call_with_amount("withdraw", amount)
+    }
+
+    /// An action withdrawing all the unstaked tokens.
+    pub fn withdraw_all(&self) -> Action {
+        self.contract
+            .call("withdraw_all", json!({}), STAKING_GAS, 0)
+    }
+
+    async fn view_balance(
+        &self,
+        method_name: &str,
+        account_id: &AccountId,
+    ) -> Result<Balance, ViewError> {
+        let balance = self
+            .contract
+            .view::<DecBalance>(method_name, json!({ "account_id": account_id }))
+            .await?;
+        Ok(balance.result.0)
+    }
+
+    fn call_with_amount(&self, method_name: &str, amount: Balance) -> Action {
+        self.contract.call(
+            method_name,
+            json!({ "amount": amount.to_string() }),
+            STAKING_GAS,
+            0,
+        )
+    }
+}
+
+impl JsonRpcClient {
+    /// Get a handle on the staking pool deployed to `pool_id`.
+    ///
+    /// See the [`staking`](crate::staking) module for details.
+    pub fn staking_pool(&self, pool_id: AccountId) -> StakingPool {
+        StakingPool {
+            contract: self.contract(pool_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use near_primitives::transaction::FunctionCallAction;
+
+    #[test]
+    fn amounts_are_decimal_strings() {
+        let pool = JsonRpcClient::connect("http://localhost:3030")
+            .staking_pool("pool.poolv1.near".parse().unwrap());
+
+        assert_eq!(
+            pool.unstake(u128::MAX),
+            Action::FunctionCall(Box::new(FunctionCallAction {
+                method_name: "unstake".to_string(),
+                args: format!(r#"{{"amount":"{}"}}"#, u128::MAX).into_bytes(),
+                gas: STAKING_GAS,
+                deposit: 0,
+            }))
+        );
+
+        let account: PoolAccount = serde_json::from_value(json!({
+            "account_id": "alice.near",
+            "unstaked_balance": "2",
+            "staked_balance": "1000000000000000000000000",
+            "can_withdraw": true,
+        }))
+        .unwrap();
+        assert_eq!(account.staked_balance, 1_000_000_000_000_000_000_000_000);
+
+        let balance: DecBalance = serde_json::from_value(json!("42")).unwrap();
+        assert_eq!(balance.0, 42);
+    }
+}
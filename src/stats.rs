@@ -0,0 +1,242 @@
+//! Per-method usage statistics.
+//!
+//! Every client keeps count of the requests it sends, the errors it receives and the bytes
+//! transferred, grouped by method name. This allows in-process accounting of RPC usage without
+//! an external metrics stack.
+//!
+//! ## Example
+//!
+//! ```
+//! use near_jsonrpc_client::{methods, JsonRpcClient};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let _ = client.call(methods::status::RpcStatusRequest).await;
+//!
+//! let stats = client.stats();
+//! assert_eq!(stats.method("status").map(|stats| stats.requests), Some(1));
+//! # }
+//! ```
+//!
+//! Calls slower than a threshold can also be reported as they happen, see
+//! [`JsonRpcClientConnector::slow_call_threshold`](crate::JsonRpcClientConnector::slow_call_threshold).
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Usage counters for a single method.
+#[derive(Eq, Copy, Clone, Debug, Default, PartialEq)]
+pub struct MethodStats {
+    /// Number of requests sent, including retries.
+    pub requests: u64,
+    /// Number of requests that resulted in an error.
+    pub errors: u64,
+    /// Number of request payload bytes sent.
+    pub bytes_sent: u64,
+    /// Number of response payload bytes received.
+    pub bytes_received: u64,
+}
+
+impl MethodStats {
+    fn merge(&mut self, other: &MethodStats) {
+        self.requests += other.requests;
+        self.errors += other.errors;
+        self.bytes_sent += other.bytes_sent;
+        self.bytes_received += other.bytes_received;
+    }
+}
+
+/// A snapshot of the usage statistics of a client.
+#[derive(Eq, Clone, Debug, Default, PartialEq)]
+pub struct ClientStats {
+    methods: BTreeMap<String, MethodStats>,
+}
+
+impl ClientStats {
+    /// Returns the usage counters for the specified method, if it has been called.
+    pub fn method(&self, method_name: &str) -> Option<&MethodStats> {
+        self.methods.get(method_name)
+    }
+
+    /// Returns an iterator over the usage counters of every called method, sorted by method name.
+    pub fn methods(&self) -> impl Iterator<Item = (&str, &MethodStats)> {
+        self.methods
+            .iter()
+            .map(|(method_name, stats)| (method_name.as_str(), stats))
+    }
+
+    /// Returns the usage counters summed across all methods.
+    pub fn total(&self) -> MethodStats {
+        self.methods
+            .values()
+            .fold(MethodStats::default(), |mut total, stats| {
+                total.merge(stats);
+                total
+            })
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StatsRecorder {
+    methods: Mutex<HashMap<String, MethodStats>>,
+}
+
+impl StatsRecorder {
+    pub(crate) fn record(&self, method_name: &str, usage: &MethodStats) {
+        let mut methods = self.methods.lock().unwrap();
+        match methods.get_mut(method_name) {
+            Some(stats) => stats.merge(usage),
+            None => {
+                methods.insert(method_name.to_string(), *usage);
+            }
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> ClientStats {
+        ClientStats {
+            methods: self
+                .methods
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(method_name, stats)| (method_name.clone(), *stats))
+                .collect(),
+        }
+    }
+}
+
+/// A method call that took longer than the configured threshold.
+#[derive(Copy, Clone, Debug)]
+pub struct SlowCall<'a> {
+    /// Name of the method called.
+    pub method_name: &'a str,
+    /// Address of the server the call was made to.
+    pub server_addr: &'a str,
+    /// Time taken by the call, including retries.
+    pub elapsed: Duration,
+    /// The threshold the call exceeded.
+    pub threshold: Duration,
+}
+
+type SlowCallCallback = Arc<dyn Fn(&SlowCall<'_>) + Send + Sync>;
+
+/// Reports calls that exceed a latency threshold.
+#[derive(Clone)]
+pub(crate) struct SlowCallWatch {
+    threshold: Duration,
+    callback: Option<SlowCallCallback>,
+}
+
+impl SlowCallWatch {
+    pub(crate) fn new(threshold: Duration, callback: Option<SlowCallCallback>) -> Self {
+        Self {
+            threshold,
+            callback,
+        }
+    }
+
+    pub(crate) fn observe(&self, method_name: &str, server_addr: &str, elapsed: Duration) {
+        if elapsed <= self.threshold {
+            return;
+        }
+        let call = SlowCall {
+            method_name,
+            server_addr,
+            elapsed,
+            threshold: self.threshold,
+        };
+        match &self.callback {
+            Some(callback) => callback(&call),
+            None => log::warn!(
+                "slow method call: {} to {} took {:?} (threshold {:?})",
+                call.method_name,
+                call.server_addr,
+                call.elapsed,
+                call.threshold
+            ),
+        }
+    }
+}
+
+impl fmt::Debug for SlowCallWatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlowCallWatch")
+            .field("threshold", &self.threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_are_aggregated_per_method() {
+        let recorder = StatsRecorder::default();
+        let usage = |errors, bytes| MethodStats {
+            requests: 1,
+            errors,
+            bytes_sent: bytes,
+            bytes_received: bytes * 2,
+        };
+
+        recorder.record("query", &usage(0, 10));
+        recorder.record("query", &usage(1, 20));
+        recorder.record("status", &usage(0, 5));
+
+        let stats = recorder.snapshot();
+
+        assert_eq!(
+            stats.method("query"),
+            Some(&MethodStats {
+                requests: 2,
+                errors: 1,
+                bytes_sent: 30,
+                bytes_received: 60,
+            })
+        );
+        assert_eq!(stats.method("block"), None);
+        assert_eq!(
+            stats.methods().map(|(name, _)| name).collect::<Vec<_>>(),
+            ["query", "status"]
+        );
+        assert_eq!(stats.total().requests, 3);
+        assert_eq!(stats.total().bytes_sent, 35);
+    }
+
+    #[test]
+    fn reports_calls_over_threshold() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let watch = SlowCallWatch::new(
+            Duration::from_secs(1),
+            Some(Arc::new({
+                let reported = reported.clone();
+                move |call: &SlowCall<'_>| {
+                    reported
+                        .lock()
+                        .unwrap()
+                        .push((call.method_name.to_string(), call.elapsed))
+                }
+            })),
+        );
+
+        watch.observe(
+            "block",
+            "https://rpc.testnet.near.org",
+            Duration::from_millis(500),
+        );
+        watch.observe(
+            "status",
+            "https://rpc.testnet.near.org",
+            Duration::from_secs(2),
+        );
+
+        assert_eq!(
+            *reported.lock().unwrap(),
+            [("status".to_string(), Duration::from_secs(2))]
+        );
+    }
+}
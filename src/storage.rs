@@ -0,0 +1,178 @@
+//! Storage management of contracts, as specified by [NEP-145](https://github.com/near/NEPs/blob/master/neps/nep-0145.md).
+//!
+//! Contracts implementing NEP-145, like most fungible tokens, require accounts to pay for the
+//! storage they use by registering with a `storage_deposit` call first. Transferring tokens to an
+//! account that isn't registered fails, which makes missing registrations the most common cause
+//! of failed fungible token transfers.
+//!
+//! The views and actions of the standard are available on any [`ContractRef`], along with
+//! [`ContractRef::storage_deposit_if_needed`], returning the `storage_deposit` call to add before
+//! a transfer when the receiver isn't registered yet.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::JsonRpcClient;
+//! use serde_json::json;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let signer = near_crypto::InMemorySigner::from_secret_key(
+//!     "fido.testnet".parse()?,
+//!     "ed25519:12dhevYshfiRqFSu8DSfxA27pTkmGRv6C5qQWTJYTcBEoB7MSTyidghi5NWXzWqrxCKgxVx97bpXPYQxYN5dieU".parse()?,
+//! );
+//!
+//! let token = client.contract("usdc.fakes.testnet".parse()?);
+//! let receiver_id = "rpc_docs.testnet".parse()?;
+//!
+//! let transfer = token.call(
+//!     "ft_transfer",
+//!     json!({ "receiver_id": receiver_id, "amount": "1000000" }),
+//!     10_000_000_000_000, // 10 TeraGas
+//!     1,                  // 1 yoctoNEAR
+//! );
+//!
+//! client
+//!     .transaction(signer, token.id().clone())
+//!     .actions(token.storage_deposit_if_needed(&receiver_id).await?)
+//!     .action(transfer)
+//!     .send()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+use near_primitives::serialize::dec_format;
+use near_primitives::transaction::Action;
+use near_primitives::types::{AccountId, Balance, Gas};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::contract::ContractRef;
+use crate::errors::ViewError;
+
+/// The gas attached to `storage_deposit` calls, 30 TeraGas.
+const STORAGE_DEPOSIT_GAS: Gas = 30_000_000_000_000;
+
+/// The storage balance of an account registered with a contract.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub struct StorageBalance {
+    /// The balance deposited for the storage of the account.
+    #[serde(with = "dec_format")]
+    pub total: Balance,
+    /// The part of the balance not used by the storage of the account yet.
+    #[serde(with = "dec_format")]
+    pub available: Balance,
+}
+
+/// The bounds of the storage balance of the accounts registered with a contract.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub struct StorageBalanceBounds {
+    /// The balance needed to register an account.
+    #[serde(with = "dec_format")]
+    pub min: Balance,
+    /// The most balance an account can deposit, if any.
+    #[serde(default, with = "dec_format")]
+    pub max: Option<Balance>,
+}
+
+impl ContractRef {
+    /// Get the bounds of the storage balance of the accounts registered with the contract.
+    pub async fn storage_balance_bounds(&self) -> Result<StorageBalanceBounds, ViewError> {
+        Ok(self.view("storage_balance_bounds", json!({})).await?.result)
+    }
+
+    /// Get the storage balance of `account_id`, or `None` if it isn't registered with the
+    /// contract.
+    pub async fn storage_balance_of(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Option<StorageBalance>, ViewError> {
+        Ok(self
+            .view("storage_balance_of", json!({ "account_id": account_id }))
+            .await?
+            .result)
+    }
+
+    /// An action depositing `deposit` for the storage of `account_id`, registering it if needed.
+    ///
+    /// With `registration_only`, only the balance needed to register the account is kept, and the
+    /// rest refunded. The action must be included in a transaction whose receiver is the
+    /// contract.
+    pub fn storage_deposit(
+        &self,
+        account_id: &AccountId,
+        deposit: Balance,
+        registration_only: bool,
+    ) -> Action {
+        self.call(
+            "storage_deposit",
+            json!({
+                "account_id": account_id,
+                "registration_only": registration_only,
+            }),
+            STORAGE_DEPOSIT_GAS,
+            deposit,
+        )
+    }
+
+    /// The action registering `account_id` with the contract, with the minimum storage balance,
+    /// if it isn't registered yet.
+    ///
+    /// Adding the action returned, if any, before a transfer to `account_id` makes sure it
+    /// doesn't fail for a missing registration.
+    pub async fn storage_deposit_if_needed(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Option<Action>, ViewError> {
+        if self.storage_balance_of(account_id).await?.is_some() {
+            return Ok(None);
+        }
+        let bounds = self.storage_balance_bounds().await?;
+        Ok(Some(self.storage_deposit(account_id, bounds.min, true)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use near_primitives::transaction::FunctionCallAction;
+
+    use crate::JsonRpcClient;
+
+    #[test]
+    fn storage_balances_are_parsed() {
+        let bounds: StorageBalanceBounds = serde_json::from_value(json!({
+            "min": "1250000000000000000000",
+            "max": null,
+        }))
+        .unwrap();
+        assert_eq!(bounds.min, 1_250_000_000_000_000_000_000);
+        assert_eq!(bounds.max, None);
+
+        let balance: Option<StorageBalance> = serde_json::from_value(json!({
+            "total": "1250000000000000000000",
+            "available": "0",
+        }))
+        .unwrap();
+        assert_eq!(balance.map(|balance| balance.available), Some(0));
+    }
+
+    #[test]
+    fn storage_deposits_register_accounts() {
+        let token =
+            JsonRpcClient::connect("http://localhost:3030").contract("token.near".parse().unwrap());
+        let action = token.storage_deposit(&"alice.near".parse().unwrap(), 125, true);
+        assert_eq!(
+            action,
+            Action::FunctionCall(Box::new(FunctionCallAction {
+                method_name: "storage_deposit".to_string(),
+                args: br#"{"account_id":"alice.near","registration_only":true}"#.to_vec(),
+                gas: STORAGE_DEPOSIT_GAS,
+                deposit: 125,
+            }))
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! OpenTelemetry instrumentation of method calls.
+//!
+//! Every request sent to the server is recorded as a client span, and the trace context is
+//! propagated to the server in the headers of the request (`traceparent`/`tracestate` with the
+//! W3C propagator), using the globally configured tracer provider and propagator.
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use opentelemetry::propagation::Injector;
+use opentelemetry::trace::{SpanKind, Status, TraceContextExt, Tracer};
+use opentelemetry::{global, Context, KeyValue};
+
+use crate::errors::{JsonRpcError, JsonRpcServerError};
+
+/// The span of a single request.
+pub(crate) struct RequestSpan {
+    cx: Context,
+}
+
+impl RequestSpan {
+    pub(crate) fn start(method_name: &str, server_addr: &str) -> Self {
+        let tracer = global::tracer("near-jsonrpc-client");
+        let span = tracer
+            .span_builder(method_name.to_string())
+            .with_kind(SpanKind::Client)
+            .with_attributes([
+                KeyValue::new("rpc.system", "jsonrpc"),
+                KeyValue::new("rpc.method", method_name.to_string()),
+                KeyValue::new("server.address", server_addr.to_string()),
+            ])
+            .start(&tracer);
+        Self {
+            cx: Context::current_with_span(span),
+        }
+    }
+
+    /// Inject the context of this span into the headers of the request.
+    pub(crate) fn inject(&self, headers: &mut HeaderMap) {
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&self.cx, &mut HeaderInjector(headers))
+        });
+    }
+
+    pub(crate) fn end<T, E>(self, result: &Result<T, JsonRpcError<E>>) {
+        let span = self.cx.span();
+        if let Err(err) = result {
+            let description = match err {
+                JsonRpcError::TransportError(err) => err.to_string(),
+                JsonRpcError::ServerError(JsonRpcServerError::HandlerError(_)) => {
+                    "handler error".to_string()
+                }
+                JsonRpcError::ServerError(_) => "server error".to_string(),
+            };
+            span.set_status(Status::error(description));
+        }
+        span.end();
+    }
+}
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Adaptive throttling based on the rate-limit headers returned by RPC providers.
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use http::header::{HeaderMap, RETRY_AFTER};
+
+use crate::errors::RateLimit;
+
+const RATELIMIT_LIMIT: &str = "x-ratelimit-limit";
+const RATELIMIT_REMAINING: &str = "x-ratelimit-remaining";
+const RATELIMIT_RESET: &str = "x-ratelimit-reset";
+
+/// Longest time a call is held back by the throttle.
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Tracks the quota advertised by the server, delaying calls to stay under it.
+#[derive(Debug, Default)]
+pub(crate) struct Throttle {
+    state: Mutex<ThrottleState>,
+}
+
+#[derive(Debug, Default)]
+struct ThrottleState {
+    /// No call should be sent before this instant.
+    not_before: Option<Instant>,
+    /// Minimum spacing between calls while the quota is running low.
+    pace: Option<Duration>,
+    last_call: Option<Instant>,
+}
+
+impl Throttle {
+    /// Reserves a slot for a call, returning how long it should wait before being sent.
+    pub(crate) fn delay(&self) -> Option<Duration> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let mut send_at = state.not_before.filter(|not_before| *not_before > now);
+        if let (Some(pace), Some(last_call)) = (state.pace, state.last_call) {
+            send_at = send_at.max(last_call.checked_add(pace).filter(|paced| *paced > now));
+        }
+        state.last_call = Some(send_at.unwrap_or(now));
+        send_at.map(|send_at| (send_at - now).min(MAX_DELAY))
+    }
+
+    /// Updates the tracked quota from the headers of a server response.
+    pub(crate) fn observe(&self, status: http::StatusCode, headers: &HeaderMap) {
+        let header = |name| header(headers, name);
+        let reset = header(RATELIMIT_RESET).and_then(parse_reset);
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+
+        if status == http::StatusCode::TOO_MANY_REQUESTS {
+            if let Some(wait) = header(RETRY_AFTER.as_str()).and_then(seconds).or(reset) {
+                state.not_before = Some(now + wait.min(MAX_DELAY));
+            }
+            return;
+        }
+
+        let remaining = match header(RATELIMIT_REMAINING) {
+            Some(remaining) => remaining,
+            None => return,
+        };
+        state.pace = None;
+        state.not_before = None;
+        if remaining < 1.0 {
+            state.not_before = reset.map(|reset| now + reset.min(MAX_DELAY));
+        } else if let (Some(limit), Some(reset)) = (header(RATELIMIT_LIMIT), reset) {
+            // spread the remaining quota evenly once less than 10% of it is left
+            if remaining < limit / 10.0 {
+                state.pace = Some(reset.div_f64(remaining).min(MAX_DELAY));
+            }
+        }
+    }
+}
+
+fn header(headers: &HeaderMap, name: &str) -> Option<f64> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|value| value.is_finite() && *value >= 0.0)
+}
+
+/// Parses the rate-limit headers of a response.
+pub(crate) fn rate_limit(headers: &HeaderMap) -> RateLimit {
+    RateLimit {
+        retry_after: header(headers, RETRY_AFTER.as_str()).and_then(seconds),
+        limit: header(headers, RATELIMIT_LIMIT).map(|limit| limit as u64),
+        remaining: header(headers, RATELIMIT_REMAINING).map(|remaining| remaining as u64),
+        reset: header(headers, RATELIMIT_RESET).and_then(parse_reset),
+    }
+}
+
+/// `x-ratelimit-reset` is either a number of seconds or a unix timestamp, depending on the provider.
+fn parse_reset(reset: f64) -> Option<Duration> {
+    if reset > 1_000_000_000.0 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        return seconds((reset - now).max(0.0));
+    }
+    seconds(reset)
+}
+
+/// Converts a number of seconds sent by the server, ignoring values too large for a `Duration`.
+fn seconds(seconds: f64) -> Option<Duration> {
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.parse().unwrap(), v.parse().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn exhausted_quota_delays_until_reset() {
+        let throttle = Throttle::default();
+        assert_eq!(throttle.delay(), None);
+
+        throttle.observe(
+            http::StatusCode::OK,
+            &headers(&[(RATELIMIT_REMAINING, "0"), (RATELIMIT_RESET, "2")]),
+        );
+        let delay = throttle.delay().expect("call should be delayed");
+        assert!(delay > Duration::from_secs(1) && delay <= Duration::from_secs(2));
+
+        throttle.observe(
+            http::StatusCode::OK,
+            &headers(&[(RATELIMIT_REMAINING, "100"), (RATELIMIT_RESET, "2")]),
+        );
+        assert_eq!(throttle.delay(), None);
+    }
+
+    #[test]
+    fn too_many_requests_honours_retry_after() {
+        let throttle = Throttle::default();
+
+        throttle.observe(
+            http::StatusCode::TOO_MANY_REQUESTS,
+            &headers(&[("retry-after", "3")]),
+        );
+        let delay = throttle.delay().expect("call should be delayed");
+        assert!(delay > Duration::from_secs(2) && delay <= Duration::from_secs(3));
+    }
+
+    #[test]
+    fn out_of_range_headers_are_ignored() {
+        let throttle = Throttle::default();
+
+        throttle.observe(
+            http::StatusCode::TOO_MANY_REQUESTS,
+            &headers(&[("retry-after", "1e20"), (RATELIMIT_RESET, "1e30")]),
+        );
+        assert_eq!(throttle.delay(), None);
+
+        throttle.observe(
+            http::StatusCode::OK,
+            &headers(&[(RATELIMIT_REMAINING, "0"), (RATELIMIT_RESET, "1e20")]),
+        );
+        assert_eq!(throttle.delay(), None);
+
+        throttle.observe(
+            http::StatusCode::OK,
+            &headers(&[
+                (RATELIMIT_LIMIT, "100"),
+                (RATELIMIT_REMAINING, "1"),
+                (RATELIMIT_RESET, "1e19"),
+            ]),
+        );
+        throttle.delay();
+        assert!(throttle.delay().unwrap() <= MAX_DELAY);
+    }
+
+    #[test]
+    fn rate_limit_headers_are_parsed() {
+        let rate_limit = rate_limit(&headers(&[
+            ("retry-after", "3"),
+            (RATELIMIT_LIMIT, "100"),
+            (RATELIMIT_REMAINING, "0"),
+            (RATELIMIT_RESET, "-1"),
+        ]));
+        assert_eq!(
+            rate_limit,
+            RateLimit {
+                retry_after: Some(Duration::from_secs(3)),
+                limit: Some(100),
+                remaining: Some(0),
+                reset: None,
+            }
+        );
+
+        let out_of_range = super::rate_limit(&headers(&[
+            ("retry-after", "1e20"),
+            (RATELIMIT_RESET, "1e20"),
+        ]));
+        assert_eq!(out_of_range.retry_after, None);
+        assert_eq!(out_of_range.reset, None);
+    }
+}
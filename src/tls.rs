@@ -0,0 +1,103 @@
+//! TLS configuration for connecting to private RPC gateways.
+//!
+//! Private deployments are commonly served with certificates signed by an internal CA, and may
+//! require clients to authenticate with a certificate of their own (mTLS). Both can be configured
+//! on a [`JsonRpcClientConnectorBuilder`](crate::JsonRpcClientConnectorBuilder) without giving up
+//! the defaults this crate applies to its web client.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::{tls, JsonRpcClient};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::builder()
+//!     .add_root_certificate(tls::Certificate::from_pem(std::fs::read("ca.pem")?))
+//!     .identity(tls::Identity::from_pem(
+//!         std::fs::read("client.pem")?,
+//!         std::fs::read("client.key")?,
+//!     ))
+//!     .build()?
+//!     .connect("https://rpc.internal.example.com");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Certificates are only parsed when the connector is built, so any malformed input is reported
+//! by [`build`](crate::JsonRpcClientConnectorBuilder::build).
+
+/// A root certificate, trusted in addition to the system's certificate store.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    not(any(feature = "native-tls", feature = "rustls-tls", feature = "hyper-tls")),
+    allow(dead_code)
+)]
+pub struct Certificate {
+    pub(crate) data: Vec<u8>,
+    pub(crate) encoding: Encoding,
+}
+
+#[derive(Eq, Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Encoding {
+    Pem,
+    Der,
+}
+
+impl Certificate {
+    /// Create a certificate from a PEM encoded certificate.
+    pub fn from_pem(pem: impl Into<Vec<u8>>) -> Self {
+        Self {
+            data: pem.into(),
+            encoding: Encoding::Pem,
+        }
+    }
+
+    /// Create a certificate from a DER encoded certificate.
+    pub fn from_der(der: impl Into<Vec<u8>>) -> Self {
+        Self {
+            data: der.into(),
+            encoding: Encoding::Der,
+        }
+    }
+}
+
+/// A client certificate and its private key, presented to servers that require mTLS.
+#[derive(Clone)]
+#[cfg_attr(
+    not(any(feature = "native-tls", feature = "rustls-tls", feature = "hyper-tls")),
+    allow(dead_code)
+)]
+pub struct Identity {
+    pub(crate) cert: Vec<u8>,
+    pub(crate) key: Vec<u8>,
+}
+
+impl Identity {
+    /// Create an identity from a PEM encoded certificate chain and a PEM encoded PKCS #8 private key.
+    pub fn from_pem(cert: impl Into<Vec<u8>>, key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            cert: cert.into(),
+            key: key.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for Identity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Identity").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_certificate_fails_to_build() {
+        let result = crate::JsonRpcClient::builder()
+            .add_root_certificate(Certificate::from_der(b"not a certificate".to_vec()))
+            .build();
+
+        assert!(result.is_err());
+    }
+}
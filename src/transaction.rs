@@ -0,0 +1,594 @@
+//! Building, signing and sending transactions.
+//!
+//! A [`TransactionBuilder`] is derived from a [`JsonRpcClient`] with
+//! [`JsonRpcClient::transaction`]. It collects the actions of a transaction, then views the
+//! access key of the signer for its nonce and a recent block hash, signs the transaction and
+//! sends it with the `send_tx` method.
+//!
+//! Already signed transactions can be sent with [`JsonRpcClient::send_and_wait`], waiting for
+//! them to execute past the timeout of the `send_tx` method.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::JsonRpcClient;
+//! use near_primitives::transaction::{Action, TransferAction};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let signer = near_crypto::InMemorySigner::from_secret_key(
+//!     "fido.testnet".parse()?,
+//!     "ed25519:12dhevYshfiRqFSu8DSfxA27pTkmGRv6C5qQWTJYTcBEoB7MSTyidghi5NWXzWqrxCKgxVx97bpXPYQxYN5dieU".parse()?,
+//! );
+//!
+//! let response = client
+//!     .transaction(signer, "rpc_docs.testnet".parse()?)
+//!     .action(Action::Transfer(TransferAction {
+//!         deposit: 1_000_000_000_000_000_000_000_000, // 1 NEAR
+//!     }))
+//!     .send()
+//!     .await?;
+//!
+//! println!("{:?}", response.final_execution_outcome);
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use near_primitives::transaction::{Action, SignedTransaction, Transaction, TransactionV0};
+use near_primitives::types::{AccountId, Gas};
+use near_primitives::views::TxExecutionStatus;
+
+use crate::account::TransactionContext;
+use crate::errors::{JsonRpcError, SendTransactionError, SignerError};
+use crate::failure::FunctionCallFailure;
+use crate::methods::send_tx::{
+    RpcSendTransactionRequest, RpcTransactionError, RpcTransactionResponse,
+};
+use crate::methods::tx::{RpcTransactionStatusRequest, TransactionInfo};
+use crate::nonce::NonceManager;
+use crate::signer::TransactionSigner;
+use crate::{Deadline, JsonRpcClient};
+
+/// How long to wait before polling again for a transaction the node doesn't know yet.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The most gas the function calls of a transaction can be attached altogether, 300 TeraGas.
+const MAX_GAS: Gas = 300_000_000_000_000;
+
+/// How long to wait before checking the congestion of a shard again.
+const CONGESTION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+impl JsonRpcClient {
+    /// Send a signed transaction with the `send_tx` method, then poll its status with the `tx`
+    /// method until it reaches the execution status `wait_until`, or `deadline` expires.
+    ///
+    /// The `send_tx` method gives up waiting on the transaction after a server-side timeout,
+    /// while the transaction may still execute. Once the deadline expires, this returns a
+    /// [`DeadlineExceeded`](crate::errors::RpcTransportError::DeadlineExceeded) error instead.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use near_jsonrpc_client::{Deadline, JsonRpcClient};
+    /// use near_primitives::views::TxExecutionStatus;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let signed_transaction: near_primitives::transaction::SignedTransaction = unimplemented!();
+    /// let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+    ///
+    /// let response = client
+    ///     .send_and_wait(
+    ///         signed_transaction,
+    ///         TxExecutionStatus::Final,
+    ///         Deadline::after(Duration::from_secs(60)),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_and_wait(
+        &self,
+        signed_transaction: SignedTransaction,
+        wait_until: TxExecutionStatus,
+        deadline: Deadline,
+    ) -> Result<RpcTransactionResponse, JsonRpcError<RpcTransactionError>> {
+        let deadline = self.deadline().map_or(deadline, |own| own.min(deadline));
+        let client = self.clone().with_deadline(deadline);
+
+        let transaction_info = TransactionInfo::TransactionId {
+            tx_hash: signed_transaction.get_hash(),
+            sender_account_id: signed_transaction.transaction.signer_id().clone(),
+        };
+        let mut response = client
+            .call(RpcSendTransactionRequest {
+                signed_transaction,
+                wait_until: wait_until.clone(),
+            })
+            .await;
+        loop {
+            match response {
+                Err(err) if is_pending(&err) => {
+                    if !matches!(err.handler_error(), Some(RpcTransactionError::TimeoutError)) {
+                        // the node returned right away, don't poll it in a busy loop
+                        let delay = deadline.budget(Some(POLL_INTERVAL)).unwrap_or_default();
+                        tokio::time::sleep(delay).await;
+                    }
+                    log::debug!("transaction still pending, polling its status: {}", err);
+                }
+                response => return response,
+            }
+            response = client
+                .call(RpcTransactionStatusRequest {
+                    transaction_info: transaction_info.clone(),
+                    wait_until: wait_until.clone(),
+                })
+                .await;
+        }
+    }
+}
+
+/// Returns true if `err` was raised for a transaction that may still execute.
+pub(crate) fn is_pending(err: &JsonRpcError<RpcTransactionError>) -> bool {
+    matches!(
+        err.handler_error(),
+        Some(
+            RpcTransactionError::TimeoutError
+                | RpcTransactionError::RequestRouted { .. }
+                | RpcTransactionError::UnknownTransaction { .. }
+        )
+    )
+}
+
+/// The response to a transaction sent by a [`TransactionBuilder`], along with the retries it took.
+#[derive(Debug)]
+pub struct SendOutcome {
+    /// The response to the last transaction sent.
+    pub response: RpcTransactionResponse,
+    /// How many times the transaction was resent after being rejected for an invalid nonce.
+    pub nonce_retries: u32,
+    /// The gas attached to the function calls altogether each time the transaction was resent
+    /// after running out of gas.
+    pub gas_bumps: Vec<Gas>,
+}
+
+/// A transaction being built, to be signed and sent.
+#[derive(Clone)]
+pub struct TransactionBuilder {
+    client: JsonRpcClient,
+    signer: Arc<dyn TransactionSigner>,
+    receiver_id: AccountId,
+    actions: Vec<Action>,
+    wait_until: TxExecutionStatus,
+    nonces: Option<NonceManager>,
+    nonce_retries: u32,
+    gas_retries: u32,
+    deadline: Option<Deadline>,
+    max_congestion: Option<f64>,
+    congestion_wait: Duration,
+}
+
+impl TransactionBuilder {
+    pub(crate) fn new(
+        client: JsonRpcClient,
+        signer: Arc<dyn TransactionSigner>,
+        receiver_id: AccountId,
+    ) -> Self {
+        Self {
+            client,
+            signer,
+            receiver_id,
+            actions: Vec::new(),
+            wait_until: TxExecutionStatus::ExecutedOptimistic,
+            nonces: None,
+            nonce_retries: 0,
+            gas_retries: 0,
+            deadline: None,
+            max_congestion: None,
+            congestion_wait: Duration::ZERO,
+        }
+    }
+
+    /// Add an action to the transaction.
+    pub fn action(mut self, action: Action) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Add actions to the transaction.
+    pub fn actions(mut self, actions: impl IntoIterator<Item = Action>) -> Self {
+        self.actions.extend(actions);
+        self
+    }
+
+    /// Wait for the transaction to reach the execution status `wait_until` when sending it.
+    ///
+    /// Defaults to [`TxExecutionStatus::ExecutedOptimistic`], same as the node.
+    pub fn wait_until(mut self, wait_until: TxExecutionStatus) -> Self {
+        self.wait_until = wait_until;
+        self
+    }
+
+    /// Get the nonce of the transaction from `nonces`, instead of viewing the access key of the
+    /// signer each time.
+    ///
+    /// See the [`nonce`](crate::nonce) module for details.
+    pub fn nonce_manager(mut self, nonces: NonceManager) -> Self {
+        self.nonces = Some(nonces);
+        self
+    }
+
+    /// Sign the transaction again with a fresh nonce and send it again, up to `max_retries`
+    /// times, when the node rejects it for an invalid nonce.
+    ///
+    /// Transactions aren't resent by default.
+    pub fn retry_invalid_nonce(mut self, max_retries: u32) -> Self {
+        self.nonce_retries = max_retries;
+        self
+    }
+
+    /// Sign the transaction again with twice the gas attached to its function calls and send it
+    /// again, up to `max_retries` times, when it fails for running out of the gas attached.
+    ///
+    /// The gas attached to the function calls altogether never goes past 300 TeraGas. Only
+    /// transactions executing their function calls, i.e. sent waiting for at least
+    /// [`TxExecutionStatus::ExecutedOptimistic`], are retried. The receipts created by the failed
+    /// transaction before running out of gas aren't reverted, so the function calls should be
+    /// safe to run again.
+    ///
+    /// Transactions aren't resent by default.
+    pub fn retry_out_of_gas(mut self, max_retries: u32) -> Self {
+        self.gas_retries = max_retries;
+        self
+    }
+
+    /// Keep waiting for the transaction to reach the execution status `wait_until` past the
+    /// timeout of the `send_tx` method, up until `deadline`.
+    ///
+    /// See [`JsonRpcClient::send_and_wait`].
+    pub fn deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Check the congestion level of the shard of the receiver before sending the transaction,
+    /// rejecting it with a [`Congested`](SendTransactionError::Congested) error above
+    /// `max_congestion`, from 0 to 1.
+    ///
+    /// See the [`congestion`](crate::congestion) module for details.
+    pub fn max_congestion(mut self, max_congestion: f64) -> Self {
+        self.max_congestion = Some(max_congestion);
+        self
+    }
+
+    /// Wait up to `max_wait` for the shard of the receiver to get below the
+    /// [`max_congestion`](Self::max_congestion), checking its congestion level again every few
+    /// seconds, before rejecting the transaction.
+    ///
+    /// The transaction is rejected right away by default.
+    pub fn wait_while_congested(mut self, max_wait: Duration) -> Self {
+        self.congestion_wait = max_wait;
+        self
+    }
+
+    /// Sign the transaction, using the next nonce of the access key of the signer, and a recent
+    /// block hash.
+    pub async fn sign(&self) -> Result<SignedTransaction, SendTransactionError> {
+        let context = match &self.nonces {
+            Some(nonces) => {
+                nonces
+                    .transaction_context(self.signer.account_id(), self.signer.public_key())
+                    .await
+            }
+            None => {
+                self.client
+                    .account(self.signer.account_id().clone())
+                    .transaction_context(self.signer.public_key().clone())
+                    .await
+            }
+        }
+        .map_err(SendTransactionError::AccessKeyError)?;
+        Ok(self.sign_with(context).await?)
+    }
+
+    /// Sign the transaction, then send it with the `send_tx` method.
+    ///
+    /// With a [`nonce_manager`](Self::nonce_manager), the nonce is resynchronized if the
+    /// transaction is rejected for an invalid nonce, and the cached block hash dropped if the
+    /// transaction expired.
+    ///
+    /// With a [`max_congestion`](Self::max_congestion), the congestion of the shard of the
+    /// receiver is checked first.
+    pub async fn send(self) -> Result<RpcTransactionResponse, SendTransactionError> {
+        Ok(self.send_with_retries().await?.response)
+    }
+
+    /// Same as [`send`](Self::send), also returning the retries it took.
+    pub async fn send_with_retries(mut self) -> Result<SendOutcome, SendTransactionError> {
+        self.check_congestion().await?;
+        let mut signed_transaction = self.sign().await?;
+        let mut nonce_retries = 0;
+        let mut gas_bumps = Vec::new();
+        loop {
+            let response = match self.deadline {
+                Some(deadline) => {
+                    self.client
+                        .send_and_wait(
+                            signed_transaction.clone(),
+                            self.wait_until.clone(),
+                            deadline,
+                        )
+                        .await
+                }
+                None => {
+                    self.client
+                        .call(RpcSendTransactionRequest {
+                            signed_transaction: signed_transaction.clone(),
+                            wait_until: self.wait_until.clone(),
+                        })
+                        .await
+                }
+            };
+            let err = match response {
+                Ok(response) => {
+                    if gas_bumps.len() < self.gas_retries as usize && is_out_of_gas(&response) {
+                        if let Some(gas) = self.bump_gas() {
+                            gas_bumps.push(gas);
+                            log::debug!(
+                                "transaction ran out of gas, resending with {} gas ({}/{})",
+                                gas,
+                                gas_bumps.len(),
+                                self.gas_retries
+                            );
+                            signed_transaction = self.sign().await?;
+                            continue;
+                        }
+                    }
+                    return Ok(SendOutcome {
+                        response,
+                        nonce_retries,
+                        gas_bumps,
+                    });
+                }
+                Err(err) => err,
+            };
+            if let Some(nonces) = &self.nonces {
+                nonces
+                    .observe(self.signer.account_id(), self.signer.public_key(), &err)
+                    .await;
+            }
+            match err.expected_nonce() {
+                Some(nonce) if nonce_retries < self.nonce_retries => {
+                    nonce_retries += 1;
+                    log::debug!(
+                        "transaction rejected for an invalid nonce, expected {}, resending ({}/{})",
+                        nonce,
+                        nonce_retries,
+                        self.nonce_retries
+                    );
+                    signed_transaction = match &self.nonces {
+                        Some(_) => self.sign().await?,
+                        None => {
+                            self.sign_with(TransactionContext {
+                                nonce,
+                                block_hash: *signed_transaction.transaction.block_hash(),
+                            })
+                            .await?
+                        }
+                    };
+                }
+                _ => return Err(err.into()),
+            }
+        }
+    }
+
+    /// Double the gas attached to the function calls, up to [`MAX_GAS`] altogether, returning the
+    /// gas now attached, or `None` if it can't be raised.
+    fn bump_gas(&mut self) -> Option<Gas> {
+        let calls = || {
+            self.actions.iter().filter_map(|action| match action {
+                Action::FunctionCall(call) => Some(call.gas),
+                _ => None,
+            })
+        };
+        let attached = calls().fold(0, Gas::saturating_add);
+        if attached == 0 || attached >= MAX_GAS {
+            return None;
+        }
+        let bumped = attached.saturating_mul(2).min(MAX_GAS);
+        for action in &mut self.actions {
+            if let Action::FunctionCall(call) = action {
+                call.gas = (call.gas as u128 * bumped as u128 / attached as u128) as Gas;
+            }
+        }
+        Some(bumped)
+    }
+
+    async fn check_congestion(&self) -> Result<(), SendTransactionError> {
+        let Some(max_congestion) = self.max_congestion else {
+            return Ok(());
+        };
+        let started = tokio::time::Instant::now();
+        let mut congestion = self
+            .client
+            .congestion_level(&self.receiver_id)
+            .await
+            .map_err(SendTransactionError::CongestionError)?;
+        loop {
+            if congestion.congestion_level <= max_congestion {
+                return Ok(());
+            }
+            if started.elapsed() + CONGESTION_POLL_INTERVAL > self.congestion_wait {
+                return Err(SendTransactionError::Congested {
+                    shard_id: congestion.shard_id,
+                    congestion_level: congestion.congestion_level,
+                });
+            }
+            log::debug!(
+                "shard {} of {} is congested, at level {}, waiting",
+                congestion.shard_id,
+                self.receiver_id,
+                congestion.congestion_level
+            );
+            tokio::time::sleep(CONGESTION_POLL_INTERVAL).await;
+            // the shard of the receiver is looked up once, polls only view its congestion
+            congestion = self
+                .client
+                .shard_congestion(congestion.shard_id)
+                .await
+                .map_err(SendTransactionError::CongestionError)?;
+        }
+    }
+
+    async fn sign_with(
+        &self,
+        context: TransactionContext,
+    ) -> Result<SignedTransaction, SignerError> {
+        let transaction = Transaction::V0(TransactionV0 {
+            signer_id: self.signer.account_id().clone(),
+            public_key: self.signer.public_key().clone(),
+            nonce: context.nonce,
+            receiver_id: self.receiver_id.clone(),
+            block_hash: context.block_hash,
+            actions: self.actions.clone(),
+        });
+        let message = borsh::to_vec(&transaction).map_err(SignerError::new)?;
+        let signature = self.signer.sign(&message).await?;
+        Ok(SignedTransaction::new(signature, transaction))
+    }
+}
+
+impl fmt::Debug for TransactionBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransactionBuilder")
+            .field("client", &self.client)
+            .field("signer_id", self.signer.account_id())
+            .field("public_key", self.signer.public_key())
+            .field("receiver_id", &self.receiver_id)
+            .field("actions", &self.actions)
+            .field("wait_until", &self.wait_until)
+            .field("nonces", &self.nonces)
+            .field("nonce_retries", &self.nonce_retries)
+            .field("gas_retries", &self.gas_retries)
+            .field("deadline", &self.deadline)
+            .field("max_congestion", &self.max_congestion)
+            .field("congestion_wait", &self.congestion_wait)
+            .finish()
+    }
+}
+
+/// Returns true if the transaction of `response` failed for running out of the gas attached.
+fn is_out_of_gas(response: &RpcTransactionResponse) -> bool {
+    response
+        .final_execution_outcome
+        .as_ref()
+        .and_then(FunctionCallFailure::from_outcome)
+        == Some(FunctionCallFailure::GasExceeded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use near_crypto::{InMemorySigner, KeyType, SecretKey};
+    use near_primitives::hash::CryptoHash;
+    use near_primitives::transaction::{FunctionCallAction, TransferAction};
+
+    use crate::errors::JsonRpcServerError;
+
+    #[tokio::test]
+    async fn transactions_are_signed_by_the_signer() {
+        let signer = InMemorySigner::from_secret_key(
+            "alice.near".parse().unwrap(),
+            SecretKey::from_random(KeyType::ED25519),
+        );
+        let builder = JsonRpcClient::connect("http://localhost:3030")
+            .transaction(signer.clone(), "bob.near".parse().unwrap())
+            .action(Action::Transfer(TransferAction { deposit: 1 }));
+
+        let signed = builder
+            .sign_with(TransactionContext {
+                nonce: 8,
+                block_hash: CryptoHash::default(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(signed.transaction.signer_id().as_str(), "alice.near");
+        assert_eq!(signed.transaction.receiver_id().as_str(), "bob.near");
+        assert_eq!(signed.transaction.nonce(), 8);
+        assert_eq!(signed.transaction.actions().len(), 1);
+        assert!(signed
+            .signature
+            .verify(signed.get_hash().as_ref(), &signer.public_key()));
+    }
+
+    #[tokio::test]
+    async fn congestion_is_checked_before_signing() {
+        let signer = InMemorySigner::from_secret_key(
+            "alice.near".parse().unwrap(),
+            SecretKey::from_random(KeyType::ED25519),
+        );
+        let err = JsonRpcClient::connect("http://localhost:1")
+            .transaction(signer, "bob.near".parse().unwrap())
+            .action(Action::Transfer(TransferAction { deposit: 1 }))
+            .max_congestion(0.5)
+            .send()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SendTransactionError::CongestionError(_)));
+    }
+
+    #[test]
+    fn gas_is_doubled_up_to_the_limit() {
+        let signer = InMemorySigner::from_secret_key(
+            "alice.near".parse().unwrap(),
+            SecretKey::from_random(KeyType::ED25519),
+        );
+        let call = |gas| {
+            Action::FunctionCall(Box::new(FunctionCallAction {
+                method_name: "increment".to_string(),
+                args: vec![],
+                gas,
+                deposit: 0,
+            }))
+        };
+        let client = JsonRpcClient::connect("http://localhost:3030");
+        let mut builder = client
+            .transaction(signer.clone(), "counter.near".parse().unwrap())
+            .actions([call(30_000_000_000_000), call(60_000_000_000_000)]);
+
+        assert_eq!(builder.bump_gas(), Some(180_000_000_000_000));
+        assert_eq!(builder.bump_gas(), Some(MAX_GAS));
+        assert_eq!(builder.bump_gas(), None);
+        assert_eq!(
+            builder.actions,
+            [call(100_000_000_000_000), call(200_000_000_000_000)]
+        );
+
+        let mut builder = client
+            .transaction(signer, "counter.near".parse().unwrap())
+            .actions([call(Gas::MAX), call(Gas::MAX)]);
+        assert_eq!(builder.bump_gas(), None);
+    }
+
+    #[test]
+    fn timeouts_are_pending() {
+        let err = |handler_error| {
+            JsonRpcError::ServerError(JsonRpcServerError::HandlerError(handler_error))
+        };
+        assert!(is_pending(&err(RpcTransactionError::TimeoutError)));
+        assert!(is_pending(&err(RpcTransactionError::UnknownTransaction {
+            requested_transaction_hash: CryptoHash::default(),
+        })));
+        assert!(!is_pending(&err(RpcTransactionError::InternalError {
+            debug_info: "shutting down".to_string(),
+        })));
+    }
+}
@@ -0,0 +1,491 @@
+//! HTTP transport backends.
+//!
+//! The client speaks to the server through either `reqwest` (the default) or a minimal
+//! client built directly on `hyper` (`hyper-transport` feature). Everything outside this
+//! module only deals with `http` types and [`HttpError`].
+use std::time::Duration;
+
+use bytes::{Bytes, BytesMut};
+use http::header::{HeaderMap, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE, USER_AGENT};
+use http::{Method, StatusCode};
+
+use crate::errors::HttpError;
+use crate::{proxy, tls};
+
+/// Settings for the web client of a transport backend.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Config {
+    pub(crate) pool_max_idle_per_host: Option<usize>,
+    pub(crate) pool_idle_timeout: Option<Option<Duration>>,
+    pub(crate) tcp_keepalive: Option<Option<Duration>>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) root_certificates: Vec<tls::Certificate>,
+    pub(crate) identity: Option<tls::Identity>,
+    pub(crate) proxies: Vec<proxy::Proxy>,
+    pub(crate) user_agent: Option<HeaderValue>,
+    pub(crate) backend: Backend,
+}
+
+#[derive(Eq, Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Backend {
+    #[cfg(feature = "reqwest")]
+    Reqwest,
+    #[cfg(feature = "hyper-transport")]
+    Hyper,
+}
+
+impl Default for Backend {
+    #[cfg(feature = "reqwest")]
+    fn default() -> Self {
+        Self::Reqwest
+    }
+
+    #[cfg(not(feature = "reqwest"))]
+    fn default() -> Self {
+        Self::Hyper
+    }
+}
+
+/// The `User-Agent` sent when none is configured, e.g. `near-jsonrpc-client/x.y.z`.
+const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Most bytes reserved for a body up front, as its `Content-Length` is only a claim of the server.
+const MAX_PREALLOCATION: usize = 64 * 1024;
+
+fn default_headers(config: &Config) -> HeaderMap {
+    let mut headers = HeaderMap::with_capacity(2);
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(
+        USER_AGENT,
+        config
+            .user_agent
+            .clone()
+            .unwrap_or_else(|| HeaderValue::from_static(DEFAULT_USER_AGENT)),
+    );
+    headers
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum Transport {
+    #[cfg(feature = "reqwest")]
+    Reqwest(reqwest::Client),
+    #[cfg(feature = "hyper-transport")]
+    Hyper(Box<hyper_backend::Client>),
+}
+
+impl Transport {
+    pub(crate) fn new(config: Config) -> Result<Self, HttpError> {
+        match config.backend {
+            #[cfg(feature = "reqwest")]
+            Backend::Reqwest => {
+                let mut builder =
+                    reqwest::Client::builder().default_headers(default_headers(&config));
+                if let Some(max) = config.pool_max_idle_per_host {
+                    builder = builder.pool_max_idle_per_host(max);
+                }
+                if let Some(timeout) = config.pool_idle_timeout {
+                    builder = builder.pool_idle_timeout(timeout);
+                }
+                if let Some(interval) = config.tcp_keepalive {
+                    builder = builder.tcp_keepalive(interval);
+                }
+                if let Some(timeout) = config.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                builder = reqwest_tls::configure(builder, &config)?;
+                for proxy in &config.proxies {
+                    builder = builder.proxy(reqwest_proxy(proxy)?);
+                }
+                Ok(Self::Reqwest(builder.build()?))
+            }
+            #[cfg(feature = "hyper-transport")]
+            Backend::Hyper => Ok(Self::Hyper(Box::new(hyper_backend::Client::new(&config)?))),
+        }
+    }
+
+    pub(crate) async fn post(
+        &self,
+        url: &Result<url::Url, url::ParseError>,
+        headers: HeaderMap,
+        body: Vec<u8>,
+        timeout: Option<Duration>,
+    ) -> Result<Response, HttpError> {
+        let url = url.as_ref().map_err(|err| HttpError::other(*err))?;
+        self.request(Method::POST, url, headers, body, timeout)
+            .await
+    }
+
+    pub(crate) async fn get(
+        &self,
+        url: &url::Url,
+        headers: HeaderMap,
+        timeout: Option<Duration>,
+    ) -> Result<Response, HttpError> {
+        self.request(Method::GET, url, headers, Vec::new(), timeout)
+            .await
+    }
+
+    async fn request(
+        &self,
+        method: Method,
+        url: &url::Url,
+        headers: HeaderMap,
+        body: Vec<u8>,
+        timeout: Option<Duration>,
+    ) -> Result<Response, HttpError> {
+        match self {
+            #[cfg(feature = "reqwest")]
+            Self::Reqwest(client) => {
+                let mut request = client
+                    .request(method, url.clone())
+                    .headers(headers)
+                    .body(body);
+                if let Some(timeout) = timeout {
+                    request = request.timeout(timeout);
+                }
+                let mut response = request.send().await?;
+                Ok(Response::new(
+                    response.status(),
+                    std::mem::take(response.headers_mut()),
+                    Body::Reqwest(response),
+                ))
+            }
+            #[cfg(feature = "hyper-transport")]
+            Self::Hyper(client) => client.request(method, url, headers, body, timeout).await,
+        }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+fn reqwest_proxy(proxy: &proxy::Proxy) -> Result<reqwest::Proxy, HttpError> {
+    let mut reqwest_proxy = match proxy.scope {
+        proxy::Scope::All => reqwest::Proxy::all(&proxy.url)?,
+        proxy::Scope::Http => reqwest::Proxy::http(&proxy.url)?,
+        proxy::Scope::Https => reqwest::Proxy::https(&proxy.url)?,
+    };
+    if let Some((username, password)) = &proxy.auth {
+        if proxy.url.starts_with("socks4") {
+            return Err(HttpError::other(
+                "SOCKS4 proxies don't support authentication",
+            ));
+        }
+        reqwest_proxy = reqwest_proxy.basic_auth(username, password);
+    }
+    Ok(reqwest_proxy)
+}
+
+#[cfg(all(
+    feature = "reqwest",
+    any(feature = "native-tls", feature = "rustls-tls")
+))]
+mod reqwest_tls {
+    use super::*;
+
+    pub(super) fn configure(
+        mut builder: reqwest::ClientBuilder,
+        config: &Config,
+    ) -> Result<reqwest::ClientBuilder, HttpError> {
+        for cert in &config.root_certificates {
+            builder = builder.add_root_certificate(match cert.encoding {
+                tls::Encoding::Pem => reqwest::Certificate::from_pem(&cert.data)?,
+                tls::Encoding::Der => reqwest::Certificate::from_der(&cert.data)?,
+            });
+        }
+        if let Some(identity) = &config.identity {
+            builder = builder.identity(self::identity(identity)?);
+        }
+        Ok(builder)
+    }
+
+    #[cfg(feature = "native-tls")]
+    fn identity(identity: &tls::Identity) -> Result<reqwest::Identity, HttpError> {
+        Ok(reqwest::Identity::from_pkcs8_pem(
+            &identity.cert,
+            &identity.key,
+        )?)
+    }
+
+    #[cfg(not(feature = "native-tls"))]
+    fn identity(identity: &tls::Identity) -> Result<reqwest::Identity, HttpError> {
+        let pem = [&identity.cert[..], b"\n", &identity.key[..]].concat();
+        Ok(reqwest::Identity::from_pem(&pem)?)
+    }
+}
+
+#[cfg(all(
+    feature = "reqwest",
+    not(any(feature = "native-tls", feature = "rustls-tls"))
+))]
+mod reqwest_tls {
+    use super::*;
+
+    pub(super) fn configure(
+        builder: reqwest::ClientBuilder,
+        config: &Config,
+    ) -> Result<reqwest::ClientBuilder, HttpError> {
+        if !config.root_certificates.is_empty() || config.identity.is_some() {
+            return Err(no_tls_backend());
+        }
+        Ok(builder)
+    }
+}
+
+#[allow(dead_code)]
+fn no_tls_backend() -> HttpError {
+    HttpError::other(
+        "TLS configuration requires either the `native-tls`, `rustls-tls` or `hyper-tls` feature",
+    )
+}
+
+/// A response whose body is yet to be read.
+pub(crate) struct Response {
+    pub(crate) status: StatusCode,
+    pub(crate) headers: HeaderMap,
+    content_length: Option<usize>,
+    body: Body,
+}
+
+enum Body {
+    #[cfg(feature = "reqwest")]
+    Reqwest(reqwest::Response),
+    #[cfg(feature = "hyper-transport")]
+    Hyper(hyper_backend::Body),
+}
+
+/// Errors returned while reading a response body.
+pub(crate) enum ReadError {
+    Http(HttpError),
+    TooLarge,
+}
+
+impl From<HttpError> for ReadError {
+    fn from(err: HttpError) -> Self {
+        Self::Http(err)
+    }
+}
+
+impl Response {
+    fn new(status: StatusCode, headers: HeaderMap, body: Body) -> Self {
+        let content_length = headers
+            .get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok()?.parse().ok());
+        Self {
+            status,
+            headers,
+            content_length,
+            body,
+        }
+    }
+
+    /// Reads the whole body, failing as soon as it's known to exceed `limit` bytes.
+    pub(crate) async fn bytes(mut self, limit: Option<usize>) -> Result<Bytes, ReadError> {
+        let limit = limit.unwrap_or(usize::MAX);
+        let content_length = self.content_length.unwrap_or(0);
+        if content_length > limit {
+            return Err(ReadError::TooLarge);
+        }
+
+        let mut body = BytesMut::with_capacity(content_length.min(MAX_PREALLOCATION));
+        while let Some(chunk) = self.chunk().await.map_err(HttpError::while_reading)? {
+            if chunk.len() > limit - body.len() {
+                return Err(ReadError::TooLarge);
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body.freeze())
+    }
+
+    /// Reads the body until more than `limit` bytes are received, ignoring read errors.
+    pub(crate) async fn prefix(mut self, limit: usize) -> Bytes {
+        let mut body = BytesMut::new();
+        while body.len() <= limit {
+            match self.chunk().await {
+                Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+                _ => break,
+            }
+        }
+        body.freeze()
+    }
+
+    async fn chunk(&mut self) -> Result<Option<Bytes>, HttpError> {
+        match &mut self.body {
+            #[cfg(feature = "reqwest")]
+            Body::Reqwest(response) => Ok(response.chunk().await?),
+            #[cfg(feature = "hyper-transport")]
+            Body::Hyper(body) => body.chunk().await,
+        }
+    }
+}
+
+#[cfg(feature = "hyper-transport")]
+mod hyper_backend {
+    use std::future::Future;
+
+    use http_body_util::{BodyExt, Full};
+    use hyper_util::client::legacy::connect::HttpConnector;
+    use hyper_util::rt::{TokioExecutor, TokioTimer};
+    use tokio::time::Instant;
+
+    use super::*;
+
+    #[cfg(feature = "hyper-tls")]
+    type Connector = hyper_tls::HttpsConnector<HttpConnector>;
+    #[cfg(not(feature = "hyper-tls"))]
+    type Connector = HttpConnector;
+
+    #[derive(Clone, Debug)]
+    pub(crate) struct Client {
+        client: hyper_util::client::legacy::Client<Connector, Full<Bytes>>,
+        default_headers: HeaderMap,
+    }
+
+    pub(crate) struct Body {
+        body: hyper::body::Incoming,
+        deadline: Option<Instant>,
+    }
+
+    /// Runs `future` to completion, unless `deadline` passes first.
+    async fn until<T, E>(
+        deadline: Option<Instant>,
+        future: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, HttpError>
+    where
+        E: Into<HttpError>,
+    {
+        match deadline {
+            Some(deadline) => match tokio::time::timeout_at(deadline, future).await {
+                Ok(result) => result.map_err(Into::into),
+                Err(elapsed) => Err(HttpError::timeout(elapsed)),
+            },
+            None => future.await.map_err(Into::into),
+        }
+    }
+
+    impl From<hyper_util::client::legacy::Error> for HttpError {
+        fn from(err: hyper_util::client::legacy::Error) -> Self {
+            if err.is_connect() {
+                HttpError::connect(err)
+            } else {
+                HttpError::other(err)
+            }
+        }
+    }
+
+    impl From<hyper::Error> for HttpError {
+        fn from(err: hyper::Error) -> Self {
+            if err.is_timeout() {
+                HttpError::timeout(err)
+            } else {
+                HttpError::other(err)
+            }
+        }
+    }
+
+    impl Client {
+        pub(crate) fn new(config: &Config) -> Result<Self, HttpError> {
+            if !config.proxies.is_empty() {
+                return Err(HttpError::other(
+                    "proxies are not supported by the hyper transport",
+                ));
+            }
+
+            let mut http = HttpConnector::new();
+            if let Some(interval) = config.tcp_keepalive {
+                http.set_keepalive(interval);
+            }
+            http.set_connect_timeout(config.connect_timeout);
+
+            #[cfg(feature = "hyper-tls")]
+            let connector = {
+                let mut tls = native_tls::TlsConnector::builder();
+                for cert in &config.root_certificates {
+                    tls.add_root_certificate(
+                        match cert.encoding {
+                            tls::Encoding::Pem => native_tls::Certificate::from_pem(&cert.data),
+                            tls::Encoding::Der => native_tls::Certificate::from_der(&cert.data),
+                        }
+                        .map_err(HttpError::other)?,
+                    );
+                }
+                if let Some(identity) = &config.identity {
+                    tls.identity(
+                        native_tls::Identity::from_pkcs8(&identity.cert, &identity.key)
+                            .map_err(HttpError::other)?,
+                    );
+                }
+                let tls = tls.build().map_err(HttpError::other)?;
+                http.enforce_http(false);
+                hyper_tls::HttpsConnector::from((http, tls.into()))
+            };
+            #[cfg(not(feature = "hyper-tls"))]
+            let connector = {
+                if !config.root_certificates.is_empty() || config.identity.is_some() {
+                    return Err(no_tls_backend());
+                }
+                http
+            };
+
+            let mut builder = hyper_util::client::legacy::Client::builder(TokioExecutor::new());
+            builder.pool_timer(TokioTimer::new());
+            if let Some(max) = config.pool_max_idle_per_host {
+                builder.pool_max_idle_per_host(max);
+            }
+            if let Some(timeout) = config.pool_idle_timeout {
+                builder.pool_idle_timeout(timeout);
+            }
+
+            Ok(Self {
+                client: builder.build(connector),
+                default_headers: default_headers(config),
+            })
+        }
+
+        pub(crate) async fn request(
+            &self,
+            method: Method,
+            url: &url::Url,
+            mut headers: HeaderMap,
+            body: Vec<u8>,
+            timeout: Option<Duration>,
+        ) -> Result<Response, HttpError> {
+            let mut request = http::Request::builder()
+                .method(method)
+                .uri(url.as_str())
+                .body(Full::new(Bytes::from(body)))
+                .map_err(HttpError::other)?;
+            for (name, value) in &self.default_headers {
+                if !headers.contains_key(name) {
+                    headers.insert(name, value.clone());
+                }
+            }
+            *request.headers_mut() = headers;
+
+            // timeouts too long to be represented are no deadline at all
+            let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+            let response = until(deadline, self.client.request(request)).await?;
+            let (parts, body) = response.into_parts();
+
+            Ok(Response::new(
+                parts.status,
+                parts.headers,
+                super::Body::Hyper(Body { body, deadline }),
+            ))
+        }
+    }
+
+    impl Body {
+        pub(crate) async fn chunk(&mut self) -> Result<Option<Bytes>, HttpError> {
+            loop {
+                let next_frame = async { self.body.frame().await.transpose() };
+                let frame = match until(self.deadline, next_frame).await? {
+                    Some(frame) => frame,
+                    None => return Ok(None),
+                };
+                // trailers carry nothing of interest, skip to the next data frame
+                if let Ok(data) = frame.into_data() {
+                    return Ok(Some(data));
+                }
+            }
+        }
+    }
+}
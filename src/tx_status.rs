@@ -0,0 +1,308 @@
+//! Following the execution of transactions.
+//!
+//! A [`TxStatusPoller`] is derived from a [`JsonRpcClient`] with
+//! [`JsonRpcClient::poll_transaction`]. It polls the status of a transaction with the `tx`
+//! method, yielding a [`Stream`] of its responses each time the execution status of the
+//! transaction changes, e.g. from `INCLUDED` to `EXECUTED_OPTIMISTIC` to `FINAL`.
+//!
+//! Transactions submitted asynchronously, e.g. with the `broadcast_tx_async` method, can be
+//! dropped before making it into a chunk. Given the signed transaction, the poller
+//! [rebroadcasts](TxStatusPoller::rebroadcast) it while the node doesn't know it.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use futures_util::StreamExt;
+//! use near_jsonrpc_client::{Deadline, JsonRpcClient};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let mut updates = client
+//!     .poll_transaction(
+//!         "9FtHUFBQsZ2MG77K3x3MJ9wjX3UT8zE1TczCrhZEcG8U".parse()?,
+//!         "miraclx.testnet".parse()?,
+//!     )
+//!     .exponential_backoff(Duration::from_millis(500), Duration::from_secs(5))
+//!     .deadline(Deadline::after(Duration::from_secs(60)))
+//!     .updates();
+//!
+//! while let Some(response) = updates.next().await {
+//!     println!("status: {:?}", response?.final_execution_status);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::stream::{self, BoxStream, StreamExt};
+use near_primitives::hash::CryptoHash;
+use near_primitives::transaction::SignedTransaction;
+use near_primitives::types::AccountId;
+use near_primitives::views::TxExecutionStatus;
+
+use crate::errors::JsonRpcError;
+use crate::methods::send_tx::RpcSendTransactionRequest;
+use crate::methods::tx::{
+    RpcTransactionError, RpcTransactionResponse, RpcTransactionStatusRequest, TransactionInfo,
+};
+use crate::{Deadline, JsonRpcClient};
+
+pub use futures_util::Stream;
+
+type Backoff = dyn Fn(u32) -> Duration + Send + Sync;
+
+/// Polls the status of a transaction.
+#[derive(Clone)]
+pub struct TxStatusPoller {
+    client: JsonRpcClient,
+    transaction_info: TransactionInfo,
+    wait_until: TxExecutionStatus,
+    backoff: Arc<Backoff>,
+    deadline: Option<Deadline>,
+    rebroadcast: Option<(SignedTransaction, Duration)>,
+}
+
+impl TxStatusPoller {
+    pub(crate) fn new(
+        client: JsonRpcClient,
+        tx_hash: CryptoHash,
+        sender_account_id: AccountId,
+    ) -> Self {
+        Self {
+            client,
+            transaction_info: TransactionInfo::TransactionId {
+                tx_hash,
+                sender_account_id,
+            },
+            wait_until: TxExecutionStatus::Final,
+            backoff: Arc::new(|_| Duration::from_secs(1)),
+            deadline: None,
+            rebroadcast: None,
+        }
+    }
+
+    /// Stop polling once the transaction reaches the execution status `wait_until`, or one
+    /// implying it.
+    ///
+    /// Defaults to [`TxExecutionStatus::Final`].
+    pub fn wait_until(mut self, wait_until: TxExecutionStatus) -> Self {
+        self.wait_until = wait_until;
+        self
+    }
+
+    /// Poll every `interval`. This is the default, with a 1s interval.
+    pub fn interval(self, interval: Duration) -> Self {
+        self.backoff(move |_| interval)
+    }
+
+    /// Poll after `initial`, doubling the delay for every poll not changing the status, up to
+    /// `max`.
+    pub fn exponential_backoff(self, initial: Duration, max: Duration) -> Self {
+        self.backoff(move |attempt| {
+            initial
+                .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+                .min(max)
+        })
+    }
+
+    /// Poll after the delay returned by `backoff`, given the number of polls since the status
+    /// last changed, starting from 1.
+    pub fn backoff(mut self, backoff: impl Fn(u32) -> Duration + Send + Sync + 'static) -> Self {
+        self.backoff = Arc::new(backoff);
+        self
+    }
+
+    /// Stop polling once `deadline` expires, returning a
+    /// [`DeadlineExceeded`](crate::errors::RpcTransportError::DeadlineExceeded) error.
+    pub fn deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Send `signed_transaction`, the transaction polled, again each time the node still doesn't
+    /// know it `after` it was last sent.
+    ///
+    /// The transaction is rebroadcast until the node rejects it, at the latest once its block hash
+    /// is too old and the transaction expired. That error is then yielded.
+    pub fn rebroadcast(mut self, signed_transaction: SignedTransaction, after: Duration) -> Self {
+        self.rebroadcast = Some((signed_transaction, after));
+        self
+    }
+
+    /// Poll the status of the transaction, yielding its response each time its execution status
+    /// changes.
+    ///
+    /// The stream ends once the transaction reaches the expected execution status, or after
+    /// yielding an error. Errors raised while the transaction isn't known to the node yet aren't
+    /// yielded, and the transaction is polled again.
+    pub fn updates(
+        self,
+    ) -> BoxStream<'static, Result<RpcTransactionResponse, JsonRpcError<RpcTransactionError>>> {
+        let client = match self.deadline {
+            Some(deadline) => {
+                let deadline = self
+                    .client
+                    .deadline()
+                    .map_or(deadline, |own| own.min(deadline));
+                self.client.clone().with_deadline(deadline)
+            }
+            None => self.client.clone(),
+        };
+        let state = Some((self, client, None::<TxExecutionStatus>));
+        stream::unfold(state, |state| async move {
+            let (poller, client, mut status) = state?;
+            // only the first poll isn't delayed
+            let mut attempt = u32::from(status.is_some());
+            let mut sent_at = Instant::now();
+            loop {
+                if attempt > 0 {
+                    let delay = (poller.backoff)(attempt);
+                    let delay = match client.deadline() {
+                        Some(deadline) => deadline.budget(Some(delay)).unwrap_or_default(),
+                        None => delay,
+                    };
+                    tokio::time::sleep(delay).await;
+                }
+                attempt += 1;
+
+                let response = client
+                    .call(RpcTransactionStatusRequest {
+                        transaction_info: poller.transaction_info.clone(),
+                        wait_until: TxExecutionStatus::None,
+                    })
+                    .await;
+                match response {
+                    Ok(response) if status.as_ref() == Some(&response.final_execution_status) => {}
+                    Ok(response) => {
+                        status = Some(response.final_execution_status.clone());
+                        let state =
+                            (!reached(&response.final_execution_status, &poller.wait_until))
+                                .then_some((poller, client, status));
+                        return Some((Ok(response), state));
+                    }
+                    Err(err) if crate::transaction::is_pending(&err) => {
+                        let Some((signed_transaction, after)) = &poller.rebroadcast else {
+                            continue;
+                        };
+                        if !matches!(
+                            err.handler_error(),
+                            Some(RpcTransactionError::UnknownTransaction { .. })
+                        ) || sent_at.elapsed() < *after
+                        {
+                            continue;
+                        }
+                        log::debug!(
+                            "transaction still unknown after {:?}, rebroadcasting it",
+                            sent_at.elapsed()
+                        );
+                        let response = client
+                            .call(RpcSendTransactionRequest {
+                                signed_transaction: signed_transaction.clone(),
+                                wait_until: TxExecutionStatus::None,
+                            })
+                            .await;
+                        match response {
+                            Err(err) if !crate::transaction::is_pending(&err) => {
+                                return Some((Err(err), None))
+                            }
+                            _ => sent_at = Instant::now(),
+                        }
+                    }
+                    Err(err) => return Some((Err(err), None)),
+                }
+            }
+        })
+        .boxed()
+    }
+
+    /// Poll the status of the transaction until it reaches the expected execution status,
+    /// returning its last response.
+    pub async fn wait(self) -> Result<RpcTransactionResponse, JsonRpcError<RpcTransactionError>> {
+        let mut updates = self.updates();
+        let mut last = None;
+        while let Some(response) = updates.next().await {
+            last = Some(response?);
+        }
+        Ok(last.expect("the updates end with a response or an error"))
+    }
+}
+
+impl fmt::Debug for TxStatusPoller {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TxStatusPoller")
+            .field("client", &self.client)
+            .field("transaction_info", &self.transaction_info)
+            .field("wait_until", &self.wait_until)
+            .field("deadline", &self.deadline)
+            .field(
+                "rebroadcast_after",
+                &self.rebroadcast.as_ref().map(|(_, after)| after),
+            )
+            .finish()
+    }
+}
+
+impl JsonRpcClient {
+    /// Poll the status of the transaction `tx_hash`, signed by `sender_account_id`.
+    ///
+    /// See the [`tx_status`](crate::tx_status) module for details.
+    pub fn poll_transaction(
+        &self,
+        tx_hash: CryptoHash,
+        sender_account_id: AccountId,
+    ) -> TxStatusPoller {
+        TxStatusPoller::new(self.clone(), tx_hash, sender_account_id)
+    }
+}
+
+/// Returns true if a transaction with the execution status `status` has reached `wait_until`.
+///
+/// `EXECUTED_OPTIMISTIC` and `INCLUDED_FINAL` don't imply each other, `EXECUTED` implies both.
+fn reached(status: &TxExecutionStatus, wait_until: &TxExecutionStatus) -> bool {
+    fn rank(status: &TxExecutionStatus) -> u8 {
+        match status {
+            TxExecutionStatus::None => 0,
+            TxExecutionStatus::Included => 1,
+            TxExecutionStatus::ExecutedOptimistic | TxExecutionStatus::IncludedFinal => 2,
+            TxExecutionStatus::Executed => 3,
+            TxExecutionStatus::Final => 4,
+        }
+    }
+    status == wait_until || rank(status) > rank(wait_until)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statuses_imply_earlier_ones() {
+        use TxExecutionStatus::*;
+
+        assert!(reached(&Final, &Executed));
+        assert!(reached(&Executed, &IncludedFinal));
+        assert!(reached(&Executed, &ExecutedOptimistic));
+        assert!(reached(&IncludedFinal, &IncludedFinal));
+        assert!(!reached(&IncludedFinal, &ExecutedOptimistic));
+        assert!(!reached(&ExecutedOptimistic, &IncludedFinal));
+        assert!(!reached(&Included, &Final));
+    }
+
+    #[test]
+    fn backoff_is_exponential() {
+        let poller = JsonRpcClient::connect("http://localhost:3030")
+            .poll_transaction(CryptoHash::default(), "alice.near".parse().unwrap())
+            .exponential_backoff(Duration::from_millis(100), Duration::from_millis(300));
+        let delays = (1..=4).map(|attempt| (poller.backoff)(attempt));
+        assert_eq!(
+            delays.collect::<Vec<_>>(),
+            [100, 200, 300, 300].map(Duration::from_millis)
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! Typed views of accounts and contracts.
+//!
+//! The `query` method returns a different [`QueryResponseKind`] for every kind of
+//! [`QueryRequest`]. The view methods of [`JsonRpcClient`] build the query, check the kind of the
+//! response and unwrap it, reporting any mismatch as a [`ViewError`].
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use near_jsonrpc_client::JsonRpcClient;
+//! use near_primitives::types::{BlockReference, Finality};
+//! use serde_json::json;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonRpcClient::connect("https://rpc.testnet.near.org");
+//!
+//! let status = client
+//!     .view_function::<serde_json::Value>(
+//!         "nosedive.testnet".parse()?,
+//!         "status",
+//!         json!({ "account_id": "miraclx.testnet" }),
+//!         BlockReference::Finality(Finality::Final),
+//!     )
+//!     .await?;
+//!
+//! println!("{} at block #{}", status.result, status.block_height);
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::BTreeMap;
+
+use near_crypto::PublicKey;
+use near_jsonrpc_primitives::types::query::{QueryResponseKind, RpcQueryResponse};
+use near_primitives::hash::CryptoHash;
+use near_primitives::types::{AccountId, BlockHeight, BlockReference, FunctionArgs, StoreKey};
+use near_primitives::views::{
+    AccessKeyInfoView, AccessKeyPermissionView, AccessKeyView, AccountView, QueryRequest,
+    ViewStateResult,
+};
+use serde::de::DeserializeOwned;
+
+use crate::errors::ViewError;
+use crate::{methods, JsonRpcClient};
+
+/// The result of a view function call.
+#[derive(Clone, Debug)]
+pub struct ViewFunctionResult<T> {
+    /// The value returned by the function.
+    pub result: T,
+    /// The logs emitted by the function.
+    pub logs: Vec<String>,
+    pub block_height: BlockHeight,
+    pub block_hash: CryptoHash,
+}
+
+/// Which access keys to list, by their permission.
+#[derive(Eq, Copy, Clone, Debug, Default, PartialEq)]
+pub enum AccessKeyFilter {
+    /// All access keys.
+    #[default]
+    All,
+    /// Only full access keys.
+    FullAccess,
+    /// Only keys restricted to calling functions.
+    FunctionCall,
+}
+
+impl AccessKeyFilter {
+    /// Returns true if keys with the `permission` pass the filter.
+    pub fn matches(&self, permission: &AccessKeyPermissionView) -> bool {
+        match self {
+            Self::All => true,
+            Self::FullAccess => matches!(permission, AccessKeyPermissionView::FullAccess),
+            Self::FunctionCall => {
+                matches!(permission, AccessKeyPermissionView::FunctionCall { .. })
+            }
+        }
+    }
+}
+
+impl JsonRpcClient {
+    /// Call a view function of a contract, with JSON arguments, deserializing its JSON result.
+    ///
+    /// See the [`views`](crate::views) module documentation for an example.
+    pub async fn view_function<T: DeserializeOwned>(
+        &self,
+        contract_id: AccountId,
+        method_name: impl Into<String>,
+        args: serde_json::Value,
+        block_reference: BlockReference,
+    ) -> Result<ViewFunctionResult<T>, ViewError> {
+        let response = self
+            .query(
+                block_reference,
+                QueryRequest::CallFunction {
+                    account_id: contract_id,
+                    method_name: method_name.into(),
+                    args: FunctionArgs::from(args.to_string().into_bytes()),
+                },
+            )
+            .await?;
+        view_function_result(response)
+    }
+
+    /// Get the account `account_id`.
+    pub async fn view_account(
+        &self,
+        account_id: AccountId,
+        block_reference: BlockReference,
+    ) -> Result<AccountView, ViewError> {
+        let response = self
+            .query(block_reference, QueryRequest::ViewAccount { account_id })
+            .await?;
+        match response.kind {
+            QueryResponseKind::ViewAccount(account) => Ok(account),
+            kind => Err(unexpected(kind, "ViewAccount")),
+        }
+    }
+
+    /// Get the access key `public_key` of the account `account_id`, along with the hash and height
+    /// of the block it was viewed at.
+    ///
+    /// The block hash is recent enough to sign transactions with, when viewing the latest block.
+    pub async fn view_access_key(
+        &self,
+        account_id: AccountId,
+        public_key: PublicKey,
+        block_reference: BlockReference,
+    ) -> Result<(AccessKeyView, CryptoHash, BlockHeight), ViewError> {
+        let response = self
+            .query(
+                block_reference,
+                QueryRequest::ViewAccessKey {
+                    account_id,
+                    public_key,
+                },
+            )
+            .await?;
+        match response.kind {
+            QueryResponseKind::AccessKey(access_key) => {
+                Ok((access_key, response.block_hash, response.block_height))
+            }
+            kind => Err(unexpected(kind, "AccessKey")),
+        }
+    }
+
+    /// List the access keys of the account `account_id` passing the `filter`.
+    pub async fn view_access_key_list(
+        &self,
+        account_id: AccountId,
+        filter: AccessKeyFilter,
+        block_reference: BlockReference,
+    ) -> Result<Vec<AccessKeyInfoView>, ViewError> {
+        let response = self
+            .query(
+                block_reference,
+                QueryRequest::ViewAccessKeyList { account_id },
+            )
+            .await?;
+        match response.kind {
+            QueryResponseKind::AccessKeyList(list) => Ok(list
+                .keys
+                .into_iter()
+                .filter(|key| filter.matches(&key.access_key.permission))
+                .collect()),
+            kind => Err(unexpected(kind, "AccessKeyList")),
+        }
+    }
+
+    /// Get the Wasm code of the contract deployed to `account_id`, along with its hash.
+    pub async fn view_code(
+        &self,
+        account_id: AccountId,
+        block_reference: BlockReference,
+    ) -> Result<(Vec<u8>, CryptoHash), ViewError> {
+        let response = self
+            .query(block_reference, QueryRequest::ViewCode { account_id })
+            .await?;
+        match response.kind {
+            QueryResponseKind::ViewCode(contract) => Ok((contract.code, contract.hash)),
+            kind => Err(unexpected(kind, "ViewCode")),
+        }
+    }
+
+    /// Get the state of the contract deployed to `account_id`, with keys starting with `prefix`.
+    ///
+    /// Nodes refuse to view large states, see [`RpcQueryError::TooLargeContractState`]. Passing an
+    /// empty `prefix` views the whole state.
+    ///
+    /// [`RpcQueryError::TooLargeContractState`]: methods::query::RpcQueryError::TooLargeContractState
+    pub async fn view_state(
+        &self,
+        account_id: AccountId,
+        prefix: &[u8],
+        block_reference: BlockReference,
+    ) -> Result<BTreeMap<Vec<u8>, Vec<u8>>, ViewError> {
+        let response = self
+            .query(
+                block_reference,
+                QueryRequest::ViewState {
+                    account_id,
+                    prefix: StoreKey::from(prefix.to_vec()),
+                    include_proof: false,
+                },
+            )
+            .await?;
+        match response.kind {
+            QueryResponseKind::ViewState(state) => Ok(state_map(state)),
+            kind => Err(unexpected(kind, "ViewState")),
+        }
+    }
+
+    async fn query(
+        &self,
+        block_reference: BlockReference,
+        request: QueryRequest,
+    ) -> crate::MethodCallResult<RpcQueryResponse, methods::query::RpcQueryError> {
+        self.call(methods::query::RpcQueryRequest {
+            block_reference,
+            request,
+        })
+        .await
+    }
+}
+
+fn view_function_result<T: DeserializeOwned>(
+    response: RpcQueryResponse,
+) -> Result<ViewFunctionResult<T>, ViewError> {
+    match response.kind {
+        QueryResponseKind::CallResult(call_result) => Ok(ViewFunctionResult {
+            result: serde_json::from_slice(&call_result.result)
+                .map_err(ViewError::ResultParseError)?,
+            logs: call_result.logs,
+            block_height: response.block_height,
+            block_hash: response.block_hash,
+        }),
+        kind => Err(unexpected(kind, "CallResult")),
+    }
+}
+
+fn state_map(state: ViewStateResult) -> BTreeMap<Vec<u8>, Vec<u8>> {
+    state
+        .values
+        .into_iter()
+        .map(|item| (item.key.into(), item.value.into()))
+        .collect()
+}
+
+fn unexpected(kind: QueryResponseKind, expected: &'static str) -> ViewError {
+    ViewError::UnexpectedResponseKind {
+        expected,
+        actual: match kind {
+            QueryResponseKind::ViewAccount(_) => "ViewAccount",
+            QueryResponseKind::ViewCode(_) => "ViewCode",
+            QueryResponseKind::ViewState(_) => "ViewState",
+            QueryResponseKind::CallResult(_) => "CallResult",
+            QueryResponseKind::AccessKey(_) => "AccessKey",
+            QueryResponseKind::AccessKeyList(_) => "AccessKeyList",
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use near_primitives::views::CallResult;
+
+    fn response(kind: QueryResponseKind) -> RpcQueryResponse {
+        RpcQueryResponse {
+            kind,
+            block_height: 1,
+            block_hash: CryptoHash::default(),
+        }
+    }
+
+    #[test]
+    fn state_is_decoded() -> Result<(), serde_json::Error> {
+        let state: ViewStateResult = serde_json::from_value(serde_json::json!({
+            "values": [
+                { "key": "U1RBVEU=", "value": "AQI=" },
+                { "key": "Y291bnQ=", "value": "Kg==" },
+            ]
+        }))?;
+        assert_eq!(
+            state_map(state),
+            BTreeMap::from([
+                (b"STATE".to_vec(), vec![1, 2]),
+                (b"count".to_vec(), vec![42]),
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn access_keys_are_filtered_by_permission() {
+        let function_call = AccessKeyPermissionView::FunctionCall {
+            allowance: None,
+            receiver_id: "app.near".to_string(),
+            method_names: vec![],
+        };
+        let full_access = AccessKeyPermissionView::FullAccess;
+
+        assert!(AccessKeyFilter::All.matches(&function_call));
+        assert!(AccessKeyFilter::All.matches(&full_access));
+        assert!(AccessKeyFilter::FullAccess.matches(&full_access));
+        assert!(!AccessKeyFilter::FullAccess.matches(&function_call));
+        assert!(AccessKeyFilter::FunctionCall.matches(&function_call));
+        assert!(!AccessKeyFilter::FunctionCall.matches(&full_access));
+    }
+
+    #[test]
+    fn view_function_results_are_deserialized() {
+        let result = view_function_result::<Vec<String>>(response(QueryResponseKind::CallResult(
+            CallResult {
+                result: br#"["alice.near"]"#.to_vec(),
+                logs: vec!["viewed".to_string()],
+            },
+        )))
+        .unwrap();
+        assert_eq!(result.result, ["alice.near"]);
+        assert_eq!(result.logs, ["viewed"]);
+        assert_eq!(result.block_height, 1);
+
+        let err =
+            view_function_result::<u64>(response(QueryResponseKind::CallResult(CallResult {
+                result: b"not json".to_vec(),
+                logs: vec![],
+            })))
+            .unwrap_err();
+        assert!(matches!(err, ViewError::ResultParseError(_)));
+
+        let err = view_function_result::<u64>(response(QueryResponseKind::AccessKeyList(
+            near_primitives::views::AccessKeyList { keys: vec![] },
+        )))
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ViewError::UnexpectedResponseKind {
+                expected: "CallResult",
+                actual: "AccessKeyList"
+            }
+        ));
+    }
+}